@@ -0,0 +1,83 @@
+// nexus-prime-core/src/capabilities.rs - Structured node capabilities
+
+use crate::fabric_proto::fabric::AgentRegistrationRequest;
+use serde::{Deserialize, Serialize};
+
+// Resources a node offers, as reported when it registered
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeCapabilities {
+    pub cpu_cores: u32,
+    pub memory_bytes: u64,
+    pub gpus: u32,
+    pub features: Vec<String>,
+}
+
+impl NodeCapabilities {
+    // The structured registration fields when any is set, otherwise the legacy capabilities string
+    pub fn from_registration(req: &AgentRegistrationRequest) -> Self {
+        let structured = Self {
+            cpu_cores: req.cpu_cores,
+            memory_bytes: req.memory_bytes,
+            gpus: req.gpus,
+            features: req.features.clone(),
+        };
+        if structured == Self::default() {
+            Self::parse(&req.capabilities)
+        } else {
+            structured
+        }
+    }
+
+    // Parse the legacy "CPU:4,RAM:16GB,GPU:1" format. Entries that are not a known
+    // resource with a readable amount are kept as features.
+    pub fn parse(capabilities: &str) -> Self {
+        let mut parsed = Self::default();
+        for entry in capabilities.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            let recognized = match entry.split_once(':') {
+                Some((key, value)) => match key.trim().to_ascii_uppercase().as_str() {
+                    "CPU" => value.trim().parse().map(|cores| parsed.cpu_cores = cores).is_ok(),
+                    "RAM" | "MEM" | "MEMORY" => parse_bytes(value).map(|bytes| parsed.memory_bytes = bytes).is_some(),
+                    "GPU" => value.trim().parse().map(|gpus| parsed.gpus = gpus).is_ok(),
+                    _ => false,
+                },
+                None => false,
+            };
+            if !recognized {
+                parsed.features.push(entry.to_string());
+            }
+        }
+        parsed
+    }
+}
+
+// Minimum resources a node must offer to host an agent; zero places no limit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityRequirement {
+    pub cpu_cores: u32,
+    pub memory_bytes: u64,
+    pub gpus: u32,
+}
+
+impl CapabilityRequirement {
+    pub fn is_met_by(&self, capabilities: &NodeCapabilities) -> bool {
+        capabilities.cpu_cores >= self.cpu_cores
+            && capabilities.memory_bytes >= self.memory_bytes
+            && capabilities.gpus >= self.gpus
+    }
+}
+
+// "16GB", "512MB" or a plain byte count, in binary units
+pub(crate) fn parse_bytes(value: &str) -> Option<u64> {
+    let value = value.trim().to_ascii_uppercase();
+    let digits = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(digits);
+    let multiplier: u64 = match unit.trim() {
+        "" | "B" => 1,
+        "KB" | "K" => 1 << 10,
+        "MB" | "M" => 1 << 20,
+        "GB" | "G" => 1 << 30,
+        "TB" | "T" => 1 << 40,
+        _ => return None,
+    };
+    amount.parse::<u64>().ok()?.checked_mul(multiplier)
+}
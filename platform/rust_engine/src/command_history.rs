@@ -0,0 +1,157 @@
+// nexus-prime-core/src/command_history.rs - Durable, bounded history of issued fabric commands
+
+use crate::fabric_proto::fabric::FabricCommand;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+pub type CommandHistoryResult<T> = Result<T, CommandHistoryError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CommandHistoryError {
+    #[error("Persistence error: {0}")]
+    Persistence(#[from] sled::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandRecord {
+    pub sequence: u64,
+    pub command_id: String,
+    pub command_type: String,
+    pub target_id: String,
+    pub parameters: HashMap<String, String>,
+    pub issued_at: DateTime<Utc>,
+    pub result: Option<Result<(), String>>, // None until the command has been executed
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandHistoryFilter {
+    pub target_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl CommandHistoryFilter {
+    fn matches(&self, record: &CommandRecord) -> bool {
+        self.target_id.as_ref().is_none_or(|target_id| &record.target_id == target_id)
+            && self.since.is_none_or(|since| record.issued_at >= since)
+            && self.until.is_none_or(|until| record.issued_at <= until)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandHistoryPage {
+    pub records: Vec<CommandRecord>, // Newest first
+    pub next_page_token: Option<u64>,
+}
+
+// Issued commands keyed by an increasing sequence number, with an index from command
+// id to sequence so results can be filled in after execution. Only the newest
+// `max_entries` commands are kept.
+#[derive(Clone)]
+pub struct CommandHistory {
+    records: sled::Tree,
+    index: sled::Tree,
+    db: sled::Db,
+    max_entries: usize,
+    len: Arc<AtomicUsize>, // sled's Tree::len is a full scan, so the count is kept here
+}
+
+impl CommandHistory {
+    pub fn open(db: &sled::Db, max_entries: usize) -> CommandHistoryResult<Self> {
+        let records = db.open_tree("command_history")?;
+        Ok(Self {
+            len: Arc::new(AtomicUsize::new(records.len())),
+            records,
+            index: db.open_tree("command_history_index")?,
+            db: db.clone(),
+            max_entries: max_entries.max(1),
+        })
+    }
+
+    pub fn record_issued(&self, command: &FabricCommand) -> CommandHistoryResult<()> {
+        let record = CommandRecord {
+            sequence: self.db.generate_id()?,
+            command_id: command.command_id.clone(),
+            command_type: command.command_type.clone(),
+            target_id: command.target_id.clone(),
+            parameters: command.parameters.clone(),
+            issued_at: Utc::now(),
+            result: None,
+        };
+        let key = record.sequence.to_be_bytes();
+        self.records.insert(key, bincode::serialize(&record)?)?;
+        self.index.insert(record.command_id.as_bytes(), &key)?;
+        self.len.fetch_add(1, Ordering::SeqCst);
+        self.enforce_retention()
+    }
+
+    pub fn record_result(&self, command_id: &str, result: &Result<(), String>) -> CommandHistoryResult<()> {
+        let Some(key) = self.index.get(command_id.as_bytes())? else {
+            return Ok(());
+        };
+        if let Some(bytes) = self.records.get(&key)? {
+            let mut record: CommandRecord = bincode::deserialize(&bytes)?;
+            record.result = Some(result.clone());
+            self.records.insert(key, bincode::serialize(&record)?)?;
+        }
+        Ok(())
+    }
+
+    // Matching commands, newest first. Pass the previous page's `next_page_token` to
+    // continue listing.
+    pub fn list(&self, filter: &CommandHistoryFilter, page_size: usize, page_token: Option<u64>) -> CommandHistoryResult<CommandHistoryPage> {
+        let page_size = page_size.max(1);
+        let upper = page_token.unwrap_or(u64::MAX);
+        let mut records = Vec::new();
+        let mut next_page_token = None;
+        for item in self.records.range(..upper.to_be_bytes()).rev() {
+            let record: CommandRecord = bincode::deserialize(&item?.1)?;
+            if !filter.matches(&record) {
+                continue;
+            }
+            if records.len() == page_size {
+                next_page_token = records.last().map(|last: &CommandRecord| last.sequence);
+                break;
+            }
+            records.push(record);
+        }
+        Ok(CommandHistoryPage { records, next_page_token })
+    }
+
+    fn enforce_retention(&self) -> CommandHistoryResult<()> {
+        while self.len.load(Ordering::SeqCst) > self.max_entries {
+            let Some((key, bytes)) = self.records.pop_min()? else { break };
+            self.len.fetch_sub(1, Ordering::SeqCst);
+            let record: CommandRecord = bincode::deserialize(&bytes)?;
+            // Keep the index entry if a newer command reused the id
+            if self.index.get(record.command_id.as_bytes())?.as_deref() == Some(&key[..]) {
+                self.index.remove(record.command_id.as_bytes())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<CommandRecord> for crate::fabric_proto::fabric::CommandHistoryEntry {
+    fn from(record: CommandRecord) -> Self {
+        let (status, error) = match record.result {
+            None => ("PENDING", String::new()),
+            Some(Ok(())) => ("SUCCEEDED", String::new()),
+            Some(Err(e)) => ("FAILED", e),
+        };
+        Self {
+            command_id: record.command_id,
+            command_type: record.command_type,
+            target_id: record.target_id,
+            parameters: record.parameters,
+            issued_at: record.issued_at.to_rfc3339(),
+            status: status.to_string(),
+            error,
+        }
+    }
+}
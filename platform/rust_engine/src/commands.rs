@@ -0,0 +1,290 @@
+// nexus-prime-core/src/commands.rs - Typed parsing and validation of FabricCommand parameters
+
+use crate::capabilities::{self, CapabilityRequirement};
+use crate::fabric_proto::fabric::FabricCommand;
+use std::collections::HashMap;
+
+pub type CommandParseResult<T> = Result<T, CommandParseError>;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CommandParseError {
+    #[error("Unknown command type: {0}")]
+    UnknownCommand(String),
+    #[error("Expected a {expected} command, got {found}")]
+    WrongCommandType { expected: &'static str, found: String },
+    #[error("{command} is missing required parameter '{parameter}'")]
+    MissingParameter { command: &'static str, parameter: &'static str },
+    #[error("{command} parameter '{parameter}' is invalid: {reason}")]
+    InvalidParameter { command: &'static str, parameter: &'static str, reason: String },
+}
+
+pub const DEPLOY_AGENT: &str = "DEPLOY_AGENT";
+pub const STOP_AGENT: &str = "STOP_AGENT";
+pub const MIGRATE_AGENT: &str = "MIGRATE_AGENT";
+pub const DRAIN_NODE: &str = "DRAIN_NODE";
+pub const UNDRAIN_NODE: &str = "UNDRAIN_NODE";
+pub const ROLLING_UPDATE: &str = "ROLLING_UPDATE";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployAgentParams {
+    pub target_node_id: Option<String>, // Absent to place the agent on the least loaded eligible node
+    pub name: String,
+    pub agent_type: String,
+    pub pinned: bool,
+    pub idempotency_key: Option<String>, // Retries with the same key return the first deploy's agent
+    pub env: HashMap<String, String>, // Agent environment, from `env.<NAME>` parameters
+    pub fleet_id: Option<String>, // Fleet the agent joins, from the `fleet` parameter
+    // What a node needs to be chosen when no target is given, from the `min_cpu_cores`,
+    // `min_memory` and `min_gpus` parameters
+    pub requirement: CapabilityRequirement,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StopAgentParams {
+    pub agent_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MigrateAgentParams {
+    pub agent_id: String,
+    pub destination_node_id: String,
+    pub force: bool, // Migrate even if the agent is pinned
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrainNodeParams {
+    pub node_id: String,
+    pub force: bool, // Also move agents pinned to the node
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndrainNodeParams {
+    pub node_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingUpdateParams {
+    pub fleet_id: String,
+    pub agent_type: String, // Type the fleet's agents are replaced with
+    pub batch_size: usize, // Agents replaced at a time, 1 when absent
+    pub env: HashMap<String, String>, // Environment of the replacement agents
+}
+
+// A FabricCommand whose parameters have been validated
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedCommand {
+    DeployAgent(DeployAgentParams),
+    StopAgent(StopAgentParams),
+    MigrateAgent(MigrateAgentParams),
+    DrainNode(DrainNodeParams),
+    UndrainNode(UndrainNodeParams),
+    RollingUpdate(RollingUpdateParams),
+}
+
+impl TryFrom<&FabricCommand> for TypedCommand {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        match command.command_type.as_str() {
+            DEPLOY_AGENT => DeployAgentParams::try_from(command).map(Self::DeployAgent),
+            STOP_AGENT => StopAgentParams::try_from(command).map(Self::StopAgent),
+            MIGRATE_AGENT => MigrateAgentParams::try_from(command).map(Self::MigrateAgent),
+            DRAIN_NODE => DrainNodeParams::try_from(command).map(Self::DrainNode),
+            UNDRAIN_NODE => UndrainNodeParams::try_from(command).map(Self::UndrainNode),
+            ROLLING_UPDATE => RollingUpdateParams::try_from(command).map(Self::RollingUpdate),
+            other => Err(CommandParseError::UnknownCommand(other.to_string())),
+        }
+    }
+}
+
+impl TryFrom<&FabricCommand> for DeployAgentParams {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        expect_command_type(command, DEPLOY_AGENT)?;
+        Ok(Self {
+            target_node_id: optional_identifier(DEPLOY_AGENT, "target_id", Some(&command.target_id))?,
+            name: required(DEPLOY_AGENT, "name", command.parameters.get("name"))?,
+            agent_type: identifier(DEPLOY_AGENT, "type", command.parameters.get("type"))?,
+            pinned: flag(DEPLOY_AGENT, "pinned", command.parameters.get("pinned"))?,
+            idempotency_key: optional(command.parameters.get("idempotency_key")),
+            env: environment(DEPLOY_AGENT, &command.parameters)?,
+            fleet_id: optional(command.parameters.get("fleet")),
+            requirement: CapabilityRequirement {
+                cpu_cores: count(DEPLOY_AGENT, "min_cpu_cores", command.parameters.get("min_cpu_cores"))?,
+                memory_bytes: byte_size(DEPLOY_AGENT, "min_memory", command.parameters.get("min_memory"))?,
+                gpus: count(DEPLOY_AGENT, "min_gpus", command.parameters.get("min_gpus"))?,
+            },
+        })
+    }
+}
+
+impl TryFrom<&FabricCommand> for StopAgentParams {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        expect_command_type(command, STOP_AGENT)?;
+        Ok(Self {
+            agent_id: identifier(STOP_AGENT, "target_id", Some(&command.target_id))?,
+        })
+    }
+}
+
+impl TryFrom<&FabricCommand> for MigrateAgentParams {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        expect_command_type(command, MIGRATE_AGENT)?;
+        Ok(Self {
+            agent_id: identifier(MIGRATE_AGENT, "target_id", Some(&command.target_id))?,
+            destination_node_id: identifier(MIGRATE_AGENT, "destination_node", command.parameters.get("destination_node"))?,
+            force: flag(MIGRATE_AGENT, "force", command.parameters.get("force"))?,
+        })
+    }
+}
+
+impl TryFrom<&FabricCommand> for DrainNodeParams {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        expect_command_type(command, DRAIN_NODE)?;
+        Ok(Self {
+            node_id: identifier(DRAIN_NODE, "target_id", Some(&command.target_id))?,
+            force: flag(DRAIN_NODE, "force", command.parameters.get("force"))?,
+        })
+    }
+}
+
+impl TryFrom<&FabricCommand> for UndrainNodeParams {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        expect_command_type(command, UNDRAIN_NODE)?;
+        Ok(Self {
+            node_id: identifier(UNDRAIN_NODE, "target_id", Some(&command.target_id))?,
+        })
+    }
+}
+
+impl TryFrom<&FabricCommand> for RollingUpdateParams {
+    type Error = CommandParseError;
+
+    fn try_from(command: &FabricCommand) -> CommandParseResult<Self> {
+        expect_command_type(command, ROLLING_UPDATE)?;
+        Ok(Self {
+            fleet_id: identifier(ROLLING_UPDATE, "target_id", Some(&command.target_id))?,
+            agent_type: identifier(ROLLING_UPDATE, "type", command.parameters.get("type"))?,
+            batch_size: positive_count(ROLLING_UPDATE, "batch_size", command.parameters.get("batch_size"))?.unwrap_or(1),
+            env: environment(ROLLING_UPDATE, &command.parameters)?,
+        })
+    }
+}
+
+fn expect_command_type(command: &FabricCommand, expected: &'static str) -> CommandParseResult<()> {
+    if command.command_type != expected {
+        return Err(CommandParseError::WrongCommandType { expected, found: command.command_type.clone() });
+    }
+    Ok(())
+}
+
+// A required parameter with surrounding whitespace trimmed
+fn required(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<String> {
+    match value.map(|v| v.trim()) {
+        Some(v) if !v.is_empty() => Ok(v.to_string()),
+        _ => Err(CommandParseError::MissingParameter { command, parameter }),
+    }
+}
+
+// A required node/agent id or type name, which must not contain whitespace
+fn identifier(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<String> {
+    let value = required(command, parameter, value)?;
+    if value.chars().any(char::is_whitespace) {
+        return Err(CommandParseError::InvalidParameter {
+            command,
+            parameter,
+            reason: format!("'{}' must not contain whitespace", value),
+        });
+    }
+    Ok(value)
+}
+
+// An optional parameter with surrounding whitespace trimmed; blank counts as absent
+fn optional(value: Option<&String>) -> Option<String> {
+    value.map(|v| v.trim()).filter(|v| !v.is_empty()).map(str::to_string)
+}
+
+// An optional node/agent id, which must not contain whitespace when given
+fn optional_identifier(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<Option<String>> {
+    match optional(value) {
+        Some(_) => identifier(command, parameter, value).map(Some),
+        None => Ok(None),
+    }
+}
+
+// `env.<NAME>` parameters keyed by NAME, which must be a usable variable name
+fn environment(command: &'static str, parameters: &HashMap<String, String>) -> CommandParseResult<HashMap<String, String>> {
+    let mut env = HashMap::new();
+    for (key, value) in parameters {
+        let Some(name) = key.strip_prefix("env.") else { continue };
+        if name.is_empty() || name.contains('=') || name.chars().any(char::is_whitespace) {
+            return Err(CommandParseError::InvalidParameter {
+                command,
+                parameter: "env",
+                reason: format!("'{}' is not a valid environment variable name", name),
+            });
+        }
+        env.insert(name.to_string(), value.clone());
+    }
+    Ok(env)
+}
+
+// An optional boolean parameter, false when absent
+fn flag(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<bool> {
+    match value.map(|v| v.trim()) {
+        None | Some("") => Ok(false),
+        Some(v) => v.parse().map_err(|_| CommandParseError::InvalidParameter {
+            command,
+            parameter,
+            reason: format!("'{}' is not true or false", v),
+        }),
+    }
+}
+
+// An optional whole number parameter, 0 when absent
+fn count(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<u32> {
+    match value.map(|v| v.trim()) {
+        None | Some("") => Ok(0),
+        Some(v) => v.parse().map_err(|_| CommandParseError::InvalidParameter {
+            command,
+            parameter,
+            reason: format!("'{}' is not a whole number", v),
+        }),
+    }
+}
+
+// An optional size such as "16GB" or "512MB", 0 when absent
+fn byte_size(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<u64> {
+    match value.map(|v| v.trim()) {
+        None | Some("") => Ok(0),
+        Some(v) => capabilities::parse_bytes(v).ok_or_else(|| CommandParseError::InvalidParameter {
+            command,
+            parameter,
+            reason: format!("'{}' is not a size such as 16GB", v),
+        }),
+    }
+}
+
+// An optional whole number parameter, which must be at least 1 when given
+fn positive_count(command: &'static str, parameter: &'static str, value: Option<&String>) -> CommandParseResult<Option<usize>> {
+    match value.map(|v| v.trim()) {
+        None | Some("") => Ok(None),
+        Some(v) => match v.parse::<usize>() {
+            Ok(count) if count > 0 => Ok(Some(count)),
+            _ => Err(CommandParseError::InvalidParameter {
+                command,
+                parameter,
+                reason: format!("'{}' is not a positive whole number", v),
+            }),
+        },
+    }
+}
@@ -0,0 +1,64 @@
+// nexus-prime-core/src/compression.rs - Format-tagged, optionally compressed encoding of persisted values
+
+use crate::config::Compression;
+use serde::{de::DeserializeOwned, Serialize};
+
+pub type EncodingResult<T> = Result<T, EncodingError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EncodingError {
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Compression error: {0}")]
+    Compression(#[from] std::io::Error),
+    #[error("Unknown value format {0}")]
+    UnknownFormat(u8),
+    #[error("Value header is missing its format byte")]
+    Truncated,
+}
+
+// Encoded values start with MAGIC and a format byte, so a reader does not need to
+// know how a value was written. Values persisted before the header was introduced are
+// plain bincode; their leading length prefix never spells MAGIC in practice.
+//
+// Values are written as JSON, which names fields and enum variants, so a value written
+// before a `#[serde(default)]` field or a new variant was added still decodes. The
+// bincode formats are positional and only decode while the type's layout is unchanged.
+const MAGIC: &[u8] = b"NXP";
+const FORMAT_BINCODE: u8 = 0;
+const FORMAT_ZSTD: u8 = 1;
+const FORMAT_JSON: u8 = 2;
+const FORMAT_ZSTD_JSON: u8 = 3;
+
+// Serialize a value as JSON, compressing it with zstd at `level` if asked to
+pub fn encode<T: Serialize>(value: &T, compression: Compression, level: i32) -> EncodingResult<Vec<u8>> {
+    let plain = serde_json::to_vec(value)?;
+    let mut encoded = MAGIC.to_vec();
+    match compression {
+        Compression::None => {
+            encoded.push(FORMAT_JSON);
+            encoded.extend(plain);
+        }
+        Compression::Zstd => {
+            encoded.push(FORMAT_ZSTD_JSON);
+            encoded.extend(zstd::encode_all(plain.as_slice(), level)?);
+        }
+    }
+    Ok(encoded)
+}
+
+pub fn decode<T: DeserializeOwned>(bytes: &[u8]) -> EncodingResult<T> {
+    let Some(header) = bytes.strip_prefix(MAGIC) else {
+        return Ok(bincode::deserialize(bytes)?);
+    };
+    match header.split_first() {
+        Some((&FORMAT_BINCODE, value)) => Ok(bincode::deserialize(value)?),
+        Some((&FORMAT_ZSTD, value)) => Ok(bincode::deserialize(&zstd::decode_all(value)?)?),
+        Some((&FORMAT_JSON, value)) => Ok(serde_json::from_slice(value)?),
+        Some((&FORMAT_ZSTD_JSON, value)) => Ok(serde_json::from_slice(&zstd::decode_all(value)?)?),
+        Some((&format, _)) => Err(EncodingError::UnknownFormat(format)),
+        None => Err(EncodingError::Truncated),
+    }
+}
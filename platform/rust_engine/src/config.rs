@@ -1,6 +1,7 @@
 // nexus-prime-core/src/config.rs - Configuration Management for Nexus Prime
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +30,34 @@ pub struct DatabaseConfig {
     pub embedded_db_path: PathBuf,
     pub use_rocksdb: bool,
     pub max_connections: u32,
+    #[serde(default = "default_acquire_timeout_secs")]
+    pub acquire_timeout_secs: u64, // How long a query waits for a free PostgreSQL connection
+    #[serde(default = "default_cache_ttl_seconds")]
+    pub cache_ttl_seconds: u64,
+    #[serde(default = "default_cache_max_entries")]
+    pub cache_max_entries: usize,
+    pub compression: Compression, // Compression of the persisted fabric state and RocksDB telemetry; reads detect the format either way
+    pub compression_level: i32, // zstd level, 1 (fastest) to 22 (smallest)
+    #[serde(default)]
+    pub backend: StorageBackend,
+}
+
+// Where node, agent and telemetry records are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Hybrid, // RocksDB and/or PostgreSQL, per use_rocksdb and postgres_url
+    Memory, // This process only; lost on restart
+}
+
+// How persisted values are compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +70,16 @@ pub struct SecurityConfig {
     pub client_key_path: Option<PathBuf>,
     pub auth_token_secret: String,
     pub session_timeout_minutes: u64,
+    #[serde(default = "default_enforce_auth")]
+    pub enforce_auth: bool, // When false, RPCs are served without checking bearer tokens; only for local development
+    #[serde(default)]
+    pub signed_event_log: bool, // Persist fabric events with HMAC signatures keyed by event_log_secret
+    #[serde(default)]
+    pub event_log_secret: Option<String>, // Required with signed_event_log; separate from auth_token_secret so either can be rotated alone
+    #[serde(default = "default_event_log_max_entries")]
+    pub event_log_max_entries: u64, // The oldest logged events are dropped past this
+    #[serde(default)]
+    pub agent_secrets_key: Option<String>, // Seals stored agent secrets; auth_token_secret is used when unset
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,8 +87,153 @@ pub struct TelemetryConfig {
     pub enable_prometheus: bool,
     pub enable_jaeger: bool,
     pub jaeger_endpoint: Option<String>,
-    pub log_level: String,
+    pub log_level: String, // e.g. "info" or "info,fabric=debug,security=warn"; NEXUS_LOG_LEVEL or RUST_LOG take precedence
     pub enable_detailed_metrics: bool,
+    pub system_metrics_interval_seconds: u64,
+    pub fabric_metrics_interval_seconds: u64,
+    #[serde(default = "default_instance_id")]
+    pub instance_id: String, // Identifies this core in telemetry and logs
+    pub slos: Vec<SloConfig>,
+    pub slo_window: u32, // Most recent samples per metric that SLOs are evaluated over
+    #[serde(default = "default_slo_check_interval_seconds")]
+    pub slo_check_interval_seconds: u64, // How often SLOs are checked for new breaches
+    pub max_tracked_operations: u32, // Distinct operation names kept in performance metrics; the least recently used is evicted past this
+    pub max_operation_samples: u32, // Durations kept per operation; the oldest half is dropped past this
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>, // Endpoints notified of selected fabric events
+    #[serde(default)]
+    pub resource_thresholds: ResourceThresholds, // Usage past which the system resources health check fails
+    #[serde(default)]
+    pub retention: RetentionPolicy, // How long stored telemetry is kept before the daily cleanup removes it
+    #[serde(default = "default_telemetry_batch_size")]
+    pub telemetry_batch_size: u32, // Records written early once this many wait for the next collection
+}
+
+// Days telemetry records are kept, by entity_type ("system", "node", "custom", ...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    pub default_days: u32, // For entity types without an entry of their own
+    pub days_by_entity_type: HashMap<String, u32>,
+}
+
+impl RetentionPolicy {
+    pub fn days_for(&self, entity_type: &str) -> u32 {
+        self.days_by_entity_type.get(entity_type).copied().unwrap_or(self.default_days)
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            default_days: 30,
+            days_by_entity_type: HashMap::new(),
+        }
+    }
+}
+
+// Percentages of CPU, memory and disk in use that fail the system resources health check
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceThresholds {
+    pub max_cpu_percent: f64,
+    pub max_memory_percent: f64,
+    pub max_disk_percent: f64, // Of the disk holding the working directory
+}
+
+impl Default for ResourceThresholds {
+    fn default() -> Self {
+        Self {
+            max_cpu_percent: 95.0,
+            max_memory_percent: 90.0,
+            max_disk_percent: 90.0,
+        }
+    }
+}
+
+// An HTTP endpoint that is POSTed a JSON payload for each fabric event passing its filters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    #[serde(default)]
+    pub event_types: Vec<String>, // e.g. "PERSISTENCE_DEGRADED"; empty allows every type
+    #[serde(default)]
+    pub min_severity: Option<EventSeverity>, // Events without a severity count as info
+    #[serde(default = "default_webhook_max_attempts")]
+    pub max_attempts: u32, // Deliveries tried per event before it is dropped
+    #[serde(default = "default_webhook_max_per_minute")]
+    pub max_per_minute: u32, // Events past this within a minute are dropped
+}
+
+// Severity carried in an event's "severity" metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+fn default_cache_ttl_seconds() -> u64 {
+    5
+}
+
+fn default_cache_max_entries() -> usize {
+    1024
+}
+
+fn default_acquire_timeout_secs() -> u64 {
+    30
+}
+
+fn default_enforce_auth() -> bool {
+    true
+}
+
+fn default_event_log_max_entries() -> u64 {
+    1_000_000
+}
+
+fn default_slo_check_interval_seconds() -> u64 {
+    30
+}
+
+fn default_telemetry_batch_size() -> u32 {
+    500
+}
+
+fn default_webhook_max_attempts() -> u32 {
+    3
+}
+
+fn default_webhook_max_per_minute() -> u32 {
+    30
+}
+
+// A service level objective: at least `target` of the recent samples of `metric` must be good
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SloConfig {
+    pub name: String,
+    pub metric: SloMetric,
+    pub target: f64, // e.g. 0.99
+    #[serde(default)]
+    pub latency_threshold_ms: Option<u64>, // Latency samples at or under this are good
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SloMetric {
+    DeploySuccess, // Whether each agent deploy succeeded
+    CommandLatency, // How long each executed command took
+}
+
+// Defaults to the machine hostname so multi-host deployments stay distinguishable
+pub fn default_instance_id() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|name| name.into_string().ok())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "nexus-prime-core".to_string())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -67,9 +251,68 @@ pub struct FabricConfig {
     pub max_nodes: u32,
     pub max_agents_per_node: u32,
     pub health_check_interval_seconds: u64,
-    pub agent_timeout_seconds: u64,
+    pub agent_timeout_seconds: u64, // Stopped or failed agents inactive this long are pruned
+    pub node_timeout_seconds: u64, // Nodes not seen for this long are pruned
+    pub node_heartbeat_interval_secs: u64, // How often nodes are told to send a Heartbeat; must be shorter than node_timeout_seconds
+    pub prune_interval_seconds: u64, // How often stale nodes and agents are pruned
     pub enable_auto_scaling: bool,
     pub enable_load_balancing: bool,
+    pub agent_progress_event_threshold: f32,
+    pub max_concurrent_reconnects: u32,
+    pub reconnects_per_second: u32,
+    pub reconnect_jitter_ms: u64,
+    pub reconnect_probation_ms: u64, // How long a reconnected node stays Recovering unless it reports itself Online sooner (0 disables)
+    pub connect_max_attempts: u32, // Attempts to connect to a node proxy before giving up for now
+    pub connect_base_delay_ms: u64, // Wait before the first connection retry, doubled for each retry after it
+    pub connect_retry_interval_secs: u64, // How often a node whose connection attempts all failed is tried again
+    pub save_failure_threshold: u32, // Consecutive save failures before entering degraded mode (0 disables)
+    #[serde(default)]
+    pub warm_pool_sizes: HashMap<String, u32>, // Idle pre-deployed agents to keep per agent type
+    #[serde(default)]
+    pub node_prune_policy: NodePrunePolicy,
+    pub command_history_limit: u32, // Most recent commands kept in the command history
+    pub max_field_length: u32, // Longest accepted free-form request string (capabilities, statuses, labels, ...), in bytes
+    pub agent_types: HashMap<String, AgentTypeConfig>, // Known agent types, keyed by type name
+    pub allow_unknown_agent_types: bool, // Deploy types missing from `agent_types` instead of rejecting them
+    pub deploy_idempotency_ttl_secs: u64, // How long a deploy idempotency key keeps returning the same agent
+    pub event_replay_max_events: u32, // Most recent events kept for clients resuming their event stream
+    pub event_replay_max_age_secs: u64, // Events older than this are not replayed
+    pub rolling_update_max_failure_rate: f32, // Share of failed replacements (0.0-1.0) past which a rolling update halts
+    pub reconcile_interval_secs: u64, // How often agent states are compared against what node proxies report (0 disables)
+    pub reconcile_grace_secs: u64, // Agents deployed or updated more recently than this are not failed for missing from their node
+    pub rebalance_interval_secs: u64, // How often agents are evened out across nodes while enable_load_balancing is set (0 disables)
+    pub pending_deploy_capacity: u32, // Automatically placed deploys that may wait at once for a node with room (0 fails them right away)
+    pub pending_deploy_timeout_secs: u64, // How long a waiting deploy waits for a node before failing
+    pub orphan_reclaim_timeout_secs: u64, // How long an agent whose node was removed waits for a new node before it is marked Failed
+    pub store_node_telemetry: bool, // Write telemetry from node status updates to the telemetry store, when one is attached
+}
+
+// Defaults applied to every agent of a registered type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentTypeConfig {
+    pub cpu_cores: f32,
+    pub memory_mb: u64,
+    #[serde(default)]
+    pub gpu_units: Option<u32>,
+    #[serde(default)]
+    pub pinned: bool, // Deploy agents of this type pinned to their node
+}
+
+impl AgentTypeConfig {
+    fn new(cpu_cores: f32, memory_mb: u64) -> Self {
+        Self { cpu_cores, memory_mb, gpu_units: None, pinned: false }
+    }
+}
+
+// What pruning does with a stale node that still hosts active agents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodePrunePolicy {
+    // Stop the agents first, and defer the prune while any of them keep running
+    #[default]
+    Graceful,
+    // Prune right away; active agents are marked Orphaned for reclaim_orphans to move
+    Force,
 }
 
 impl Default for NexusConfig {
@@ -88,6 +331,12 @@ impl Default for NexusConfig {
                 embedded_db_path: PathBuf::from("./data/nexus_db"),
                 use_rocksdb: true,
                 max_connections: 10,
+                acquire_timeout_secs: default_acquire_timeout_secs(),
+                cache_ttl_seconds: default_cache_ttl_seconds(),
+                cache_max_entries: default_cache_max_entries(),
+                compression: Compression::None,
+                compression_level: 3,
+                backend: StorageBackend::Hybrid,
             },
             security: SecurityConfig {
                 enable_mtls: false,
@@ -96,8 +345,13 @@ impl Default for NexusConfig {
                 server_key_path: None,
                 client_cert_path: None,
                 client_key_path: None,
-                auth_token_secret: "CHANGEME_IN_PRODUCTION".to_string(),
+                auth_token_secret: DEFAULT_AUTH_TOKEN_SECRET.to_string(),
                 session_timeout_minutes: 60,
+                enforce_auth: default_enforce_auth(),
+                signed_event_log: false,
+                event_log_secret: None,
+                agent_secrets_key: None,
+                event_log_max_entries: default_event_log_max_entries(),
             },
             telemetry: TelemetryConfig {
                 enable_prometheus: true,
@@ -105,6 +359,31 @@ impl Default for NexusConfig {
                 jaeger_endpoint: None,
                 log_level: "info".to_string(),
                 enable_detailed_metrics: true,
+                system_metrics_interval_seconds: 30,
+                fabric_metrics_interval_seconds: 60,
+                instance_id: default_instance_id(),
+                slos: vec![
+                    SloConfig {
+                        name: "deploy_success".to_string(),
+                        metric: SloMetric::DeploySuccess,
+                        target: 0.99,
+                        latency_threshold_ms: None,
+                    },
+                    SloConfig {
+                        name: "command_latency_p95".to_string(),
+                        metric: SloMetric::CommandLatency,
+                        target: 0.95,
+                        latency_threshold_ms: Some(2000),
+                    },
+                ],
+                slo_window: 1000,
+                slo_check_interval_seconds: default_slo_check_interval_seconds(),
+                max_tracked_operations: 1000,
+                max_operation_samples: 1000,
+                webhooks: vec![],
+                resource_thresholds: ResourceThresholds::default(),
+                retention: RetentionPolicy::default(),
+                telemetry_batch_size: default_telemetry_batch_size(),
             },
             consensus: ConsensusConfig {
                 enable_raft: false,
@@ -119,23 +398,228 @@ impl Default for NexusConfig {
                 max_agents_per_node: 50,
                 health_check_interval_seconds: 30,
                 agent_timeout_seconds: 300,
+                node_timeout_seconds: 300,
+                node_heartbeat_interval_secs: 60,
+                prune_interval_seconds: 300,
                 enable_auto_scaling: true,
                 enable_load_balancing: true,
+                agent_progress_event_threshold: 0.05,
+                max_concurrent_reconnects: 8,
+                reconnects_per_second: 10,
+                reconnect_jitter_ms: 250,
+                reconnect_probation_ms: 10_000,
+                connect_max_attempts: 3,
+                connect_base_delay_ms: 100,
+                connect_retry_interval_secs: 30,
+                save_failure_threshold: 3,
+                warm_pool_sizes: HashMap::new(),
+                node_prune_policy: NodePrunePolicy::Graceful,
+                command_history_limit: 10_000,
+                max_field_length: 4096,
+                agent_types: HashMap::from([
+                    ("Synthesizer".to_string(), AgentTypeConfig::new(2.0, 2048)),
+                    ("Protector".to_string(), AgentTypeConfig::new(1.0, 1024)),
+                    ("Worker".to_string(), AgentTypeConfig::new(1.0, 512)),
+                ]),
+                allow_unknown_agent_types: false,
+                deploy_idempotency_ttl_secs: 3600,
+                event_replay_max_events: 1000,
+                event_replay_max_age_secs: 3600,
+                rolling_update_max_failure_rate: 0.25,
+                reconcile_interval_secs: 60,
+                reconcile_grace_secs: 30,
+                rebalance_interval_secs: 300,
+                pending_deploy_capacity: 0,
+                pending_deploy_timeout_secs: 300,
+                orphan_reclaim_timeout_secs: 120,
+                store_node_telemetry: true,
             },
         }
     }
 }
 
+// Placeholder for secrets and credential paths in reports of the loaded config
+pub const REDACTED: &str = "[REDACTED]";
+
+// The shipped token secret, which anyone can read and so forge tokens with. Refused
+// while auth is enforced, as is any secret shorter than an HS256 key (32 bytes).
+pub const DEFAULT_AUTH_TOKEN_SECRET: &str = "CHANGEME_IN_PRODUCTION";
+pub const MIN_AUTH_TOKEN_SECRET_LENGTH: usize = 32;
+
+fn merge_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge_json(base.entry(key).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, value) => *base = value,
+    }
+}
+
 impl NexusConfig {
     pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let config = config::Config::builder()
             .add_source(config::File::with_name(path))
-            .add_source(config::Environment::with_prefix("NEXUS"))
+            .add_source(Self::env_overrides())
             .build()?;
         
         Ok(config.try_deserialize()?)
     }
 
+    // The defaults with environment overrides applied. The defaults are merged as JSON
+    // rather than loaded as a config source, which would lowercase map keys such as
+    // agent type names.
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let overrides: serde_json::Value = config::Config::builder()
+            .add_source(Self::env_overrides().try_parsing(true))
+            .build()?
+            .try_deserialize()?;
+        let mut config = serde_json::to_value(Self::default())?;
+        merge_json(&mut config, overrides);
+        Ok(serde_json::from_value(config)?)
+    }
+
+    // NEXUS_<SECTION>__<KEY>, e.g. NEXUS_FABRIC__MAX_NODES=200
+    fn env_overrides() -> config::Environment {
+        config::Environment::with_prefix("NEXUS")
+            .prefix_separator("_")
+            .separator("__")
+    }
+
+    // A copy that is safe to show operators: the auth and event log secrets, database
+    // URL, webhook URLs (which often embed a token) and certificate/key paths are
+    // replaced with REDACTED
+    pub fn redacted(&self) -> Self {
+        let redact_path = |path: &Option<PathBuf>| path.as_ref().map(|_| PathBuf::from(REDACTED));
+        let mut config = self.clone();
+        config.security.auth_token_secret = REDACTED.to_string();
+        config.security.event_log_secret = self.security.event_log_secret.as_ref().map(|_| REDACTED.to_string());
+        config.security.agent_secrets_key = self.security.agent_secrets_key.as_ref().map(|_| REDACTED.to_string());
+        config.security.ca_cert_path = redact_path(&self.security.ca_cert_path);
+        config.security.server_cert_path = redact_path(&self.security.server_cert_path);
+        config.security.server_key_path = redact_path(&self.security.server_key_path);
+        config.security.client_cert_path = redact_path(&self.security.client_cert_path);
+        config.security.client_key_path = redact_path(&self.security.client_key_path);
+        config.database.postgres_url = self.database.postgres_url.as_ref().map(|_| REDACTED.to_string());
+        for webhook in &mut config.telemetry.webhooks {
+            webhook.url = REDACTED.to_string();
+        }
+        config
+    }
+
+    // Problems that make the configuration unusable, one message per problem
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let ports = [
+            ("server.grpc_port", self.server.grpc_port),
+            ("server.websocket_port", self.server.websocket_port),
+            ("server.metrics_port", self.server.metrics_port),
+        ];
+        for (i, (name, port)) in ports.iter().enumerate() {
+            if *port == 0 {
+                errors.push(format!("{} must not be 0", name));
+            }
+            if let Some((other, _)) = ports[i + 1..].iter().find(|(_, other_port)| other_port == port) {
+                errors.push(format!("{} and {} are both set to port {}", name, other, port));
+            }
+        }
+        if self.fabric.max_nodes == 0 {
+            errors.push("fabric.max_nodes must be at least 1".to_string());
+        }
+        if self.fabric.max_agents_per_node == 0 {
+            errors.push("fabric.max_agents_per_node must be at least 1".to_string());
+        }
+        if self.fabric.health_check_interval_seconds == 0 {
+            errors.push("fabric.health_check_interval_seconds must be at least 1".to_string());
+        }
+        if self.fabric.node_heartbeat_interval_secs == 0 || self.fabric.node_heartbeat_interval_secs >= self.fabric.node_timeout_seconds {
+            errors.push("fabric.node_heartbeat_interval_secs must be at least 1 and below fabric.node_timeout_seconds".to_string());
+        }
+        if self.fabric.prune_interval_seconds == 0 {
+            errors.push("fabric.prune_interval_seconds must be at least 1".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.fabric.agent_progress_event_threshold) {
+            errors.push("fabric.agent_progress_event_threshold must be between 0 and 1".to_string());
+        }
+        if self.database.max_connections == 0 {
+            errors.push("database.max_connections must be at least 1".to_string());
+        }
+        if self.database.acquire_timeout_secs == 0 {
+            errors.push("database.acquire_timeout_secs must be at least 1".to_string());
+        }
+        if self.database.compression == Compression::Zstd && !(1..=22).contains(&self.database.compression_level) {
+            errors.push("database.compression_level must be between 1 and 22".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.fabric.rolling_update_max_failure_rate) {
+            errors.push("fabric.rolling_update_max_failure_rate must be between 0 and 1".to_string());
+        }
+        if self.telemetry.max_tracked_operations == 0 {
+            errors.push("telemetry.max_tracked_operations must be at least 1".to_string());
+        }
+        if self.telemetry.max_operation_samples < 2 {
+            errors.push("telemetry.max_operation_samples must be at least 2".to_string());
+        }
+        for slo in &self.telemetry.slos {
+            if !(slo.target > 0.0 && slo.target <= 1.0) {
+                errors.push(format!("SLO {} target must be above 0 and at most 1", slo.name));
+            }
+            if slo.metric == SloMetric::CommandLatency && slo.latency_threshold_ms.is_none() {
+                errors.push(format!("SLO {} needs a latency_threshold_ms", slo.name));
+            }
+        }
+        for webhook in &self.telemetry.webhooks {
+            if !webhook.url.starts_with("http://") && !webhook.url.starts_with("https://") {
+                errors.push(format!("Webhook URL {} must start with http:// or https://", webhook.url));
+            }
+            if webhook.max_attempts == 0 || webhook.max_per_minute == 0 {
+                errors.push(format!("Webhook {} needs max_attempts and max_per_minute of at least 1", webhook.url));
+            }
+        }
+        let thresholds = &self.telemetry.resource_thresholds;
+        for (name, percent) in [
+            ("max_cpu_percent", thresholds.max_cpu_percent),
+            ("max_memory_percent", thresholds.max_memory_percent),
+            ("max_disk_percent", thresholds.max_disk_percent),
+        ] {
+            if !(percent > 0.0 && percent <= 100.0) {
+                errors.push(format!("telemetry.resource_thresholds.{} must be above 0 and at most 100", name));
+            }
+        }
+        if self.telemetry.slo_check_interval_seconds == 0 {
+            errors.push("telemetry.slo_check_interval_seconds must be at least 1".to_string());
+        }
+        if self.telemetry.telemetry_batch_size == 0 {
+            errors.push("telemetry.telemetry_batch_size must be at least 1".to_string());
+        }
+        let retention = &self.telemetry.retention;
+        if retention.default_days == 0 || retention.days_by_entity_type.values().any(|days| *days == 0) {
+            errors.push("telemetry.retention must keep telemetry for at least 1 day".to_string());
+        }
+        if self.security.auth_token_secret.is_empty() {
+            errors.push("security.auth_token_secret must not be empty".to_string());
+        } else if self.security.enforce_auth {
+            if self.security.auth_token_secret == DEFAULT_AUTH_TOKEN_SECRET {
+                errors.push("security.auth_token_secret must be changed from its default while enforce_auth is on".to_string());
+            } else if self.security.auth_token_secret.len() < MIN_AUTH_TOKEN_SECRET_LENGTH {
+                errors.push(format!("security.auth_token_secret must be at least {} bytes while enforce_auth is on", MIN_AUTH_TOKEN_SECRET_LENGTH));
+            }
+        }
+        if self.security.signed_event_log {
+            match self.security.event_log_secret.as_deref() {
+                None | Some("") => errors.push("security.event_log_secret must be set when signed_event_log is enabled".to_string()),
+                Some(secret) if secret == self.security.auth_token_secret => {
+                    errors.push("security.event_log_secret must differ from security.auth_token_secret".to_string());
+                }
+                Some(_) => {}
+            }
+        }
+        if self.security.event_log_max_entries == 0 {
+            errors.push("security.event_log_max_entries must be at least 1".to_string());
+        }
+        errors
+    }
+
     pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
         let toml = toml::to_string_pretty(self)?;
         std::fs::write(path, toml)?;
@@ -0,0 +1,33 @@
+// nexus-prime-core/src/event_encoding.rs - Wire encodings for the WebSocket event stream
+
+use crate::{FabricManager, InternalFabricEvent};
+use prost::Message;
+use serde::Deserialize;
+
+// How events are framed for one WebSocket connection, chosen by the client with
+// `?encoding=json` (the default) or `?encoding=protobuf`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventEncoding {
+    #[default]
+    Json,
+    Protobuf, // Length-delimited `FabricEvent` messages, as sent over gRPC
+}
+
+// One encoded event, sent as a text or a binary frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncodedEvent {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl EventEncoding {
+    pub fn encode(&self, event: &InternalFabricEvent) -> EncodedEvent {
+        match self {
+            EventEncoding::Json => EncodedEvent::Text(
+                serde_json::to_string(event).unwrap_or_else(|_| "{\"error\":\"Failed to serialize event\"}".to_string()),
+            ),
+            EventEncoding::Protobuf => EncodedEvent::Binary(FabricManager::convert_event(event).encode_length_delimited_to_vec()),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+// nexus-prime-core/src/event_log.rs - Append-only log of fabric events with optional HMAC signing
+
+use crate::compression::{self, EncodingError};
+use crate::config::Compression;
+use crate::InternalFabricEvent;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::{Arc, Mutex};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub type EventLogResult<T> = Result<T, EventLogError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventLogError {
+    #[error("Persistence error: {0}")]
+    Persistence(#[from] sled::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("Encoding error: {0}")]
+    Encoding(#[from] EncodingError),
+    #[error("Gap in event log: expected sequence {expected}, found {found}")]
+    Gap { expected: u64, found: u64 },
+    #[error("Event {0} has a missing or invalid signature")]
+    InvalidSignature(u64),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventLogEntry {
+    pub sequence: u64,
+    pub recorded_at: chrono::DateTime<Utc>,
+    pub event: InternalFabricEvent,
+    // HMAC-SHA256 over the entry and the previous entry's signature, so removing or
+    // reordering entries breaks the chain as well as editing them
+    pub signature: Option<Vec<u8>>,
+}
+
+// Where a trimmed log now starts, and the signature of the last dropped entry that
+// the first kept entry is chained to
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LogStart {
+    sequence: u64,
+    previous_signature: Option<Vec<u8>>,
+}
+
+const LOG_START_KEY: &[u8] = b"start";
+
+// Persists fabric events in sequence order, keeping the newest `max_entries`. Entries
+// are stored with `compression::encode`, so events are identified by variant name and
+// the format is tagged. With a signing key each entry is chained to the one before it,
+// and `verify_event_log` detects edited, removed or reordered entries. Truncating the
+// newest entries cannot be detected from the log alone.
+#[derive(Clone)]
+pub struct EventLog {
+    tree: sled::Tree,
+    meta: sled::Tree,
+    signing_key: Option<Vec<u8>>,
+    max_entries: u64,
+    append_lock: Arc<Mutex<()>>,
+}
+
+impl EventLog {
+    pub fn open(db: &sled::Db) -> EventLogResult<Self> {
+        Ok(Self {
+            tree: db.open_tree("fabric_event_log")?,
+            meta: db.open_tree("fabric_event_log_meta")?,
+            signing_key: None,
+            max_entries: u64::MAX,
+            append_lock: Arc::new(Mutex::new(())),
+        })
+    }
+
+    pub fn with_signing_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.signing_key = Some(key.into());
+        self
+    }
+
+    pub fn with_max_entries(mut self, max_entries: u64) -> Self {
+        self.max_entries = max_entries.max(1);
+        self
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.signing_key.is_some()
+    }
+
+    // Append an event and return its sequence number
+    pub fn append(&self, event: &InternalFabricEvent) -> EventLogResult<u64> {
+        let _guard = self.append_lock.lock().unwrap();
+        let previous = self.last_entry()?;
+        let sequence = previous.as_ref().map_or(0, |entry| entry.sequence + 1);
+        let mut entry = EventLogEntry {
+            sequence,
+            recorded_at: Utc::now(),
+            event: event.clone(),
+            signature: None,
+        };
+        if let Some(key) = &self.signing_key {
+            let previous_signature = previous.and_then(|entry| entry.signature);
+            entry.signature = Some(Self::sign(key, &entry, previous_signature.as_deref())?);
+        }
+        self.tree.insert(sequence.to_be_bytes(), compression::encode(&entry, Compression::None, 0)?)?;
+        self.trim(sequence)?;
+        Ok(sequence)
+    }
+
+    // Drop the oldest entries past `max_entries`, moving the recorded start along so
+    // the kept entries still verify
+    fn trim(&self, newest: u64) -> EventLogResult<()> {
+        let mut start = self.start()?;
+        if newest - start.sequence < self.max_entries {
+            return Ok(());
+        }
+        while newest - start.sequence >= self.max_entries {
+            let key = start.sequence.to_be_bytes();
+            if let Some(value) = self.tree.get(key)? {
+                start.previous_signature = compression::decode::<EventLogEntry>(&value)?.signature;
+            }
+            self.tree.remove(key)?;
+            start.sequence += 1;
+        }
+        self.meta.insert(LOG_START_KEY, compression::encode(&start, Compression::None, 0)?)?;
+        Ok(())
+    }
+
+    fn start(&self) -> EventLogResult<LogStart> {
+        match self.meta.get(LOG_START_KEY)? {
+            Some(value) => Ok(compression::decode(&value)?),
+            None => Ok(LogStart::default()),
+        }
+    }
+
+    // All entries in sequence order
+    pub fn entries(&self) -> EventLogResult<Vec<EventLogEntry>> {
+        self.tree.iter()
+            .values()
+            .map(|value| Ok(compression::decode(&value?)?))
+            .collect()
+    }
+
+    // Check that sequence numbers are contiguous from where the log starts and, when
+    // signing is enabled, that every signature matches. Returns the number of entries verified.
+    pub fn verify_event_log(&self) -> EventLogResult<u64> {
+        let start = self.start()?;
+        let mut previous_signature = start.previous_signature;
+        let mut expected = start.sequence;
+        for entry in self.entries()? {
+            if entry.sequence != expected {
+                return Err(EventLogError::Gap { expected, found: entry.sequence });
+            }
+            if let Some(key) = &self.signing_key {
+                let signature = entry.signature.as_deref()
+                    .ok_or(EventLogError::InvalidSignature(entry.sequence))?;
+                Self::mac(key, &entry, previous_signature.as_deref())?
+                    .verify_slice(signature)
+                    .map_err(|_| EventLogError::InvalidSignature(entry.sequence))?;
+                previous_signature = Some(signature.to_vec());
+            }
+            expected += 1;
+        }
+        Ok(expected - start.sequence)
+    }
+
+    fn last_entry(&self) -> EventLogResult<Option<EventLogEntry>> {
+        match self.tree.last()? {
+            Some((_, value)) => Ok(Some(compression::decode(&value)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn sign(key: &[u8], entry: &EventLogEntry, previous_signature: Option<&[u8]>) -> EventLogResult<Vec<u8>> {
+        Ok(Self::mac(key, entry, previous_signature)?.finalize().into_bytes().to_vec())
+    }
+
+    fn mac(key: &[u8], entry: &EventLogEntry, previous_signature: Option<&[u8]>) -> EventLogResult<HmacSha256> {
+        let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(previous_signature.unwrap_or_default());
+        mac.update(&entry.sequence.to_be_bytes());
+        mac.update(&bincode::serialize(&(&entry.recorded_at, &entry.event))?);
+        Ok(mac)
+    }
+}
@@ -0,0 +1,98 @@
+// nexus-prime-core/src/event_replay.rs - Persisted backlog of recent fabric events for resuming streams
+
+use crate::fabric_proto::fabric::FabricEvent;
+use chrono::{DateTime, Utc};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub type EventReplayResult<T> = Result<T, EventReplayError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventReplayError {
+    #[error("Persistence error: {0}")]
+    Persistence(#[from] sled::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+    #[error("Decode error: {0}")]
+    Decode(#[from] prost::DecodeError),
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReplayEntry {
+    recorded_at: DateTime<Utc>,
+    event: Vec<u8>, // Protobuf-encoded FabricEvent
+}
+
+// The most recent events sent to streaming clients, keyed by their sequence number.
+// Stored in sled so a client can resume from its last sequence after a core restart.
+// Only the newest `max_events` events younger than `max_age` are kept.
+#[derive(Clone)]
+pub struct EventReplay {
+    tree: sled::Tree,
+    db: sled::Db,
+    max_events: usize,
+    max_age: Duration,
+    len: Arc<AtomicUsize>, // sled's Tree::len is a full scan, so the count is kept here
+}
+
+impl EventReplay {
+    pub fn open(db: &sled::Db, max_events: usize, max_age: Duration) -> EventReplayResult<Self> {
+        let tree = db.open_tree("event_replay")?;
+        let replay = Self {
+            len: Arc::new(AtomicUsize::new(tree.len())),
+            tree,
+            db: db.clone(),
+            max_events: max_events.max(1),
+            max_age,
+        };
+        replay.enforce_retention()?;
+        Ok(replay)
+    }
+
+    // Assign the event its sequence number and retain it
+    pub fn record(&self, event: &mut FabricEvent) -> EventReplayResult<()> {
+        // Sequences start at 1 so that 0 can mean "nothing seen yet"
+        event.sequence = self.db.generate_id()? + 1;
+        let entry = ReplayEntry { recorded_at: Utc::now(), event: event.encode_to_vec() };
+        self.tree.insert(event.sequence.to_be_bytes(), bincode::serialize(&entry)?)?;
+        self.len.fetch_add(1, Ordering::SeqCst);
+        self.enforce_retention()
+    }
+
+    // Retained events with a sequence after `resume_after`, oldest first
+    pub fn events_after(&self, resume_after: u64) -> EventReplayResult<Vec<FabricEvent>> {
+        let cutoff = self.cutoff();
+        let mut events = Vec::new();
+        for item in self.tree.range(resume_after.saturating_add(1).to_be_bytes()..) {
+            let entry: ReplayEntry = bincode::deserialize(&item?.1)?;
+            if entry.recorded_at >= cutoff {
+                events.push(FabricEvent::decode(entry.event.as_slice())?);
+            }
+        }
+        Ok(events)
+    }
+
+    fn cutoff(&self) -> DateTime<Utc> {
+        chrono::Duration::from_std(self.max_age)
+            .ok()
+            .and_then(|max_age| Utc::now().checked_sub_signed(max_age))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC)
+    }
+
+    fn enforce_retention(&self) -> EventReplayResult<()> {
+        let cutoff = self.cutoff();
+        while let Some((key, value)) = self.tree.first()? {
+            let expired = bincode::deserialize::<ReplayEntry>(&value)?.recorded_at < cutoff;
+            if !expired && self.len.load(Ordering::SeqCst) <= self.max_events {
+                break;
+            }
+            if self.tree.remove(key)?.is_some() {
+                self.len.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+        Ok(())
+    }
+}
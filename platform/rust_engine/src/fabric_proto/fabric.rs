@@ -21,6 +21,15 @@ pub struct AgentRegistrationRequest {
     /// The address (e.g., "127.0.0.1:50052") the proxy is listening on
     #[prost(string, tag = "4")]
     pub proxy_listen_address: ::prost::alloc::string::String,
+    /// Structured capabilities, preferred over `capabilities` when any is set
+    #[prost(uint32, tag = "5")]
+    pub cpu_cores: u32,
+    #[prost(uint64, tag = "6")]
+    pub memory_bytes: u64,
+    #[prost(uint32, tag = "7")]
+    pub gpus: u32,
+    #[prost(string, repeated, tag = "8")]
+    pub features: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 /// Response to agent registration
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -34,6 +43,294 @@ pub struct AgentRegistrationResponse {
     #[prost(string, tag = "3")]
     pub message: ::prost::alloc::string::String,
 }
+/// Summary of a streamed bulk registration
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterNodesResponse {
+    /// Assigned by Nexus Prime, in request order
+    #[prost(string, repeated, tag = "1")]
+    pub node_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub message: ::prost::alloc::string::String,
+}
+/// Change the labels and free-form metadata attached to a compute node
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateNodeMetadataRequest {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    #[prost(map = "string, string", tag = "2")]
+    pub labels: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    #[prost(map = "string, string", tag = "3")]
+    pub metadata: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    /// Replace both maps instead of merging; when merging, an empty value removes the key
+    #[prost(bool, tag = "4")]
+    pub replace: bool,
+}
+/// Pin an agent to its node so rebalancing and migration leave it in place
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetAgentPinnedRequest {
+    #[prost(string, tag = "1")]
+    pub agent_id: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub pinned: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetMaintenanceModeRequest {
+    #[prost(bool, tag = "1")]
+    pub enabled: bool,
+    /// Shown to clients whose deploys and commands are rejected
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// The configuration the server is running with, secrets redacted
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EffectiveConfigResponse {
+    /// NexusConfig as JSON
+    #[prost(string, tag = "1")]
+    pub config_json: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeregisterNodeRequest {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeartbeatRequest {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HeartbeatResponse {
+    /// When the node should send its next heartbeat
+    #[prost(uint64, tag = "1")]
+    pub heartbeat_interval_secs: u64,
+}
+/// Progress of a command run through ExecuteCommand
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandProgressUpdate {
+    #[prost(string, tag = "1")]
+    pub command_id: ::prost::alloc::string::String,
+    /// 0-100
+    #[prost(float, tag = "2")]
+    pub percent: f32,
+    #[prost(string, tag = "3")]
+    pub current_step: ::prost::alloc::string::String,
+    /// Outcome so far for each agent the command touches
+    #[prost(map = "string, string", tag = "4")]
+    pub agent_status: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    /// Set on the final update only
+    #[prost(message, optional, tag = "5")]
+    pub result: ::core::option::Option<CommandResponse>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidateConfigRequest {
+    /// Candidate NexusConfig as JSON
+    #[prost(string, tag = "1")]
+    pub config_json: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ValidateConfigResponse {
+    /// No errors; warnings do not make a config invalid
+    #[prost(bool, tag = "1")]
+    pub valid: bool,
+    #[prost(string, repeated, tag = "2")]
+    pub errors: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// e.g. limits below what the running fabric already uses
+    #[prost(string, repeated, tag = "3")]
+    pub warnings: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SloStatus {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(double, tag = "2")]
+    pub target: f64,
+    /// Fraction of good samples in the evaluation window
+    #[prost(double, tag = "3")]
+    pub current: f64,
+    #[prost(uint64, tag = "4")]
+    pub samples: u64,
+    #[prost(bool, tag = "5")]
+    pub met: bool,
+    /// 0.0 once the budget is exhausted
+    #[prost(double, tag = "6")]
+    pub error_budget_remaining: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SloStatusResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub slos: ::prost::alloc::vec::Vec<SloStatus>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetAgentsByNodeRequest {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentSummary {
+    #[prost(string, tag = "1")]
+    pub agent_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub agent_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "5")]
+    pub current_task: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(float, optional, tag = "6")]
+    pub task_progress: ::core::option::Option<f32>,
+    #[prost(bool, tag = "7")]
+    pub pinned: bool,
+    /// Empty while the agent is not assigned to a node
+    #[prost(string, tag = "8")]
+    pub node_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentsByNodeResponse {
+    /// Sorted by agent id
+    #[prost(message, repeated, tag = "1")]
+    pub agents: ::prost::alloc::vec::Vec<AgentSummary>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PruneNowResponse {
+    #[prost(uint32, tag = "1")]
+    pub pruned_nodes: u32,
+    #[prost(uint32, tag = "2")]
+    pub pruned_agents: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListNodesRequest {
+    /// Only nodes with this status, e.g. "Online" (optional)
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NodeSummary {
+    #[prost(string, tag = "1")]
+    pub node_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub node_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub capabilities: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub ip_address: ::prost::alloc::string::String,
+    /// RFC3339
+    #[prost(string, tag = "6")]
+    pub last_seen: ::prost::alloc::string::String,
+    #[prost(map = "string, string", tag = "7")]
+    pub labels: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    #[prost(string, optional, tag = "8")]
+    pub last_error: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListNodesResponse {
+    /// Sorted by node id
+    #[prost(message, repeated, tag = "1")]
+    pub nodes: ::prost::alloc::vec::Vec<NodeSummary>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAgentsRequest {
+    /// Only agents with this status, e.g. "Running" (optional)
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAgentsResponse {
+    /// Sorted by agent id
+    #[prost(message, repeated, tag = "1")]
+    pub agents: ::prost::alloc::vec::Vec<AgentSummary>,
+}
+/// Query the history of issued fabric commands, newest first
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandHistoryRequest {
+    /// Only commands for this target (optional)
+    #[prost(string, tag = "1")]
+    pub target_id: ::prost::alloc::string::String,
+    /// RFC3339 lower bound on issue time (optional)
+    #[prost(string, tag = "2")]
+    pub since: ::prost::alloc::string::String,
+    /// RFC3339 upper bound on issue time (optional)
+    #[prost(string, tag = "3")]
+    pub until: ::prost::alloc::string::String,
+    /// Defaults to 100
+    #[prost(uint32, tag = "4")]
+    pub page_size: u32,
+    /// next_page_token from a previous response
+    #[prost(string, tag = "5")]
+    pub page_token: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandHistoryEntry {
+    #[prost(string, tag = "1")]
+    pub command_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub command_type: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub target_id: ::prost::alloc::string::String,
+    #[prost(map = "string, string", tag = "4")]
+    pub parameters: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
+    /// RFC3339
+    #[prost(string, tag = "5")]
+    pub issued_at: ::prost::alloc::string::String,
+    /// "PENDING", "SUCCEEDED" or "FAILED"
+    #[prost(string, tag = "6")]
+    pub status: ::prost::alloc::string::String,
+    /// Set when status is "FAILED"
+    #[prost(string, tag = "7")]
+    pub error: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CommandHistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<CommandHistoryEntry>,
+    /// Empty on the last page
+    #[prost(string, tag = "2")]
+    pub next_page_token: ::prost::alloc::string::String,
+}
 /// Update message for node or AI agent status
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -95,6 +392,17 @@ pub struct FabricEvent {
     /// Event-specific telemetry
     #[prost(message, optional, tag = "6")]
     pub telemetry: ::core::option::Option<TelemetryData>,
+    /// Position in the replay backlog, 0 when the backlog is disabled
+    #[prost(uint64, tag = "7")]
+    pub sequence: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StreamFabricEventsRequest {
+    /// Sequence of the last event the client received; retained events after it are
+    /// replayed before live events
+    #[prost(uint64, optional, tag = "1")]
+    pub resume_after: ::core::option::Option<u64>,
 }
 /// Commands issued by the Architect (via UI) to the fabric
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -114,6 +422,77 @@ pub struct FabricCommand {
         ::prost::alloc::string::String,
     >,
 }
+/// Point-in-time host metrics of the core
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SystemMetricsSnapshot {
+    /// ISO 8601 string
+    #[prost(string, tag = "1")]
+    pub timestamp: ::prost::alloc::string::String,
+    #[prost(float, tag = "2")]
+    pub cpu_usage: f32,
+    #[prost(float, tag = "3")]
+    pub memory_usage: f32,
+    #[prost(uint64, tag = "4")]
+    pub memory_total: u64,
+    #[prost(uint64, tag = "5")]
+    pub memory_available: u64,
+    #[prost(float, tag = "6")]
+    pub disk_usage: f32,
+    #[prost(uint64, tag = "7")]
+    pub disk_total: u64,
+    #[prost(uint64, tag = "8")]
+    pub disk_available: u64,
+    #[prost(uint64, tag = "9")]
+    pub network_in_bytes: u64,
+    #[prost(uint64, tag = "10")]
+    pub network_out_bytes: u64,
+    /// 1min, 5min, 15min
+    #[prost(float, repeated, tag = "11")]
+    pub load_average: ::prost::alloc::vec::Vec<f32>,
+    #[prost(uint32, tag = "12")]
+    pub process_count: u32,
+    #[prost(uint32, tag = "13")]
+    pub thread_count: u32,
+    #[prost(uint32, tag = "14")]
+    pub file_descriptor_count: u32,
+}
+/// Point-in-time fabric-wide metrics
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FabricMetricsSnapshot {
+    /// ISO 8601 string
+    #[prost(string, tag = "1")]
+    pub timestamp: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub total_nodes: u32,
+    #[prost(uint32, tag = "3")]
+    pub online_nodes: u32,
+    #[prost(uint32, tag = "4")]
+    pub total_agents: u32,
+    #[prost(uint32, tag = "5")]
+    pub running_agents: u32,
+    #[prost(uint32, tag = "6")]
+    pub pending_tasks: u32,
+    #[prost(uint32, tag = "7")]
+    pub completed_tasks: u32,
+    #[prost(uint32, tag = "8")]
+    pub failed_tasks: u32,
+    #[prost(float, tag = "9")]
+    pub average_task_duration_ms: f32,
+    #[prost(float, tag = "10")]
+    pub fabric_throughput_ops_per_sec: f32,
+    #[prost(float, tag = "11")]
+    pub fabric_latency_ms: f32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TelemetrySnapshot {
+    #[prost(message, optional, tag = "1")]
+    pub system: ::core::option::Option<SystemMetricsSnapshot>,
+    #[prost(message, optional, tag = "2")]
+    pub fabric: ::core::option::Option<FabricMetricsSnapshot>,
+}
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DeployAgentRequest {
@@ -129,6 +508,15 @@ pub struct DeployAgentRequest {
         ::prost::alloc::string::String,
         ::prost::alloc::string::String,
     >,
+    /// When set, the agent is restored from this snapshot
+    #[prost(message, optional, tag = "5")]
+    pub checkpoint: ::core::option::Option<AgentCheckpoint>,
+    /// Environment variables the proxy sets for the agent
+    #[prost(map = "string, string", tag = "6")]
+    pub env: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        ::prost::alloc::string::String,
+    >,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -136,6 +524,74 @@ pub struct StopAgentRequest {
     #[prost(string, tag = "1")]
     pub agent_id: ::prost::alloc::string::String,
 }
+/// Resources reserved for an agent on its node
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentResources {
+    #[prost(float, tag = "1")]
+    pub cpu_cores: f32,
+    #[prost(uint64, tag = "2")]
+    pub memory_mb: u64,
+    #[prost(uint32, optional, tag = "3")]
+    pub gpu_units: ::core::option::Option<u32>,
+}
+/// Snapshot of a running agent, used to carry in-progress work across a migration
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AgentCheckpoint {
+    #[prost(string, tag = "1")]
+    pub agent_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub agent_type: ::prost::alloc::string::String,
+    /// Opaque, agent-type specific state blob
+    #[prost(bytes = "vec", tag = "3")]
+    pub state: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub resources: ::core::option::Option<AgentResources>,
+    /// ISO 8601 string
+    #[prost(string, tag = "5")]
+    pub created_at: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointAgentRequest {
+    #[prost(string, tag = "1")]
+    pub agent_id: ::prost::alloc::string::String,
+}
+/// Sent to node proxies when the core stops on purpose, so they can tell it apart from a crash
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CoreShutdownNotice {
+    #[prost(string, tag = "1")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointAgentResponse {
+    /// "SUCCESS" or "UNSUPPORTED" when the agent type cannot be checkpointed
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub checkpoint: ::core::option::Option<AgentCheckpoint>,
+}
+/// An agent as the node proxy running it sees it
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NodeAgentState {
+    #[prost(string, tag = "1")]
+    pub agent_id: ::prost::alloc::string::String,
+    /// e.g. "Running", "Stopped", "Failed"
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NodeAgentList {
+    #[prost(message, repeated, tag = "1")]
+    pub agents: ::prost::alloc::vec::Vec<NodeAgentState>,
+}
 /// --- Enums ---
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -310,6 +766,34 @@ pub mod fabric_service_client {
                 .insert(GrpcMethod::new("fabric.FabricService", "RegisterAgent"));
             self.inner.unary(req, path, codec).await
         }
+        /// Fleet controllers register many nodes over a single stream
+        pub async fn register_nodes(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<
+                Message = super::AgentRegistrationRequest,
+            >,
+        ) -> std::result::Result<
+            tonic::Response<super::RegisterNodesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/RegisterNodes",
+            );
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "RegisterNodes"));
+            self.inner.client_streaming(req, path, codec).await
+        }
         /// Compute Node/Proxy sends status updates and telemetry
         pub async fn update_agent_status(
             &mut self,
@@ -339,7 +823,7 @@ pub mod fabric_service_client {
         /// UI/Mobile app subscribes to real-time fabric events
         pub async fn stream_fabric_events(
             &mut self,
-            request: impl tonic::IntoRequest<()>,
+            request: impl tonic::IntoRequest<super::StreamFabricEventsRequest>,
         ) -> std::result::Result<
             tonic::Response<tonic::codec::Streaming<super::FabricEvent>>,
             tonic::Status,
@@ -388,100 +872,168 @@ pub mod fabric_service_client {
                 .insert(GrpcMethod::new("fabric.FabricService", "SendFabricCommand"));
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated client implementations.
-pub mod node_proxy_service_client {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    use tonic::codegen::http::Uri;
-    /// Service definition for the node proxies, called by the Nexus Prime Core
-    #[derive(Debug, Clone)]
-    pub struct NodeProxyServiceClient<T> {
-        inner: tonic::client::Grpc<T>,
-    }
-    impl NodeProxyServiceClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
-    impl<T> NodeProxyServiceClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
-        }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
-            Self { inner }
+        /// Forces an immediate telemetry collection cycle and returns the fresh metrics
+        pub async fn collect_telemetry_now(
+            &mut self,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::TelemetrySnapshot>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/CollectTelemetryNow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "CollectTelemetryNow"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(
-            inner: T,
-            interceptor: F,
-        ) -> NodeProxyServiceClient<InterceptedService<T, F>>
-        where
-            F: tonic::service::Interceptor,
-            T::ResponseBody: Default,
-            T: tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-                Response = http::Response<
-                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
-                >,
-            >,
-            <T as tonic::codegen::Service<
-                http::Request<tonic::body::BoxBody>,
-            >>::Error: Into<StdError> + Send + Sync,
-        {
-            NodeProxyServiceClient::new(InterceptedService::new(inner, interceptor))
+        /// Operators change node labels/metadata without re-registering the node
+        pub async fn update_node_metadata(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateNodeMetadataRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/UpdateNodeMetadata",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "UpdateNodeMetadata"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress requests with the given encoding.
-        ///
-        /// This requires the server to support it otherwise it might respond with an
-        /// error.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.send_compressed(encoding);
-            self
+        /// Audit which commands were issued and how they turned out
+        pub async fn list_command_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CommandHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandHistoryResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/ListCommandHistory",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "ListCommandHistory"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Enable decompressing responses.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.inner = self.inner.accept_compressed(encoding);
-            self
+        /// Pin or unpin an agent to the node it is running on
+        pub async fn set_agent_pinned(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetAgentPinnedRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/SetAgentPinned",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "SetAgentPinned"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_decoding_message_size(limit);
-            self
+        /// Shows which configuration the server actually loaded, after env overrides
+        pub async fn get_effective_config(
+            &mut self,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::EffectiveConfigResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/GetEffectiveConfig",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "GetEffectiveConfig"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.inner = self.inner.max_encoding_message_size(limit);
-            self
+        /// The agents assigned to one node, for dashboards inspecting that node
+        pub async fn get_agents_by_node(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetAgentsByNodeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AgentsByNodeResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/GetAgentsByNode",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "GetAgentsByNode"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Instructs a node to deploy a new AI agent
-        pub async fn deploy_agent(
+        /// Dry-run a config change against the running server without applying it
+        pub async fn validate_config(
             &mut self,
-            request: impl tonic::IntoRequest<super::DeployAgentRequest>,
+            request: impl tonic::IntoRequest<super::ValidateConfigRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::CommandResponse>,
+            tonic::Response<super::ValidateConfigResponse>,
             tonic::Status,
         > {
             self.inner
@@ -495,17 +1047,17 @@ pub mod node_proxy_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/fabric.NodeProxyService/DeployAgent",
+                "/fabric.FabricService/ValidateConfig",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("fabric.NodeProxyService", "DeployAgent"));
+                .insert(GrpcMethod::new("fabric.FabricService", "ValidateConfig"));
             self.inner.unary(req, path, codec).await
         }
-        /// Instructs a node to stop a running AI agent
-        pub async fn stop_agent(
+        /// A node shutting down gracefully leaves the fabric right away instead of going stale
+        pub async fn deregister_node(
             &mut self,
-            request: impl tonic::IntoRequest<super::StopAgentRequest>,
+            request: impl tonic::IntoRequest<super::DeregisterNodeRequest>,
         ) -> std::result::Result<
             tonic::Response<super::CommandResponse>,
             tonic::Status,
@@ -521,98 +1073,264 @@ pub mod node_proxy_service_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/fabric.NodeProxyService/StopAgent",
+                "/fabric.FabricService/DeregisterNode",
             );
             let mut req = request.into_request();
             req.extensions_mut()
-                .insert(GrpcMethod::new("fabric.NodeProxyService", "StopAgent"));
+                .insert(GrpcMethod::new("fabric.FabricService", "DeregisterNode"));
             self.inner.unary(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod fabric_service_server {
-    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with FabricServiceServer.
-    #[async_trait]
-    pub trait FabricService: Send + Sync + 'static {
-        /// Compute Node/Proxy registers itself with Nexus Prime
-        async fn register_agent(
-            &self,
-            request: tonic::Request<super::AgentRegistrationRequest>,
+        /// Keeps a node from going stale without a status update; emits no fabric event
+        pub async fn heartbeat(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HeartbeatRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::AgentRegistrationResponse>,
+            tonic::Response<super::HeartbeatResponse>,
             tonic::Status,
-        >;
-        /// Compute Node/Proxy sends status updates and telemetry
-        async fn update_agent_status(
-            &self,
-            request: tonic::Request<super::AgentStatusUpdate>,
-        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
-        /// Server streaming response type for the StreamFabricEvents method.
-        type StreamFabricEventsStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<super::FabricEvent, tonic::Status>,
-            >
-            + Send
-            + 'static;
-        /// UI/Mobile app subscribes to real-time fabric events
-        async fn stream_fabric_events(
-            &self,
-            request: tonic::Request<()>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/Heartbeat",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "Heartbeat"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Runs a command immediately, streaming progress of long commands such as DRAIN_NODE
+        pub async fn execute_command(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FabricCommand>,
         ) -> std::result::Result<
-            tonic::Response<Self::StreamFabricEventsStream>,
+            tonic::Response<tonic::codec::Streaming<super::CommandProgressUpdate>>,
             tonic::Status,
-        >;
-        /// Architect issues commands to the fabric (e.g., via UI)
-        async fn send_fabric_command(
-            &self,
-            request: tonic::Request<super::FabricCommand>,
-        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
-    }
-    /// Nexus Prime Fabric Management Service
-    #[derive(Debug)]
-    pub struct FabricServiceServer<T: FabricService> {
-        inner: _Inner<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/ExecuteCommand",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "ExecuteCommand"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Whether each configured SLO is currently met and how much error budget is left
+        pub async fn get_slo_status(
+            &mut self,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::SloStatusResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/GetSloStatus",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "GetSloStatus"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// The current nodes and agents, for clients that did not see every event
+        pub async fn list_nodes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListNodesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListNodesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/ListNodes",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "ListNodes"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_agents(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListAgentsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAgentsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/ListAgents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "ListAgents"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Prunes stale nodes and agents right away instead of waiting for the periodic prune
+        pub async fn prune_now(
+            &mut self,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::PruneNowResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/PruneNow",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "PruneNow"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Pauses new deploys and commands fabric-wide; running agents and event streams carry on
+        pub async fn set_maintenance_mode(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetMaintenanceModeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.FabricService/SetMaintenanceMode",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.FabricService", "SetMaintenanceMode"));
+            self.inner.unary(req, path, codec).await
+        }
     }
-    struct _Inner<T>(Arc<T>);
-    impl<T: FabricService> FabricServiceServer<T> {
+}
+/// Generated client implementations.
+pub mod node_proxy_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Service definition for the node proxies, called by the Nexus Prime Core
+    #[derive(Debug, Clone)]
+    pub struct NodeProxyServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl NodeProxyServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> NodeProxyServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
         pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            let inner = _Inner(inner);
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
         }
         pub fn with_interceptor<F>(
             inner: T,
             interceptor: F,
-        ) -> InterceptedService<Self, F>
+        ) -> NodeProxyServiceClient<InterceptedService<T, F>>
         where
             F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
         {
-            InterceptedService::new(Self::new(inner), interceptor)
+            NodeProxyServiceClient::new(InterceptedService::new(inner, interceptor))
         }
-        /// Enable decompressing requests with the given encoding.
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
         #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
             self
         }
-        /// Compress responses with the given encoding, if the client supports it.
+        /// Enable decompressing responses.
         #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
             self
         }
         /// Limits the maximum size of a decoded message.
@@ -620,7 +1338,7 @@ pub mod fabric_service_server {
         /// Default: `4MB`
         #[must_use]
         pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
+            self.inner = self.inner.max_decoding_message_size(limit);
             self
         }
         /// Limits the maximum size of an encoded message.
@@ -628,47 +1346,1136 @@ pub mod fabric_service_server {
         /// Default: `usize::MAX`
         #[must_use]
         pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
+            self.inner = self.inner.max_encoding_message_size(limit);
             self
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for FabricServiceServer<T>
-    where
-        T: FabricService,
-        B: Body + Send + 'static,
-        B::Error: Into<StdError> + Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
+        /// Instructs a node to deploy a new AI agent
+        pub async fn deploy_agent(
             &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+            request: impl tonic::IntoRequest<super::DeployAgentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.NodeProxyService/DeployAgent",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.NodeProxyService", "DeployAgent"));
+            self.inner.unary(req, path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            let inner = self.inner.clone();
-            match req.uri().path() {
-                "/fabric.FabricService/RegisterAgent" => {
+        /// Instructs a node to stop a running AI agent
+        pub async fn stop_agent(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StopAgentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.NodeProxyService/StopAgent",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.NodeProxyService", "StopAgent"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Instructs a node to snapshot a running AI agent's state for migration
+        pub async fn checkpoint_agent(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CheckpointAgentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CheckpointAgentResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.NodeProxyService/CheckpointAgent",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.NodeProxyService", "CheckpointAgent"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Tells a node the core is shutting down intentionally
+        pub async fn notify_core_shutdown(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CoreShutdownNotice>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.NodeProxyService/NotifyCoreShutdown",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("fabric.NodeProxyService", "NotifyCoreShutdown"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Reports the agents the node is actually running, for reconciliation
+        pub async fn list_agents(
+            &mut self,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<tonic::Response<super::NodeAgentList>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/fabric.NodeProxyService/ListAgents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("fabric.NodeProxyService", "ListAgents"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod fabric_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with FabricServiceServer.
+    #[async_trait]
+    pub trait FabricService: Send + Sync + 'static {
+        /// Compute Node/Proxy registers itself with Nexus Prime
+        async fn register_agent(
+            &self,
+            request: tonic::Request<super::AgentRegistrationRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AgentRegistrationResponse>,
+            tonic::Status,
+        >;
+        /// Fleet controllers register many nodes over a single stream
+        async fn register_nodes(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::AgentRegistrationRequest>>,
+        ) -> std::result::Result<
+            tonic::Response<super::RegisterNodesResponse>,
+            tonic::Status,
+        >;
+        /// Compute Node/Proxy sends status updates and telemetry
+        async fn update_agent_status(
+            &self,
+            request: tonic::Request<super::AgentStatusUpdate>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Server streaming response type for the StreamFabricEvents method.
+        type StreamFabricEventsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::FabricEvent, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// UI/Mobile app subscribes to real-time fabric events
+        async fn stream_fabric_events(
+            &self,
+            request: tonic::Request<super::StreamFabricEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamFabricEventsStream>,
+            tonic::Status,
+        >;
+        /// Architect issues commands to the fabric (e.g., via UI)
+        async fn send_fabric_command(
+            &self,
+            request: tonic::Request<super::FabricCommand>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Forces an immediate telemetry collection cycle and returns the fresh metrics
+        async fn collect_telemetry_now(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::TelemetrySnapshot>,
+            tonic::Status,
+        >;
+        /// Operators change node labels/metadata without re-registering the node
+        async fn update_node_metadata(
+            &self,
+            request: tonic::Request<super::UpdateNodeMetadataRequest>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Audit which commands were issued and how they turned out
+        async fn list_command_history(
+            &self,
+            request: tonic::Request<super::CommandHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CommandHistoryResponse>,
+            tonic::Status,
+        >;
+        /// Pin or unpin an agent to the node it is running on
+        async fn set_agent_pinned(
+            &self,
+            request: tonic::Request<super::SetAgentPinnedRequest>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Shows which configuration the server actually loaded, after env overrides
+        async fn get_effective_config(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::EffectiveConfigResponse>,
+            tonic::Status,
+        >;
+        /// The agents assigned to one node, for dashboards inspecting that node
+        async fn get_agents_by_node(
+            &self,
+            request: tonic::Request<super::GetAgentsByNodeRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::AgentsByNodeResponse>,
+            tonic::Status,
+        >;
+        /// Dry-run a config change against the running server without applying it
+        async fn validate_config(
+            &self,
+            request: tonic::Request<super::ValidateConfigRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ValidateConfigResponse>,
+            tonic::Status,
+        >;
+        /// A node shutting down gracefully leaves the fabric right away instead of going stale
+        async fn deregister_node(
+            &self,
+            request: tonic::Request<super::DeregisterNodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Keeps a node from going stale without a status update; emits no fabric event
+        async fn heartbeat(
+            &self,
+            request: tonic::Request<super::HeartbeatRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::HeartbeatResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the ExecuteCommand method.
+        type ExecuteCommandStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::CommandProgressUpdate, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Runs a command immediately, streaming progress of long commands such as DRAIN_NODE
+        async fn execute_command(
+            &self,
+            request: tonic::Request<super::FabricCommand>,
+        ) -> std::result::Result<
+            tonic::Response<Self::ExecuteCommandStream>,
+            tonic::Status,
+        >;
+        /// Whether each configured SLO is currently met and how much error budget is left
+        async fn get_slo_status(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::SloStatusResponse>,
+            tonic::Status,
+        >;
+        /// The current nodes and agents, for clients that did not see every event
+        async fn list_nodes(
+            &self,
+            request: tonic::Request<super::ListNodesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListNodesResponse>,
+            tonic::Status,
+        >;
+        async fn list_agents(
+            &self,
+            request: tonic::Request<super::ListAgentsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListAgentsResponse>,
+            tonic::Status,
+        >;
+        /// Prunes stale nodes and agents right away instead of waiting for the periodic prune
+        async fn prune_now(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<
+            tonic::Response<super::PruneNowResponse>,
+            tonic::Status,
+        >;
+        /// Pauses new deploys and commands fabric-wide; running agents and event streams carry on
+        async fn set_maintenance_mode(
+            &self,
+            request: tonic::Request<super::SetMaintenanceModeRequest>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+    }
+    /// Nexus Prime Fabric Management Service
+    #[derive(Debug)]
+    pub struct FabricServiceServer<T: FabricService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: FabricService> FabricServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for FabricServiceServer<T>
+    where
+        T: FabricService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/fabric.FabricService/RegisterAgent" => {
+                    #[allow(non_camel_case_types)]
+                    struct RegisterAgentSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::AgentRegistrationRequest>
+                    for RegisterAgentSvc<T> {
+                        type Response = super::AgentRegistrationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AgentRegistrationRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::register_agent(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RegisterAgentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/RegisterNodes" => {
+                    #[allow(non_camel_case_types)]
+                    struct RegisterNodesSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::ClientStreamingService<
+                        super::AgentRegistrationRequest,
+                    > for RegisterNodesSvc<T> {
+                        type Response = super::RegisterNodesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<
+                                tonic::Streaming<super::AgentRegistrationRequest>,
+                            >,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::register_nodes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RegisterNodesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/UpdateAgentStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateAgentStatusSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::AgentStatusUpdate>
+                    for UpdateAgentStatusSvc<T> {
+                        type Response = super::CommandResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AgentStatusUpdate>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::update_agent_status(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateAgentStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/StreamFabricEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamFabricEventsSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::ServerStreamingService<
+                        super::StreamFabricEventsRequest,
+                    > for StreamFabricEventsSvc<T> {
+                        type Response = super::FabricEvent;
+                        type ResponseStream = T::StreamFabricEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamFabricEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::stream_fabric_events(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamFabricEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/SendFabricCommand" => {
+                    #[allow(non_camel_case_types)]
+                    struct SendFabricCommandSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::FabricCommand>
+                    for SendFabricCommandSvc<T> {
+                        type Response = super::CommandResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FabricCommand>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::send_fabric_command(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SendFabricCommandSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/CollectTelemetryNow" => {
+                    #[allow(non_camel_case_types)]
+                    struct CollectTelemetryNowSvc<T: FabricService>(pub Arc<T>);
+                    impl<T: FabricService> tonic::server::UnaryService<()>
+                    for CollectTelemetryNowSvc<T> {
+                        type Response = super::TelemetrySnapshot;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::collect_telemetry_now(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CollectTelemetryNowSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/UpdateNodeMetadata" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateNodeMetadataSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::UpdateNodeMetadataRequest>
+                    for UpdateNodeMetadataSvc<T> {
+                        type Response = super::CommandResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateNodeMetadataRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::update_node_metadata(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateNodeMetadataSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/ListCommandHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListCommandHistorySvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::CommandHistoryRequest>
+                    for ListCommandHistorySvc<T> {
+                        type Response = super::CommandHistoryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CommandHistoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::list_command_history(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListCommandHistorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/SetAgentPinned" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetAgentPinnedSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::SetAgentPinnedRequest>
+                    for SetAgentPinnedSvc<T> {
+                        type Response = super::CommandResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetAgentPinnedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::set_agent_pinned(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetAgentPinnedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/GetEffectiveConfig" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetEffectiveConfigSvc<T: FabricService>(pub Arc<T>);
+                    impl<T: FabricService> tonic::server::UnaryService<()>
+                    for GetEffectiveConfigSvc<T> {
+                        type Response = super::EffectiveConfigResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::get_effective_config(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetEffectiveConfigSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/GetAgentsByNode" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetAgentsByNodeSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::GetAgentsByNodeRequest>
+                    for GetAgentsByNodeSvc<T> {
+                        type Response = super::AgentsByNodeResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetAgentsByNodeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::get_agents_by_node(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetAgentsByNodeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/ValidateConfig" => {
+                    #[allow(non_camel_case_types)]
+                    struct ValidateConfigSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::ValidateConfigRequest>
+                    for ValidateConfigSvc<T> {
+                        type Response = super::ValidateConfigResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ValidateConfigRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::validate_config(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ValidateConfigSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/DeregisterNode" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeregisterNodeSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::DeregisterNodeRequest>
+                    for DeregisterNodeSvc<T> {
+                        type Response = super::CommandResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeregisterNodeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::deregister_node(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DeregisterNodeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/Heartbeat" => {
+                    #[allow(non_camel_case_types)]
+                    struct HeartbeatSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::HeartbeatRequest>
+                    for HeartbeatSvc<T> {
+                        type Response = super::HeartbeatResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HeartbeatRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::heartbeat(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = HeartbeatSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/ExecuteCommand" => {
                     #[allow(non_camel_case_types)]
-                    struct RegisterAgentSvc<T: FabricService>(pub Arc<T>);
+                    struct ExecuteCommandSvc<T: FabricService>(pub Arc<T>);
                     impl<
                         T: FabricService,
-                    > tonic::server::UnaryService<super::AgentRegistrationRequest>
-                    for RegisterAgentSvc<T> {
-                        type Response = super::AgentRegistrationResponse;
+                    > tonic::server::ServerStreamingService<super::FabricCommand>
+                    for ExecuteCommandSvc<T> {
+                        type Response = super::CommandProgressUpdate;
+                        type ResponseStream = T::ExecuteCommandStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FabricCommand>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::execute_command(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExecuteCommandSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/GetSloStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetSloStatusSvc<T: FabricService>(pub Arc<T>);
+                    impl<T: FabricService> tonic::server::UnaryService<()>
+                    for GetSloStatusSvc<T> {
+                        type Response = super::SloStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FabricService>::get_slo_status(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetSloStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.FabricService/ListNodes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListNodesSvc<T: FabricService>(pub Arc<T>);
+                    impl<
+                        T: FabricService,
+                    > tonic::server::UnaryService<super::ListNodesRequest>
+                    for ListNodesSvc<T> {
+                        type Response = super::ListNodesResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AgentRegistrationRequest>,
+                            request: tonic::Request<super::ListNodesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as FabricService>::register_agent(&inner, request).await
+                                <T as FabricService>::list_nodes(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -680,7 +2487,7 @@ pub mod fabric_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = RegisterAgentSvc(inner);
+                        let method = ListNodesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -696,26 +2503,25 @@ pub mod fabric_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/fabric.FabricService/UpdateAgentStatus" => {
+                "/fabric.FabricService/ListAgents" => {
                     #[allow(non_camel_case_types)]
-                    struct UpdateAgentStatusSvc<T: FabricService>(pub Arc<T>);
+                    struct ListAgentsSvc<T: FabricService>(pub Arc<T>);
                     impl<
                         T: FabricService,
-                    > tonic::server::UnaryService<super::AgentStatusUpdate>
-                    for UpdateAgentStatusSvc<T> {
-                        type Response = super::CommandResponse;
+                    > tonic::server::UnaryService<super::ListAgentsRequest>
+                    for ListAgentsSvc<T> {
+                        type Response = super::ListAgentsResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::AgentStatusUpdate>,
+                            request: tonic::Request<super::ListAgentsRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as FabricService>::update_agent_status(&inner, request)
-                                    .await
+                                <T as FabricService>::list_agents(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -727,7 +2533,7 @@ pub mod fabric_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = UpdateAgentStatusSvc(inner);
+                        let method = ListAgentsSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -743,22 +2549,20 @@ pub mod fabric_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/fabric.FabricService/StreamFabricEvents" => {
+                "/fabric.FabricService/PruneNow" => {
                     #[allow(non_camel_case_types)]
-                    struct StreamFabricEventsSvc<T: FabricService>(pub Arc<T>);
-                    impl<T: FabricService> tonic::server::ServerStreamingService<()>
-                    for StreamFabricEventsSvc<T> {
-                        type Response = super::FabricEvent;
-                        type ResponseStream = T::StreamFabricEventsStream;
+                    struct PruneNowSvc<T: FabricService>(pub Arc<T>);
+                    impl<T: FabricService> tonic::server::UnaryService<()>
+                    for PruneNowSvc<T> {
+                        type Response = super::PruneNowResponse;
                         type Future = BoxFuture<
-                            tonic::Response<Self::ResponseStream>,
+                            tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as FabricService>::stream_fabric_events(&inner, request)
-                                    .await
+                                <T as FabricService>::prune_now(&inner, request).await
                             };
                             Box::pin(fut)
                         }
@@ -770,7 +2574,7 @@ pub mod fabric_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = StreamFabricEventsSvc(inner);
+                        let method = PruneNowSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -781,18 +2585,18 @@ pub mod fabric_service_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/fabric.FabricService/SendFabricCommand" => {
+                "/fabric.FabricService/SetMaintenanceMode" => {
                     #[allow(non_camel_case_types)]
-                    struct SendFabricCommandSvc<T: FabricService>(pub Arc<T>);
+                    struct SetMaintenanceModeSvc<T: FabricService>(pub Arc<T>);
                     impl<
                         T: FabricService,
-                    > tonic::server::UnaryService<super::FabricCommand>
-                    for SendFabricCommandSvc<T> {
+                    > tonic::server::UnaryService<super::SetMaintenanceModeRequest>
+                    for SetMaintenanceModeSvc<T> {
                         type Response = super::CommandResponse;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
@@ -800,11 +2604,11 @@ pub mod fabric_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FabricCommand>,
+                            request: tonic::Request<super::SetMaintenanceModeRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
-                                <T as FabricService>::send_fabric_command(&inner, request)
+                                <T as FabricService>::set_maintenance_mode(&inner, request)
                                     .await
                             };
                             Box::pin(fut)
@@ -817,7 +2621,7 @@ pub mod fabric_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = SendFabricCommandSvc(inner);
+                        let method = SetMaintenanceModeSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -891,6 +2695,24 @@ pub mod node_proxy_service_server {
             &self,
             request: tonic::Request<super::StopAgentRequest>,
         ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Instructs a node to snapshot a running AI agent's state for migration
+        async fn checkpoint_agent(
+            &self,
+            request: tonic::Request<super::CheckpointAgentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CheckpointAgentResponse>,
+            tonic::Status,
+        >;
+        /// Tells a node the core is shutting down intentionally
+        async fn notify_core_shutdown(
+            &self,
+            request: tonic::Request<super::CoreShutdownNotice>,
+        ) -> std::result::Result<tonic::Response<super::CommandResponse>, tonic::Status>;
+        /// Reports the agents the node is actually running, for reconciliation
+        async fn list_agents(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<tonic::Response<super::NodeAgentList>, tonic::Status>;
     }
     /// Service definition for the node proxies, called by the Nexus Prime Core
     #[derive(Debug)]
@@ -1064,6 +2886,144 @@ pub mod node_proxy_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/fabric.NodeProxyService/CheckpointAgent" => {
+                    #[allow(non_camel_case_types)]
+                    struct CheckpointAgentSvc<T: NodeProxyService>(pub Arc<T>);
+                    impl<
+                        T: NodeProxyService,
+                    > tonic::server::UnaryService<super::CheckpointAgentRequest>
+                    for CheckpointAgentSvc<T> {
+                        type Response = super::CheckpointAgentResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CheckpointAgentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeProxyService>::checkpoint_agent(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CheckpointAgentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.NodeProxyService/NotifyCoreShutdown" => {
+                    #[allow(non_camel_case_types)]
+                    struct NotifyCoreShutdownSvc<T: NodeProxyService>(pub Arc<T>);
+                    impl<
+                        T: NodeProxyService,
+                    > tonic::server::UnaryService<super::CoreShutdownNotice>
+                    for NotifyCoreShutdownSvc<T> {
+                        type Response = super::CommandResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CoreShutdownNotice>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeProxyService>::notify_core_shutdown(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = NotifyCoreShutdownSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/fabric.NodeProxyService/ListAgents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListAgentsSvc<T: NodeProxyService>(pub Arc<T>);
+                    impl<T: NodeProxyService> tonic::server::UnaryService<()>
+                    for ListAgentsSvc<T> {
+                        type Response = super::NodeAgentList;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NodeProxyService>::list_agents(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListAgentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -0,0 +1,79 @@
+// nexus-prime-core/src/grpc_metrics.rs - Request count, failure and latency metrics for every RPC
+
+use crate::observability::ObservabilityEngine;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tonic::codegen::{http, BoxFuture, Service};
+use tower::Layer;
+
+// Tower layer for the tonic server that records each call through
+// ObservabilityEngine::record_grpc_request
+#[derive(Clone)]
+pub struct GrpcMetricsLayer {
+    observability: Arc<ObservabilityEngine>,
+}
+
+impl GrpcMetricsLayer {
+    pub fn new(observability: Arc<ObservabilityEngine>) -> Self {
+        Self { observability }
+    }
+}
+
+impl<S> Layer<S> for GrpcMetricsLayer {
+    type Service = GrpcMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcMetrics { inner, observability: self.observability.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct GrpcMetrics<S> {
+    inner: S,
+    observability: Arc<ObservabilityEngine>,
+}
+
+impl<S, ReqBody, ResBody> Service<http::Request<ReqBody>> for GrpcMetrics<S>
+where
+    S: Service<http::Request<ReqBody>, Response = http::Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    // Streaming calls are timed until their response headers are sent
+    fn call(&mut self, request: http::Request<ReqBody>) -> Self::Future {
+        let method = rpc_method(request.uri().path()).to_string();
+        let observability = self.observability.clone();
+        let started = Instant::now();
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await;
+            let code = match &response {
+                Ok(response) => response_code(response),
+                Err(_) => tonic::Code::Unknown,
+            };
+            observability.record_grpc_request(&method, code, started.elapsed());
+            response
+        })
+    }
+}
+
+// "/fabric.FabricService/RegisterAgent" -> "RegisterAgent"
+fn rpc_method(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+// Failed calls answer with the status in the response headers. Successful ones carry
+// it in the trailers, so a response without one counts as OK.
+fn response_code<B>(response: &http::Response<B>) -> tonic::Code {
+    response.headers()
+        .get("grpc-status")
+        .map_or(tonic::Code::Ok, |status| tonic::Code::from_bytes(status.as_bytes()))
+}
@@ -0,0 +1,80 @@
+// nexus-prime-core/src/health_endpoints.rs - Liveness and readiness probes over HTTP
+
+use crate::observability::{HealthStatus, ObservabilityEngine};
+use crate::watchdog::Watchdog;
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+
+// Components that have yet to start. The process is ready once every component it
+// was created with has been marked ready, and while no watched background task is
+// unhealthy.
+#[derive(Clone)]
+pub struct Readiness {
+    pending: Arc<Mutex<BTreeSet<String>>>,
+    watchdog: Option<Watchdog>,
+}
+
+impl Readiness {
+    pub fn new(components: &[&str]) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(components.iter().map(|component| component.to_string()).collect())),
+            watchdog: None,
+        }
+    }
+
+    pub fn with_watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+
+    pub fn mark_ready(&self, component: &str) {
+        self.pending.lock().unwrap().remove(component);
+    }
+
+    pub fn pending(&self) -> Vec<String> {
+        self.pending.lock().unwrap().iter().cloned().collect()
+    }
+
+    // Background tasks the watchdog last found dead or stalled
+    pub fn unhealthy_tasks(&self) -> Vec<String> {
+        self.watchdog.as_ref().map(Watchdog::unhealthy_tasks).unwrap_or_default()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.pending.lock().unwrap().is_empty() && self.unhealthy_tasks().is_empty()
+    }
+}
+
+#[derive(Clone)]
+struct HealthEndpoints {
+    observability: Arc<ObservabilityEngine>,
+    readiness: Readiness,
+}
+
+// /healthz serves the latest health state, with 503 while unhealthy or critical.
+// /readyz answers 503, with the components still starting and the unhealthy background
+// tasks, until all have started and every task is healthy.
+pub fn health_router(observability: Arc<ObservabilityEngine>, readiness: Readiness) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .with_state(HealthEndpoints { observability, readiness })
+}
+
+async fn healthz(State(endpoints): State<HealthEndpoints>) -> impl IntoResponse {
+    let health = endpoints.observability.get_health_state().await;
+    let status = match health.overall_status {
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+        HealthStatus::Unhealthy | HealthStatus::Critical => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    (status, Json(health))
+}
+
+async fn readyz(State(endpoints): State<HealthEndpoints>) -> impl IntoResponse {
+    let pending = endpoints.readiness.pending();
+    let unhealthy_tasks = endpoints.readiness.unhealthy_tasks();
+    let ready = pending.is_empty() && unhealthy_tasks.is_empty();
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(serde_json::json!({ "ready": ready, "pending": pending, "unhealthy_tasks": unhealthy_tasks })))
+}
@@ -0,0 +1,31 @@
+// nexus-prime-core/src/metrics_endpoint.rs - Prometheus scrape endpoint over HTTP
+
+use crate::observability::{ExportFormat, ObservabilityEngine};
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use tracing::error;
+
+// /metrics serves the exported metrics as Prometheus text, or as OpenMetrics (with
+// exemplars) to scrapers that ask for it
+pub fn metrics_router(observability: Arc<ObservabilityEngine>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(observability)
+}
+
+async fn metrics(State(observability): State<Arc<ObservabilityEngine>>, headers: HeaderMap) -> Response {
+    let format = ExportFormat::from_accept(headers.get(header::ACCEPT).and_then(|value| value.to_str().ok()));
+    match observability.export_metrics(format).await {
+        Ok(body) => ([(header::CONTENT_TYPE, format.content_type())], body).into_response(),
+        Err(e) => {
+            error!("Failed to export metrics: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to export metrics").into_response()
+        }
+    }
+}
@@ -0,0 +1,169 @@
+// nexus-prime-core/src/observability/health_probes.rs - Readings behind the resource and database health checks
+
+use super::HealthCheck;
+use crate::config::ResourceThresholds;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use sysinfo::{Disks, System};
+
+const DATABASE_PING_TIMEOUT: Duration = Duration::from_secs(5);
+const HEALTH_CHECK_TREE: &str = "health_check";
+
+// Percentages of CPU, memory and disk in use
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceUsage {
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+    pub disk_percent: f64,
+}
+
+pub trait ResourceProbe: Send + Sync {
+    fn sample(&self) -> ResourceUsage;
+}
+
+// Reads usage from the host with sysinfo. CPU usage is measured since the previous
+// sample, so the first sample reads as idle.
+pub struct SysinfoProbe {
+    disk_path: PathBuf, // Disk usage is that of the disk holding this path
+    system: Mutex<System>,
+}
+
+impl SysinfoProbe {
+    pub fn new(disk_path: impl Into<PathBuf>) -> Self {
+        Self {
+            disk_path: disk_path.into(),
+            system: Mutex::new(System::new()),
+        }
+    }
+}
+
+impl ResourceProbe for SysinfoProbe {
+    fn sample(&self) -> ResourceUsage {
+        let (cpu_percent, memory_percent) = {
+            let mut system = self.system.lock().unwrap();
+            system.refresh_cpu_usage();
+            system.refresh_memory();
+            (f64::from(system.global_cpu_usage()), percent(system.used_memory(), system.total_memory()))
+        };
+
+        // The disk mounted at the longest prefix of the path
+        let path = self.disk_path.canonicalize().unwrap_or_else(|_| self.disk_path.clone());
+        let disks = Disks::new_with_refreshed_list();
+        let disk_percent = disks.list().iter()
+            .filter(|disk| path.starts_with(disk.mount_point()))
+            .max_by_key(|disk| disk.mount_point().as_os_str().len())
+            .map_or(0.0, |disk| percent(disk.total_space().saturating_sub(disk.available_space()), disk.total_space()));
+
+        ResourceUsage { cpu_percent, memory_percent, disk_percent }
+    }
+}
+
+fn percent(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 * 100.0 / total as f64
+    }
+}
+
+// Fails when any reading is above its threshold. Details carry each reading and threshold.
+pub fn check_system_resources(usage: ResourceUsage, thresholds: &ResourceThresholds) -> HealthCheck {
+    let readings = [
+        ("cpu", usage.cpu_percent, thresholds.max_cpu_percent),
+        ("memory", usage.memory_percent, thresholds.max_memory_percent),
+        ("disk", usage.disk_percent, thresholds.max_disk_percent),
+    ];
+    let mut details = HashMap::new();
+    let mut breaches = Vec::new();
+    for (resource, observed, max) in readings {
+        details.insert(format!("{}_percent", resource), format!("{:.1}", observed));
+        details.insert(format!("max_{}_percent", resource), format!("{:.1}", max));
+        if observed > max {
+            breaches.push(format!("{} usage {:.1}% is above {:.1}%", resource, observed, max));
+        }
+    }
+
+    HealthCheck {
+        name: "system_resources".to_string(),
+        passed: breaches.is_empty(),
+        message: if breaches.is_empty() {
+            "System resources within acceptable limits".to_string()
+        } else {
+            breaches.join("; ")
+        },
+        details,
+    }
+}
+
+// Fails when a configured database does not answer. Passes with nothing configured.
+pub async fn check_database_connectivity(sled: Option<&sled::Db>, postgres: Option<&PgPool>) -> HealthCheck {
+    let mut details = HashMap::new();
+    let mut failures = Vec::new();
+    if let Some(db) = sled {
+        match ping_sled(db).await {
+            Ok(latency) => {
+                details.insert("sled_latency_ms".to_string(), latency.as_millis().to_string());
+                if let Ok(size) = db.size_on_disk() {
+                    details.insert("sled_size_on_disk_bytes".to_string(), size.to_string());
+                }
+            }
+            Err(e) => {
+                details.insert("sled_error".to_string(), e.clone());
+                failures.push(format!("sled: {}", e));
+            }
+        }
+    }
+    if let Some(pool) = postgres {
+        match ping_postgres(pool).await {
+            Ok(latency) => {
+                details.insert("postgres_latency_ms".to_string(), latency.as_millis().to_string());
+            }
+            Err(e) => {
+                details.insert("postgres_error".to_string(), e.clone());
+                failures.push(format!("postgres: {}", e));
+            }
+        }
+    }
+
+    let message = if sled.is_none() && postgres.is_none() {
+        "No database configured".to_string()
+    } else if failures.is_empty() {
+        "Database connectivity verified".to_string()
+    } else {
+        failures.join("; ")
+    };
+    HealthCheck {
+        name: "database_connectivity".to_string(),
+        passed: failures.is_empty(),
+        message,
+        details,
+    }
+}
+
+// Write, read back and flush a marker in a tree of its own
+async fn ping_sled(db: &sled::Db) -> Result<Duration, String> {
+    let started = Instant::now();
+    let tree = db.open_tree(HEALTH_CHECK_TREE).map_err(|e| e.to_string())?;
+    let stamp = chrono::Utc::now().timestamp_millis().to_be_bytes();
+    tree.insert("last_ping", &stamp).map_err(|e| e.to_string())?;
+    if tree.get("last_ping").map_err(|e| e.to_string())?.as_deref() != Some(&stamp[..]) {
+        return Err("read back a different value than was written".to_string());
+    }
+    tokio::time::timeout(DATABASE_PING_TIMEOUT, tree.flush_async())
+        .await
+        .map_err(|_| format!("flush timed out after {:?}", DATABASE_PING_TIMEOUT))?
+        .map_err(|e| e.to_string())?;
+    Ok(started.elapsed())
+}
+
+async fn ping_postgres(pool: &PgPool) -> Result<Duration, String> {
+    let started = Instant::now();
+    tokio::time::timeout(DATABASE_PING_TIMEOUT, sqlx::query("SELECT 1").execute(pool))
+        .await
+        .map_err(|_| format!("no answer within {:?}", DATABASE_PING_TIMEOUT))?
+        .map_err(|e| e.to_string())?;
+    Ok(started.elapsed())
+}
@@ -5,11 +5,52 @@ use prometheus::{
     Counter, CounterVec, Gauge, GaugeVec, Histogram, HistogramVec, IntCounter, IntCounterVec,
     IntGauge, IntGaugeVec, Registry, Encoder, TextEncoder, Opts, HistogramOpts,
 };
-use std::collections::HashMap;
+use prometheus::core::Collector;
+use prometheus::proto::{MetricFamily, MetricType};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 
+// Maximum number of recent exemplars kept per histogram series
+const MAX_EXEMPLARS_PER_SERIES: usize = 32;
+
+// Exposition format for `MetricsCollector::export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Prometheus,
+    OpenMetrics,
+}
+
+impl ExportFormat {
+    // Negotiate the format from an HTTP Accept header, defaulting to Prometheus text
+    pub fn from_accept(accept: Option<&str>) -> Self {
+        match accept {
+            Some(accept) if accept.contains("application/openmetrics-text") => ExportFormat::OpenMetrics,
+            _ => ExportFormat::Prometheus,
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Prometheus => "text/plain; version=0.0.4; charset=utf-8",
+            ExportFormat::OpenMetrics => "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        }
+    }
+}
+
+// A sampled observation linking a histogram series to a trace
+#[derive(Debug, Clone)]
+pub struct Exemplar {
+    pub trace_id: String,
+    pub value: f64,
+    pub timestamp: f64, // Seconds since the Unix epoch
+}
+
+// Recent exemplars keyed by series (metric name plus sorted labels)
+pub type ExemplarStore = Arc<Mutex<HashMap<String, Vec<Exemplar>>>>;
+
 #[derive(Debug, Clone)]
 pub struct MetricsCollector {
     registry: Registry,
@@ -51,6 +92,9 @@ pub struct MetricsCollector {
     
     // Custom metrics registry
     custom_metrics: Arc<Mutex<HashMap<String, Box<dyn prometheus::core::Metric>>>>,
+
+    // Trace exemplars attached to histogram observations
+    exemplars: ExemplarStore,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -276,6 +320,7 @@ impl MetricsCollector {
             queue_depth,
             message_size_bytes,
             custom_metrics: Arc::new(Mutex::new(HashMap::new())),
+            exemplars: Arc::new(Mutex::new(HashMap::new())),
         })
     }
     
@@ -294,6 +339,35 @@ impl MetricsCollector {
             .observe(response_size as f64);
     }
     
+    // Record an HTTP request and link its latency observation to a trace
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_http_request_traced(&self, method: &str, endpoint: &str, status_code: u16, service: &str, version: &str, duration: Duration, response_size: u64, trace_id: &str) {
+        self.record_http_request(method, endpoint, status_code, service, version, duration, response_size);
+        self.record_exemplar(&self.http_request_duration, &[method, endpoint, service, version], duration.as_secs_f64(), trace_id);
+    }
+    
+    // Observe a histogram value and keep it as a trace exemplar for OpenMetrics export
+    pub fn observe_with_exemplar(&self, histogram: &HistogramVec, label_values: &[&str], value: f64, trace_id: &str) {
+        histogram.with_label_values(label_values).observe(value);
+        self.record_exemplar(histogram, label_values, value, trace_id);
+    }
+    
+    fn record_exemplar(&self, histogram: &HistogramVec, label_values: &[&str], value: f64, trace_id: &str) {
+        let desc = histogram.desc()[0];
+        let labels: Vec<(&str, &str)> = desc.variable_labels.iter()
+            .map(String::as_str)
+            .zip(label_values.iter().copied())
+            .collect();
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64();
+        
+        let mut exemplars = self.exemplars.lock().unwrap();
+        let series = exemplars.entry(series_key(&desc.fq_name, &labels)).or_default();
+        series.push(Exemplar { trace_id: trace_id.to_string(), value, timestamp });
+        if series.len() > MAX_EXEMPLARS_PER_SERIES {
+            series.remove(0);
+        }
+    }
+    
     // Workflow metrics helpers
     pub fn record_workflow_execution(&self, workflow_type: &str, status: &str, service: &str, version: &str, duration: Duration) {
         self.workflow_executions_total
@@ -379,19 +453,180 @@ impl MetricsCollector {
             .observe(size_bytes as f64);
     }
     
-    // Export metrics for Prometheus scraping
-    pub fn export(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let encoder = TextEncoder::new();
+    // Export metrics for scraping in the requested exposition format
+    pub fn export(&self, format: ExportFormat) -> Result<String, Box<dyn std::error::Error>> {
         let metric_families = self.registry.gather();
-        let mut buffer = Vec::new();
-        encoder.encode(&metric_families, &mut buffer)?;
-        Ok(String::from_utf8(buffer)?)
+        match format {
+            ExportFormat::Prometheus => encode_prometheus(&metric_families),
+            ExportFormat::OpenMetrics => Ok(encode_openmetrics(&metric_families, &self.exemplars.lock().unwrap())),
+        }
     }
     
     // Get registry for custom metrics
     pub fn registry(&self) -> &Registry {
         &self.registry
     }
+
+    // Shared handle on the exemplars recorded so far
+    pub fn exemplars(&self) -> ExemplarStore {
+        self.exemplars.clone()
+    }
+}
+
+pub fn encode_prometheus(metric_families: &[MetricFamily]) -> Result<String, Box<dyn std::error::Error>> {
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    encoder.encode(metric_families, &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+// Encode gathered metrics in the OpenMetrics 1.0 text format. Histogram buckets carry
+// the most recent exemplar whose value falls into that bucket.
+pub fn encode_openmetrics(metric_families: &[MetricFamily], exemplars: &HashMap<String, Vec<Exemplar>>) -> String {
+    let mut out = String::new();
+    for family in metric_families {
+        let name = family.get_name();
+        let metric_type = family.get_field_type();
+        // OpenMetrics names the counter family without the `_total` suffix
+        let family_name = match metric_type {
+            MetricType::COUNTER => name.strip_suffix("_total").unwrap_or(name),
+            _ => name,
+        };
+        let type_name = match metric_type {
+            MetricType::COUNTER => "counter",
+            MetricType::GAUGE => "gauge",
+            MetricType::HISTOGRAM => "histogram",
+            MetricType::SUMMARY => "summary",
+            MetricType::UNTYPED => "unknown",
+        };
+        let _ = writeln!(out, "# TYPE {} {}", family_name, type_name);
+        let _ = writeln!(out, "# HELP {} {}", family_name, escape_help(family.get_help()));
+
+        for metric in family.get_metric() {
+            let labels: Vec<(&str, &str)> = metric.get_label().iter()
+                .map(|pair| (pair.get_name(), pair.get_value()))
+                .collect();
+            match metric_type {
+                MetricType::COUNTER => {
+                    let _ = writeln!(out, "{}_total{} {}", family_name, format_labels(&labels, None), format_float(metric.get_counter().get_value()));
+                }
+                MetricType::GAUGE => {
+                    let _ = writeln!(out, "{}{} {}", name, format_labels(&labels, None), format_float(metric.get_gauge().get_value()));
+                }
+                MetricType::UNTYPED => {
+                    let _ = writeln!(out, "{}{} {}", name, format_labels(&labels, None), format_float(metric.get_untyped().get_value()));
+                }
+                MetricType::HISTOGRAM => {
+                    let histogram = metric.get_histogram();
+                    let series_exemplars = exemplars.get(&series_key(name, &labels)).map(Vec::as_slice).unwrap_or(&[]);
+                    let mut lower_bound = f64::NEG_INFINITY;
+                    let mut buckets: Vec<(f64, u64)> = histogram.get_bucket().iter()
+                        .map(|bucket| (bucket.get_upper_bound(), bucket.get_cumulative_count()))
+                        .collect();
+                    if buckets.last().is_none_or(|(bound, _)| bound.is_finite()) {
+                        buckets.push((f64::INFINITY, histogram.get_sample_count()));
+                    }
+                    for (upper_bound, count) in buckets {
+                        let le = format_float(upper_bound);
+                        let _ = write!(out, "{}_bucket{} {}", name, format_labels(&labels, Some(&le)), count);
+                        if let Some(exemplar) = series_exemplars.iter().rev().find(|e| e.value > lower_bound && e.value <= upper_bound) {
+                            let _ = write!(out, " # {{trace_id=\"{}\"}} {} {}", escape_label_value(&exemplar.trace_id), format_float(exemplar.value), exemplar.timestamp);
+                        }
+                        out.push('\n');
+                        lower_bound = upper_bound;
+                    }
+                    let _ = writeln!(out, "{}_count{} {}", name, format_labels(&labels, None), histogram.get_sample_count());
+                    let _ = writeln!(out, "{}_sum{} {}", name, format_labels(&labels, None), format_float(histogram.get_sample_sum()));
+                }
+                MetricType::SUMMARY => {
+                    let summary = metric.get_summary();
+                    for quantile in summary.get_quantile() {
+                        let mut quantile_labels = labels.clone();
+                        let q = format_float(quantile.get_quantile());
+                        quantile_labels.push(("quantile", &q));
+                        let _ = writeln!(out, "{}{} {}", name, format_labels(&quantile_labels, None), format_float(quantile.get_value()));
+                    }
+                    let _ = writeln!(out, "{}_count{} {}", name, format_labels(&labels, None), summary.get_sample_count());
+                    let _ = writeln!(out, "{}_sum{} {}", name, format_labels(&labels, None), format_float(summary.get_sample_sum()));
+                }
+            }
+        }
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+// Rewrite Prometheus text, as rendered by metrics-exporter-prometheus, into OpenMetrics
+// lines: counter families drop the `_total` suffix their samples must carry, `untyped`
+// becomes `unknown` and blank lines go. No `# EOF` is added, so the result can be
+// spliced into another OpenMetrics export.
+pub fn prometheus_text_to_openmetrics(text: &str) -> String {
+    let counters: HashSet<&str> = text.lines()
+        .filter_map(|line| line.strip_prefix("# TYPE ")?.strip_suffix(" counter"))
+        .collect();
+    let mut out = String::new();
+    for line in text.lines().filter(|line| !line.trim().is_empty()) {
+        if let Some((keyword, rest)) = line.strip_prefix("# TYPE ").map(|rest| ("TYPE", rest))
+            .or_else(|| line.strip_prefix("# HELP ").map(|rest| ("HELP", rest)))
+        {
+            let (name, description) = rest.split_once(' ').unwrap_or((rest, ""));
+            let family = if counters.contains(name) { name.strip_suffix("_total").unwrap_or(name) } else { name };
+            let description = if keyword == "TYPE" && description == "untyped" { "unknown" } else { description };
+            let _ = writeln!(out, "{}", format!("# {} {} {}", keyword, family, description).trim_end());
+        } else {
+            let name_end = line.find(['{', ' ']).unwrap_or(line.len());
+            let name = &line[..name_end];
+            if counters.contains(name) && !name.ends_with("_total") {
+                let _ = writeln!(out, "{}_total{}", name, &line[name_end..]);
+            } else {
+                let _ = writeln!(out, "{}", line);
+            }
+        }
+    }
+    out
+}
+
+fn series_key(name: &str, labels: &[(&str, &str)]) -> String {
+    let mut labels = labels.to_vec();
+    labels.sort();
+    format!("{}{}", name, format_labels(&labels, None))
+}
+
+fn format_labels(labels: &[(&str, &str)], le: Option<&str>) -> String {
+    let mut pairs: Vec<String> = labels.iter()
+        .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        pairs.push(format!("le=\"{}\"", le));
+    }
+    if pairs.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+// OpenMetrics requires canonical floats, e.g. `1.0` and `+Inf`
+fn format_float(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else if value.is_nan() {
+        "NaN".to_string()
+    } else if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.1}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
 }
 
 // Timer helper for measuring durations
@@ -467,7 +702,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     metrics.record_security_event("SUSPICIOUS_LOGIN", "HIGH", "nexus-prime-core");
     
     // Export metrics for Prometheus
-    let metrics_output = metrics.export()?;
+    let metrics_output = metrics.export(ExportFormat::Prometheus)?;
     println!("{}", metrics_output);
     
     Ok(())
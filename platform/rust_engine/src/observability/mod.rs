@@ -17,18 +17,21 @@ use std::time::Duration;
 use tokio::sync::RwLock;
 use tracing::{info, error, warn, debug};
 use metrics::{counter, histogram, gauge, describe_counter, describe_histogram, describe_gauge};
-use prometheus::{Registry, Encoder, TextEncoder};
+use prometheus::Registry;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::config::ResourceThresholds;
 
 pub mod structured_logging;
 pub mod metrics;
 pub mod distributed_tracing;
+pub mod health_probes;
 pub mod stubs;
 
 pub use structured_logging::*;
 pub use metrics::*;
 pub use distributed_tracing::*;
+pub use health_probes::{ResourceProbe, ResourceUsage, SysinfoProbe};
 pub use stubs::*;
 
 /// Centralized observability engine managing all telemetry collection
@@ -51,6 +54,20 @@ pub struct ObservabilityEngine {
     
     /// Operational context
     pub operational_context: Arc<RwLock<OperationalContext>>,
+
+    /// Source of CPU, memory and disk readings for the system resources check
+    resource_probe: Arc<dyn ResourceProbe>,
+    resource_thresholds: ResourceThresholds,
+
+    /// Databases pinged by the database connectivity check
+    database: Option<sled::Db>,
+    postgres: Option<sqlx::PgPool>,
+
+    /// Renders metrics recorded through the `metrics` macros
+    prometheus_handle: Option<metrics_exporter_prometheus::PrometheusHandle>,
+
+    /// Trace exemplars attached to histograms in OpenMetrics exports
+    exemplars: ExemplarStore,
 }
 
 /// System health state tracking
@@ -169,8 +186,48 @@ impl ObservabilityEngine {
                 service_name: "nexus-prime-core".to_string(),
                 custom_attributes: HashMap::new(),
             })),
+            resource_probe: Arc::new(SysinfoProbe::new(".")),
+            resource_thresholds: ResourceThresholds::default(),
+            database: None,
+            postgres: None,
+            prometheus_handle: None,
+            exemplars: ExemplarStore::default(),
         }
     }
+
+    /// Include what `handle`'s recorder collected in both export formats
+    pub fn with_prometheus_handle(mut self, handle: metrics_exporter_prometheus::PrometheusHandle) -> Self {
+        self.prometheus_handle = Some(handle);
+        self
+    }
+
+    /// Export `collector`'s metrics, with their trace exemplars, instead of an empty registry
+    pub fn with_metrics_collector(mut self, collector: &MetricsCollector) -> Self {
+        self.metrics_registry = Arc::new(collector.registry().clone());
+        self.exemplars = collector.exemplars();
+        self
+    }
+
+    /// Read resource usage from `probe` instead of the host
+    pub fn with_resource_probe(mut self, probe: Arc<dyn ResourceProbe>) -> Self {
+        self.resource_probe = probe;
+        self
+    }
+
+    pub fn with_resource_thresholds(mut self, thresholds: ResourceThresholds) -> Self {
+        self.resource_thresholds = thresholds;
+        self
+    }
+
+    pub fn with_database(mut self, db: sled::Db) -> Self {
+        self.database = Some(db);
+        self
+    }
+
+    pub fn with_postgres(mut self, pool: sqlx::PgPool) -> Self {
+        self.postgres = Some(pool);
+        self
+    }
     
     /// Setup core system metrics
     fn setup_core_metrics() {
@@ -210,9 +267,9 @@ impl ObservabilityEngine {
         error: Option<&str>
     ) {
         let labels = [
-            ("method", method),
-            ("status_code", &status_code.to_string()),
-            ("request_type", request_type),
+            ("method", method.to_string()),
+            ("status_code", status_code.to_string()),
+            ("request_type", request_type.to_string()),
         ];
         
         counter!("http_requests_total", &labels).increment(1);
@@ -242,6 +299,16 @@ impl ObservabilityEngine {
         );
     }
     
+    /// Record a finished gRPC call under its method and status code
+    pub fn record_grpc_request(&self, method: &str, code: tonic::Code, duration: Duration) {
+        let labels = [("method", method.to_string()), ("code", format!("{:?}", code))];
+        counter!("grpc_requests_total", &labels).increment(1);
+        histogram!("grpc_request_duration_seconds", &labels).record(duration.as_secs_f64());
+        if code != tonic::Code::Ok {
+            counter!("grpc_requests_failed_total", &labels).increment(1);
+        }
+    }
+    
     /// Update health state for a subsystem
     pub async fn update_subsystem_health(
         &self, 
@@ -293,13 +360,28 @@ impl ObservabilityEngine {
         self.health_state.read().await.clone()
     }
     
-    /// Export metrics in Prometheus format
-    pub async fn export_metrics(&self) -> Result<String, Box<dyn std::error::Error>> {
-        let encoder = TextEncoder::new();
+    /// Export metrics in the Prometheus text or OpenMetrics format. Prometheus text
+    /// also carries the metrics recorded through the `metrics` macros.
+    pub async fn export_metrics(&self, format: ExportFormat) -> Result<String, Box<dyn std::error::Error>> {
         let metric_families = self.metrics_registry.gather();
-        let mut buffer = Vec::new();
-        encoder.encode(&metric_families, &mut buffer)?;
-        Ok(String::from_utf8(buffer)?)
+        match format {
+            ExportFormat::Prometheus => {
+                let mut text = encode_prometheus(&metric_families)?;
+                if let Some(handle) = &self.prometheus_handle {
+                    text.push_str(&handle.render());
+                }
+                Ok(text)
+            }
+            ExportFormat::OpenMetrics => {
+                let text = encode_openmetrics(&metric_families, &self.exemplars.lock().unwrap());
+                let Some(handle) = &self.prometheus_handle else {
+                    return Ok(text);
+                };
+                // The handle's families go before the single closing `# EOF`
+                let body = text.strip_suffix("# EOF\n").unwrap_or(&text);
+                Ok(format!("{}{}# EOF\n", body, prometheus_text_to_openmetrics(&handle.render())))
+            }
+        }
     }
     
     /// Create operational context for a request
@@ -365,9 +447,10 @@ impl ObservabilityEngine {
 
 /// Health check implementation
 impl ObservabilityEngine {
-    /// Perform comprehensive health check
+    /// Perform comprehensive health check and record each check as a subsystem
     pub async fn perform_health_check(&self) -> HealthCheckResult {
         info!("🔍 Starting comprehensive health check");
+        let started = std::time::Instant::now();
         
         let mut checks = Vec::new();
         
@@ -383,33 +466,52 @@ impl ObservabilityEngine {
         } else {
             HealthStatus::Unhealthy
         };
+        let duration = started.elapsed();
+
+        for check in &checks {
+            let status = if check.passed { HealthStatus::Healthy } else { HealthStatus::Unhealthy };
+            self.update_subsystem_health(
+                &check.name,
+                status,
+                u64::from(!check.passed),
+                0,
+                if check.passed { 1.0 } else { 0.0 },
+                check.details.clone(),
+            ).await;
+        }
+        histogram!("health_check_duration_seconds").record(duration.as_secs_f64());
         
         HealthCheckResult {
             overall_status,
             checks,
             timestamp: chrono::Utc::now(),
-            duration: Duration::from_millis(100), // Placeholder
+            duration,
         }
     }
+
+    /// Run health checks in the background, starting with one right away. Checks
+    /// repeat every `interval` while healthy and faster while unhealthy, so recovery
+    /// is noticed quickly.
+    pub fn start_health_checks(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let engine = self.clone();
+        tokio::spawn(async move {
+            let mut schedule = HealthCheckSchedule::new(interval);
+            loop {
+                let result = engine.perform_health_check().await;
+                let passed = matches!(result.overall_status, HealthStatus::Healthy);
+                tokio::time::sleep(schedule.next_delay(passed)).await;
+            }
+        })
+    }
     
     async fn check_system_resources(&self) -> HealthCheck {
-        // Placeholder implementation
-        HealthCheck {
-            name: "system_resources".to_string(),
-            passed: true,
-            message: "System resources within acceptable limits".to_string(),
-            details: HashMap::new(),
-        }
+        let probe = self.resource_probe.clone();
+        let usage = tokio::task::spawn_blocking(move || probe.sample()).await.unwrap_or_default();
+        health_probes::check_system_resources(usage, &self.resource_thresholds)
     }
     
     async fn check_database_connectivity(&self) -> HealthCheck {
-        // Placeholder implementation
-        HealthCheck {
-            name: "database_connectivity".to_string(),
-            passed: true,
-            message: "Database connectivity verified".to_string(),
-            details: HashMap::new(),
-        }
+        health_probes::check_database_connectivity(self.database.as_ref(), self.postgres.as_ref()).await
     }
     
     async fn check_external_dependencies(&self) -> HealthCheck {
@@ -433,6 +535,29 @@ impl ObservabilityEngine {
     }
 }
 
+/// Delay before the next health check. A failed check drops the delay to a quarter
+/// of the base interval; each passing check after that doubles it back up to the base.
+#[derive(Debug, Clone)]
+pub struct HealthCheckSchedule {
+    base: Duration,
+    current: Duration,
+}
+
+impl HealthCheckSchedule {
+    pub fn new(base: Duration) -> Self {
+        Self { base, current: base }
+    }
+
+    pub fn next_delay(&mut self, passed: bool) -> Duration {
+        self.current = if passed {
+            (self.current * 2).min(self.base)
+        } else {
+            self.base / 4
+        };
+        self.current
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthCheckResult {
     pub overall_status: HealthStatus,
@@ -449,29 +574,30 @@ pub struct HealthCheck {
     pub details: HashMap<String, String>,
 }
 
-/// Initialize global observability infrastructure
+/// Initialize global observability infrastructure. Logging is set up separately, by
+/// `initialize_structured_logging`, as soon as the configured log level is known.
 pub fn initialize_observability(
     app_name: &str,
     app_version: &str,
     environment: &str,
     deployment_id: &str,
 ) -> ObservabilityEngine {
-    // Initialize structured logging
-    initialize_structured_logging();
-    
     // Initialize metrics
-    initialize_metrics();
+    let prometheus_handle = initialize_metrics();
     
     // Initialize tracing
     initialize_tracing(app_name, environment);
     
     // Create observability engine
-    let engine = ObservabilityEngine::new(
+    let mut engine = ObservabilityEngine::new(
         app_name.to_string(),
         app_version.to_string(),
         environment.to_string(),
         deployment_id.to_string(),
     );
+    if let Some(handle) = prometheus_handle {
+        engine = engine.with_prometheus_handle(handle);
+    }
     
     info!(
         app_name = %app_name,
@@ -1,11 +1,26 @@
 // Simplified observability functions for compilation
 
-pub fn initialize_structured_logging() {
-    env_logger::init();
+// Install the process-wide subscriber printing `tracing` events, and `log` records
+// forwarded to it, that pass `filter`. Does nothing when one is already installed.
+pub fn initialize_structured_logging(filter: &super::LogLevelFilter) {
+    use tracing_subscriber::prelude::*;
+    let targets: tracing_subscriber::filter::Targets = match filter.to_string().parse() {
+        Ok(targets) => targets,
+        Err(e) => {
+            eprintln!("Ignoring log level directives {}: {}", filter, e);
+            Default::default()
+        }
+    };
+    let _ = tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(targets)
+        .try_init();
 }
 
-pub fn initialize_metrics() {
-    // Basic metrics initialization - can be expanded later
+// Install the global recorder for the `metrics` macros, returning a handle that renders
+// what they recorded as Prometheus text. None when a recorder is already installed.
+pub fn initialize_metrics() -> Option<metrics_exporter_prometheus::PrometheusHandle> {
+    metrics_exporter_prometheus::PrometheusBuilder::new().install_recorder().ok()
 }
 
 pub fn initialize_tracing(_app_name: &str, _environment: &str) {
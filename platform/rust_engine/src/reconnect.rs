@@ -0,0 +1,81 @@
+// nexus-prime-core/src/reconnect.rs - Rate-limited reconnection to node proxies
+
+use crate::config::FabricConfig;
+use rand::Rng;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+// Shared limiter for reconnection attempts. Caps how many attempts run at once and
+// spaces attempt start times (with random jitter) so that many nodes dropping at
+// the same moment do not all reconnect at the same moment.
+#[derive(Clone)]
+pub struct ReconnectLimiter {
+    permits: Arc<Semaphore>,
+    next_slot: Arc<Mutex<Instant>>,
+    spacing: Duration,
+    max_jitter: Duration,
+    in_progress: Arc<AtomicUsize>,
+}
+
+// Held for the duration of a reconnection attempt
+pub struct ReconnectPermit {
+    _permit: OwnedSemaphorePermit,
+    in_progress: Arc<AtomicUsize>,
+}
+
+impl Drop for ReconnectPermit {
+    fn drop(&mut self) {
+        self.in_progress.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ReconnectLimiter {
+    pub fn new(config: &FabricConfig) -> Self {
+        let per_second = config.reconnects_per_second.max(1);
+        Self {
+            permits: Arc::new(Semaphore::new(config.max_concurrent_reconnects.max(1) as usize)),
+            next_slot: Arc::new(Mutex::new(Instant::now())),
+            spacing: Duration::from_secs(1) / per_second,
+            max_jitter: Duration::from_millis(config.reconnect_jitter_ms),
+            in_progress: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    // Wait for a free reconnection slot
+    pub async fn acquire(&self) -> ReconnectPermit {
+        let permit = Arc::clone(&self.permits)
+            .acquire_owned()
+            .await
+            .expect("reconnect semaphore is never closed");
+
+        // Reserve the next start slot so attempts never start closer than `spacing` apart
+        let start_at = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now()) + self.jitter();
+            *next_slot = slot + self.spacing;
+            slot
+        };
+        tokio::time::sleep_until(start_at).await;
+
+        self.in_progress.fetch_add(1, Ordering::SeqCst);
+        ReconnectPermit {
+            _permit: permit,
+            in_progress: Arc::clone(&self.in_progress),
+        }
+    }
+
+    // Number of reconnection attempts currently running
+    pub fn in_progress(&self) -> usize {
+        self.in_progress.load(Ordering::SeqCst)
+    }
+
+    fn jitter(&self) -> Duration {
+        if self.max_jitter.is_zero() {
+            return Duration::ZERO;
+        }
+        rand::thread_rng().gen_range(Duration::ZERO..self.max_jitter)
+    }
+}
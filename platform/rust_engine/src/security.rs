@@ -1,6 +1,7 @@
 // nexus-prime-core/src/security.rs - Advanced Security and mTLS Implementation
 
 use crate::config::SecurityConfig;
+use crate::clock::{Clock, SystemClock};
 use rustls::{pki_types::{CertificateDer, PrivateKeyDer}, ServerConfig as RustlsServerConfig, ClientConfig as RustlsClientConfig};
 use rustls_pemfile::{certs, pkcs8_private_keys};
 use std::fs::File;
@@ -13,6 +14,11 @@ use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tokio::sync::RwLock;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
 
 pub type SecurityResult<T> = Result<T, SecurityError>;
 
@@ -30,6 +36,21 @@ pub enum SecurityError {
     Authorization(String),
     #[error("Token error: {0}")]
     Token(String),
+    #[error("Persistence error: {0}")]
+    Persistence(#[from] sled::Error),
+}
+
+impl From<SecurityError> for tonic::Status {
+    fn from(e: SecurityError) -> Self {
+        let message = e.to_string();
+        match e {
+            SecurityError::Authentication(_) | SecurityError::Token(_) => tonic::Status::unauthenticated(message),
+            SecurityError::Authorization(_) => tonic::Status::permission_denied(message),
+            SecurityError::Tls(_) | SecurityError::Io(_) | SecurityError::Certificate(_) | SecurityError::Persistence(_) => {
+                tonic::Status::internal(message)
+            }
+        }
+    }
 }
 
 // Authentication token structure
@@ -44,6 +65,46 @@ pub struct AuthToken {
     pub metadata: HashMap<String, String>,
 }
 
+// Sled tree of revoked token ids, each mapped to the token's expiry in big-endian seconds
+const REVOKED_TOKENS_TREE: &str = "revoked_tokens";
+// Sled tree of revoked entity ids, each mapped to the revocation time in big-endian seconds
+const REVOKED_ENTITIES_TREE: &str = "revoked_entities";
+
+// JOSE header of every token this manager issues
+const JWT_HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+#[derive(Deserialize)]
+struct JwtHeader {
+    alg: String,
+}
+
+// Registered JWT claims plus what an AuthToken carries
+#[derive(Serialize, Deserialize)]
+struct JwtClaims {
+    jti: Uuid,
+    sub: String,
+    iat: i64,
+    exp: i64,
+    entity_type: EntityType,
+    permissions: Vec<Permission>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl From<&AuthToken> for JwtClaims {
+    fn from(token: &AuthToken) -> Self {
+        Self {
+            jti: token.token_id,
+            sub: token.entity_id.clone(),
+            iat: token.issued_at.timestamp(),
+            exp: token.expires_at.timestamp(),
+            entity_type: token.entity_type.clone(),
+            permissions: token.permissions.clone(),
+            metadata: token.metadata.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EntityType {
     Node,
@@ -52,7 +113,7 @@ pub enum EntityType {
     Agent,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Permission {
     // Node permissions
     RegisterNode,
@@ -76,11 +137,74 @@ pub enum Permission {
     EmergencyAccess,
 }
 
-// Security manager for handling authentication, authorization, and TLS
+// Preset permission sets that tokens can be issued for, see permissions_for_role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Role {
+    Viewer, // Reads fabric status and telemetry
+    Operator, // Viewer, plus managing the fabric and its agents
+    Admin, // Every permission
+    NodeAgent, // What a compute node needs to register and report
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Viewer => "Viewer",
+            Role::Operator => "Operator",
+            Role::Admin => "Admin",
+            Role::NodeAgent => "NodeAgent",
+        }
+    }
+
+    // The role named by `name`, ignoring case, e.g. "admin" or "NodeAgent"
+    pub fn parse(name: &str) -> Option<Self> {
+        [Role::Viewer, Role::Operator, Role::Admin, Role::NodeAgent]
+            .into_iter()
+            .find(|role| role.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+pub fn permissions_for_role(role: Role) -> Vec<Permission> {
+    match role {
+        Role::Viewer => vec![Permission::ViewFabricStatus, Permission::ViewTelemetry],
+        Role::Operator => vec![
+            Permission::ViewFabricStatus,
+            Permission::ViewTelemetry,
+            Permission::ManageFabric,
+            Permission::ManageTelemetry,
+            Permission::DeployAgent,
+            Permission::StopAgent,
+        ],
+        Role::Admin => vec![
+            Permission::RegisterNode,
+            Permission::UpdateNodeStatus,
+            Permission::DeployAgent,
+            Permission::StopAgent,
+            Permission::ViewFabricStatus,
+            Permission::ManageFabric,
+            Permission::ViewTelemetry,
+            Permission::ManageTelemetry,
+            Permission::ManageUsers,
+            Permission::ManageSecurityPolicy,
+            Permission::ViewAuditLogs,
+            Permission::SystemControl,
+            Permission::EmergencyAccess,
+        ],
+        Role::NodeAgent => vec![Permission::RegisterNode, Permission::UpdateNodeStatus],
+    }
+}
+
+// Security manager for handling authentication, authorization, and TLS. Tokens are
+// validated from their signed claims alone, so they stay valid across restarts and
+// on any instance sharing the auth token secret.
 pub struct SecurityManager {
     config: SecurityConfig,
-    active_tokens: Arc<RwLock<HashMap<String, AuthToken>>>,
-    revoked_tokens: Arc<RwLock<Vec<Uuid>>>,
+    active_tokens: Arc<RwLock<HashMap<String, AuthToken>>>, // Tokens issued or validated here, used to find an entity's tokens to revoke
+    revoked_tokens: Arc<RwLock<HashMap<Uuid, DateTime<Utc>>>>, // Revoked token ids and when each token expires
+    revocation_list: Option<sled::Tree>, // Persisted copy of revoked_tokens
+    revoked_entities: Arc<RwLock<HashMap<String, DateTime<Utc>>>>, // Entities whose tokens issued up to then are revoked
+    entity_revocation_list: Option<sled::Tree>, // Persisted copy of revoked_entities
+    clock: Arc<dyn Clock>, // Source of "now" for token issue and expiry
 }
 
 impl SecurityManager {
@@ -88,10 +212,60 @@ impl SecurityManager {
         Self {
             config,
             active_tokens: Arc::new(RwLock::new(HashMap::new())),
-            revoked_tokens: Arc::new(RwLock::new(Vec::new())),
+            revoked_tokens: Arc::new(RwLock::new(HashMap::new())),
+            revocation_list: None,
+            revoked_entities: Arc::new(RwLock::new(HashMap::new())),
+            entity_revocation_list: None,
+            clock: Arc::new(SystemClock),
         }
     }
 
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    // Keep revocations in sled so they survive restarts, loading the ones already there
+    pub fn with_revocation_list(mut self, db: &sled::Db) -> SecurityResult<Self> {
+        let tree = db.open_tree(REVOKED_TOKENS_TREE)?;
+        let mut revoked = HashMap::new();
+        for entry in tree.iter() {
+            let (key, value) = entry?;
+            let token_id = Uuid::from_slice(&key).ok();
+            let expires_at = Self::decode_seconds(&value);
+            match (token_id, expires_at) {
+                (Some(token_id), Some(expires_at)) => {
+                    revoked.insert(token_id, expires_at);
+                }
+                _ => log::warn!("Skipping unreadable entry in the token revocation list"),
+            }
+        }
+        self.revoked_tokens = Arc::new(RwLock::new(revoked));
+        self.revocation_list = Some(tree);
+
+        let tree = db.open_tree(REVOKED_ENTITIES_TREE)?;
+        let mut revoked_entities = HashMap::new();
+        for entry in tree.iter() {
+            let (key, value) = entry?;
+            let entity_id = String::from_utf8(key.to_vec()).ok();
+            let revoked_at = Self::decode_seconds(&value);
+            match (entity_id, revoked_at) {
+                (Some(entity_id), Some(revoked_at)) => {
+                    revoked_entities.insert(entity_id, revoked_at);
+                }
+                _ => log::warn!("Skipping unreadable entry in the entity revocation list"),
+            }
+        }
+        self.revoked_entities = Arc::new(RwLock::new(revoked_entities));
+        self.entity_revocation_list = Some(tree);
+        Ok(self)
+    }
+
+    fn decode_seconds(value: &[u8]) -> Option<DateTime<Utc>> {
+        <[u8; 8]>::try_from(value).ok()
+            .and_then(|seconds| DateTime::from_timestamp(i64::from_be_bytes(seconds), 0))
+    }
+
     // Create server TLS config for gRPC server
     pub fn create_server_tls_config(&self) -> SecurityResult<Option<ServerTlsConfig>> {
         if !self.config.enable_mtls {
@@ -150,14 +324,32 @@ impl SecurityManager {
 
     // Generate authentication token
     pub async fn generate_token(&self, entity_id: String, entity_type: EntityType, permissions: Vec<Permission>) -> SecurityResult<String> {
+        self.issue_token(entity_id, entity_type, permissions, HashMap::new()).await
+    }
+
+    // Generate a token with the role's permissions. The role is recorded in the
+    // token's "role" metadata for auditing.
+    pub async fn generate_token_for_role(&self, entity_id: String, entity_type: EntityType, role: Role) -> SecurityResult<String> {
+        let metadata = HashMap::from([("role".to_string(), role.as_str().to_string())]);
+        self.issue_token(entity_id, entity_type, permissions_for_role(role), metadata).await
+    }
+
+    async fn issue_token(
+        &self,
+        entity_id: String,
+        entity_type: EntityType,
+        permissions: Vec<Permission>,
+        metadata: HashMap<String, String>,
+    ) -> SecurityResult<String> {
+        let now = self.clock.now();
         let token = AuthToken {
             token_id: Uuid::new_v4(),
-            entity_id: entity_id.clone(),
+            entity_id,
             entity_type,
             permissions,
-            issued_at: Utc::now(),
-            expires_at: Utc::now() + Duration::minutes(self.config.session_timeout_minutes as i64),
-            metadata: HashMap::new(),
+            issued_at: now,
+            expires_at: now + Duration::minutes(self.config.session_timeout_minutes as i64),
+            metadata,
         };
 
         let token_string = self.encode_token(&token)?;
@@ -169,26 +361,64 @@ impl SecurityManager {
         Ok(token_string)
     }
 
-    // Validate authentication token
+    // Validate authentication token. Its signature and expiry are checked before
+    // any of its claims are trusted; beyond that only the revocation lists are consulted,
+    // so tokens issued before a restart or by another instance are accepted.
     pub async fn validate_token(&self, token_string: &str) -> SecurityResult<AuthToken> {
-        // Check if token is revoked
-        let revoked_tokens = self.revoked_tokens.read().await;
-        
-        let active_tokens = self.active_tokens.read().await;
-        let token = active_tokens.get(token_string)
-            .ok_or_else(|| SecurityError::Authentication("Token not found".to_string()))?;
+        let token = self.decode_token(token_string)?;
 
         // Check if token is revoked
-        if revoked_tokens.contains(&token.token_id) {
+        if self.revoked_tokens.read().await.contains_key(&token.token_id) {
             return Err(SecurityError::Authentication("Token has been revoked".to_string()));
         }
+        // `iat` has whole seconds, so tokens issued in the second of an entity revocation
+        // are left to the per-token list above; one reissued right away stays valid
+        if self.revoked_entities.read().await.get(&token.entity_id).is_some_and(|revoked_at| token.issued_at.timestamp() < revoked_at.timestamp()) {
+            return Err(SecurityError::Authentication(format!("Tokens of {} have been revoked", token.entity_id)));
+        }
+
+        // Remember the token so revoke_entity can find it
+        if !self.active_tokens.read().await.contains_key(token_string) {
+            self.active_tokens.write().await.insert(token_string.to_string(), token.clone());
+        }
+
+        Ok(token)
+    }
 
-        // Check if token is expired
-        if Utc::now() > token.expires_at {
+    // Verify a token's HS256 signature against the auth token secret and return its
+    // claims, rejecting tokens that were tampered with or have expired
+    pub fn decode_token(&self, token_string: &str) -> SecurityResult<AuthToken> {
+        let mut parts = token_string.split('.');
+        let (Some(header), Some(claims), Some(signature), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(SecurityError::Token("Token is not a JWT".to_string()));
+        };
+
+        let signature = URL_SAFE_NO_PAD.decode(signature)
+            .map_err(|_| SecurityError::Token("Token signature is not base64url".to_string()))?;
+        self.token_mac(header, claims)
+            .verify_slice(&signature)
+            .map_err(|_| SecurityError::Token("Token signature is invalid".to_string()))?;
+
+        let header: JwtHeader = Self::decode_segment(header)?;
+        if header.alg != "HS256" {
+            return Err(SecurityError::Token(format!("Unsupported token algorithm {}", header.alg)));
+        }
+        let claims: JwtClaims = Self::decode_segment(claims)?;
+        if self.clock.now().timestamp() >= claims.exp {
             return Err(SecurityError::Authentication("Token has expired".to_string()));
         }
 
-        Ok(token.clone())
+        let timestamp = |seconds: i64| DateTime::from_timestamp(seconds, 0)
+            .ok_or_else(|| SecurityError::Token(format!("Token timestamp {} is out of range", seconds)));
+        Ok(AuthToken {
+            token_id: claims.jti,
+            entity_id: claims.sub,
+            entity_type: claims.entity_type,
+            permissions: claims.permissions,
+            issued_at: timestamp(claims.iat)?,
+            expires_at: timestamp(claims.exp)?,
+            metadata: claims.metadata,
+        })
     }
 
     // Check if entity has specific permission
@@ -197,22 +427,100 @@ impl SecurityManager {
         Ok(token.permissions.contains(required_permission))
     }
 
-    // Revoke authentication token
+    // Check the bearer token in a gRPC request's "authorization" metadata for the given
+    // permission, answering UNAUTHENTICATED or PERMISSION_DENIED when it falls short.
+    // Every request is let through while `enforce_auth` is off.
+    pub async fn authorize_metadata(&self, metadata: &tonic::metadata::MetadataMap, permission: Permission) -> Result<(), tonic::Status> {
+        if !self.config.enforce_auth {
+            return Ok(());
+        }
+        let token = metadata.get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim_start_matches("Bearer ").to_string())
+            .ok_or_else(|| tonic::Status::unauthenticated("Missing authorization token."))?;
+
+        match self.check_permission(&token, &permission).await {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(tonic::Status::permission_denied(format!("Missing permission {:?}.", permission))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // The entity of the valid bearer token in a gRPC request's "authorization" metadata,
+    // if there is one. Used to tie registrations to who made them, even while
+    // `enforce_auth` is off.
+    pub async fn token_entity(&self, metadata: &tonic::metadata::MetadataMap) -> Option<String> {
+        let token = metadata.get("authorization")?.to_str().ok()?.trim_start_matches("Bearer ");
+        self.validate_token(token).await.ok().map(|token| token.entity_id)
+    }
+
+    // Revoke authentication token. Tokens that no longer validate need no revoking.
     pub async fn revoke_token(&self, token_string: &str) -> SecurityResult<()> {
+        self.active_tokens.write().await.remove(token_string);
+        match self.decode_token(token_string) {
+            Ok(token) => self.revoke(&[token]).await,
+            Err(_) => Ok(()),
+        }
+    }
+
+    // Revoke every token of an entity (e.g. a compromised or evicted node) issued before
+    // now, wherever it was issued, persisting the cutoff when the revocation list is kept
+    // in sled. Tokens issued afterwards are accepted. Returns how many of the revoked
+    // tokens this manager had issued or validated.
+    pub async fn revoke_entity(&self, entity_id: &str) -> usize {
+        let revoked_at = self.clock.now();
+        self.revoked_entities.write().await.insert(entity_id.to_string(), revoked_at);
+        if let Some(entity_revocation_list) = &self.entity_revocation_list {
+            let persisted = entity_revocation_list.insert(entity_id.as_bytes(), &revoked_at.timestamp().to_be_bytes());
+            if let Err(e) = persisted {
+                log::error!("Failed to persist revocation of {}: {}", entity_id, e);
+            } else if let Err(e) = entity_revocation_list.flush_async().await {
+                log::error!("Failed to persist revocation of {}: {}", entity_id, e);
+            }
+        }
+
         let mut active_tokens = self.active_tokens.write().await;
-        
-        if let Some(token) = active_tokens.remove(token_string) {
-            let mut revoked_tokens = self.revoked_tokens.write().await;
-            revoked_tokens.push(token.token_id);
+        let mut revoked = Vec::new();
+        active_tokens.retain(|_, token| {
+            if token.entity_id == entity_id {
+                revoked.push(token.clone());
+                false
+            } else {
+                true
+            }
+        });
+        drop(active_tokens);
+        if let Err(e) = self.revoke(&revoked).await {
+            log::error!("Failed to persist revocation of tokens for {}: {}", entity_id, e);
         }
 
+        let mut details = HashMap::new();
+        details.insert("revoked_tokens".to_string(), revoked.len().to_string());
+        self.log_security_event("ENTITY_TOKENS_REVOKED", entity_id, details).await;
+        revoked.len()
+    }
+
+    // Add tokens to the revocation list, persisting them when it is kept in sled
+    async fn revoke(&self, tokens: &[AuthToken]) -> SecurityResult<()> {
+        let mut revoked_tokens = self.revoked_tokens.write().await;
+        for token in tokens {
+            revoked_tokens.insert(token.token_id, token.expires_at);
+        }
+        drop(revoked_tokens);
+        if let Some(revocation_list) = &self.revocation_list {
+            for token in tokens {
+                revocation_list.insert(token.token_id.as_bytes(), &token.expires_at.timestamp().to_be_bytes())?;
+            }
+            revocation_list.flush_async().await?;
+        }
         Ok(())
     }
 
-    // Clean up expired tokens
+    // Clean up expired tokens, and revocations of tokens that have expired since.
+    // Returns how many cached tokens expired.
     pub async fn cleanup_expired_tokens(&self) -> SecurityResult<usize> {
         let mut active_tokens = self.active_tokens.write().await;
-        let now = Utc::now();
+        let now = self.clock.now();
         
         let mut expired_count = 0;
         active_tokens.retain(|_, token| {
@@ -223,8 +531,37 @@ impl SecurityManager {
                 true
             }
         });
+        drop(active_tokens);
+
+        let mut revoked_tokens = self.revoked_tokens.write().await;
+        let lapsed: Vec<Uuid> = revoked_tokens.iter()
+            .filter(|(_, expires_at)| now > **expires_at)
+            .map(|(token_id, _)| *token_id)
+            .collect();
+        for token_id in &lapsed {
+            revoked_tokens.remove(token_id);
+            if let Some(revocation_list) = &self.revocation_list {
+                revocation_list.remove(token_id.as_bytes())?;
+            }
+        }
+
+        drop(revoked_tokens);
+
+        // Once a whole session has passed, every token an entity revocation covers has expired
+        let session = Duration::minutes(self.config.session_timeout_minutes as i64);
+        let mut revoked_entities = self.revoked_entities.write().await;
+        let lapsed_entities: Vec<String> = revoked_entities.iter()
+            .filter(|(_, revoked_at)| now > **revoked_at + session)
+            .map(|(entity_id, _)| entity_id.clone())
+            .collect();
+        for entity_id in &lapsed_entities {
+            revoked_entities.remove(entity_id);
+            if let Some(entity_revocation_list) = &self.entity_revocation_list {
+                entity_revocation_list.remove(entity_id.as_bytes())?;
+            }
+        }
 
-        log::info!("Cleaned up {} expired authentication tokens", expired_count);
+        log::info!("Cleaned up {} expired authentication tokens and {} lapsed revocations", expired_count, lapsed.len() + lapsed_entities.len());
         Ok(expired_count)
     }
 
@@ -244,14 +581,32 @@ impl SecurityManager {
         // and potentially trigger alerts for suspicious activities
     }
 
-    // Encode token (simplified - in production, use proper JWT or similar)
+    // Encode a token as a JWT signed with HMAC-SHA256 under the auth token secret
     fn encode_token(&self, token: &AuthToken) -> SecurityResult<String> {
-        let serialized = serde_json::to_string(token)
+        let claims = serde_json::to_string(&JwtClaims::from(token))
             .map_err(|e| SecurityError::Token(format!("Failed to serialize token: {}", e)))?;
-        
-        // In production, this should use proper HMAC signing with the secret key
-        let encoded = base64::encode(serialized);
-        Ok(format!("{}:{}", self.config.auth_token_secret, encoded))
+
+        let header = URL_SAFE_NO_PAD.encode(JWT_HEADER);
+        let claims = URL_SAFE_NO_PAD.encode(claims);
+        let signature = URL_SAFE_NO_PAD.encode(self.token_mac(&header, &claims).finalize().into_bytes());
+        Ok(format!("{}.{}.{}", header, claims, signature))
+    }
+
+    // MAC over the signing input of a JWT, its encoded header and claims
+    fn token_mac(&self, header: &str, claims: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(self.config.auth_token_secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(header.as_bytes());
+        mac.update(b".");
+        mac.update(claims.as_bytes());
+        mac
+    }
+
+    fn decode_segment<T: serde::de::DeserializeOwned>(segment: &str) -> SecurityResult<T> {
+        let json = URL_SAFE_NO_PAD.decode(segment)
+            .map_err(|_| SecurityError::Token("Token segment is not base64url".to_string()))?;
+        serde_json::from_slice(&json)
+            .map_err(|e| SecurityError::Token(format!("Token segment is malformed: {}", e)))
     }
 
     // Start background cleanup task
@@ -278,6 +633,10 @@ impl Clone for SecurityManager {
             config: self.config.clone(),
             active_tokens: Arc::clone(&self.active_tokens),
             revoked_tokens: Arc::clone(&self.revoked_tokens),
+            revocation_list: self.revocation_list.clone(),
+            revoked_entities: Arc::clone(&self.revoked_entities),
+            entity_revocation_list: self.entity_revocation_list.clone(),
+            clock: Arc::clone(&self.clock),
         }
     }
 }
@@ -0,0 +1,130 @@
+// nexus-prime-core/src/slo.rs - Evaluation of configured SLOs over recent deploy and command samples
+
+use crate::config::{SloConfig, SloMetric};
+use metrics::gauge;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy)]
+enum Sample {
+    Success(bool),
+    Latency(Duration),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloStatus {
+    pub name: String,
+    pub target: f64,
+    pub current: f64, // Fraction of good samples; 1.0 before any sample
+    pub samples: usize,
+    pub met: bool,
+    pub error_budget_remaining: f64, // Fraction of the allowed bad samples not yet used, 0.0 once exhausted
+}
+
+// Keeps the most recent `window` samples per metric and checks every configured SLO
+// against them. Each evaluation also publishes the `slo_met` and
+// `slo_error_budget_remaining` gauges.
+#[derive(Clone)]
+pub struct SloEvaluator {
+    slos: Vec<SloConfig>,
+    window: usize,
+    samples: Arc<Mutex<HashMap<SloMetric, VecDeque<Sample>>>>,
+    breached: Arc<Mutex<HashSet<String>>>, // SLOs already reported by new_breaches
+}
+
+impl SloEvaluator {
+    pub fn new(slos: Vec<SloConfig>, window: usize) -> Self {
+        Self {
+            slos,
+            window: window.max(1),
+            samples: Arc::new(Mutex::new(HashMap::new())),
+            breached: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub fn record_deploy(&self, succeeded: bool) {
+        self.record(SloMetric::DeploySuccess, Sample::Success(succeeded));
+    }
+
+    pub fn record_command_latency(&self, latency: Duration) {
+        self.record(SloMetric::CommandLatency, Sample::Latency(latency));
+    }
+
+    fn record(&self, metric: SloMetric, sample: Sample) {
+        let mut samples = self.samples.lock().unwrap();
+        let samples = samples.entry(metric).or_default();
+        samples.push_back(sample);
+        while samples.len() > self.window {
+            samples.pop_front();
+        }
+    }
+
+    pub fn evaluate(&self) -> Vec<SloStatus> {
+        let samples = self.samples.lock().unwrap();
+        self.slos.iter().map(|slo| {
+            let recent = samples.get(&slo.metric);
+            let total = recent.map_or(0, VecDeque::len);
+            let good = recent.map_or(0, |recent| recent.iter().filter(|sample| Self::is_good(slo, sample)).count());
+            let current = if total == 0 { 1.0 } else { good as f64 / total as f64 };
+            let allowed_bad = 1.0 - slo.target;
+            let bad = 1.0 - current;
+            let error_budget_remaining = if allowed_bad > 0.0 {
+                (1.0 - bad / allowed_bad).max(0.0)
+            } else if bad > 0.0 {
+                0.0
+            } else {
+                1.0
+            };
+            let status = SloStatus {
+                name: slo.name.clone(),
+                target: slo.target,
+                current,
+                samples: total,
+                met: current >= slo.target,
+                error_budget_remaining,
+            };
+            gauge!("slo_met", "slo" => status.name.clone()).set(if status.met { 1.0 } else { 0.0 });
+            gauge!("slo_error_budget_remaining", "slo" => status.name.clone()).set(status.error_budget_remaining);
+            status
+        }).collect()
+    }
+
+    // SLOs that are not met now but were when last checked. Each breach is reported
+    // once; an SLO is reported again only after it has been met in between.
+    pub fn new_breaches(&self) -> Vec<SloStatus> {
+        let statuses = self.evaluate();
+        let mut breached = self.breached.lock().unwrap();
+        statuses.into_iter()
+            .filter(|status| {
+                if status.met {
+                    breached.remove(&status.name);
+                    false
+                } else {
+                    breached.insert(status.name.clone())
+                }
+            })
+            .collect()
+    }
+
+    fn is_good(slo: &SloConfig, sample: &Sample) -> bool {
+        match sample {
+            Sample::Success(succeeded) => *succeeded,
+            Sample::Latency(latency) => slo.latency_threshold_ms
+                .is_none_or(|threshold_ms| *latency <= Duration::from_millis(threshold_ms)),
+        }
+    }
+}
+
+impl From<SloStatus> for crate::fabric_proto::fabric::SloStatus {
+    fn from(status: SloStatus) -> Self {
+        Self {
+            name: status.name,
+            target: status.target,
+            current: status.current,
+            samples: status.samples as u64,
+            met: status.met,
+            error_budget_remaining: status.error_budget_remaining,
+        }
+    }
+}
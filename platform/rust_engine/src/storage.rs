@@ -1,19 +1,26 @@
 // nexus-prime-core/src/storage.rs - Advanced Storage Abstraction Layer
 
-use crate::config::{DatabaseConfig, NexusConfig};
+use crate::config::{DatabaseConfig, NexusConfig, RetentionPolicy, StorageBackend};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use rocksdb::{DB, Options as RocksOptions};
 use serde::{Deserialize, Serialize};
 use sqlx::{Pool, Postgres, PgPool, Row};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::types::Json;
 use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
+use tracing::Instrument;
 use uuid::Uuid;
 
 pub type StorageResult<T> = Result<T, StorageError>;
 
+// PostgreSQL takes at most 65535 bind parameters per statement, nine per telemetry row
+const TELEMETRY_ROWS_PER_INSERT: usize = 7000;
+
 #[derive(Debug, thiserror::Error)]
 pub enum StorageError {
     #[error("Database error: {0}")]
@@ -22,6 +29,10 @@ pub enum StorageError {
     RocksDB(#[from] rocksdb::Error),
     #[error("Serialization error: {0}")]
     Serialization(#[from] bincode::Error),
+    #[error("Deserialization error: {0}")]
+    Deserialization(String),
+    #[error("Encoding error: {0}")]
+    Encoding(#[from] crate::compression::EncodingError),
     #[error("Not found: {0}")]
     NotFound(String),
     #[error("Configuration error: {0}")]
@@ -33,6 +44,7 @@ pub enum StorageError {
 pub trait NodeStorage: Send + Sync {
     async fn store_node(&self, node: &FabricNode) -> StorageResult<()>;
     async fn get_node(&self, node_id: &str) -> StorageResult<Option<FabricNode>>;
+    // Implementations must return nodes sorted by node_id
     async fn list_nodes(&self) -> StorageResult<Vec<FabricNode>>;
     async fn update_node_status(&self, node_id: &str, status: NodeStatus) -> StorageResult<()>;
     async fn delete_node(&self, node_id: &str) -> StorageResult<()>;
@@ -42,6 +54,7 @@ pub trait NodeStorage: Send + Sync {
 pub trait AgentStorage: Send + Sync {
     async fn store_agent(&self, agent: &AIAgent) -> StorageResult<()>;
     async fn get_agent(&self, agent_id: &str) -> StorageResult<Option<AIAgent>>;
+    // Implementations must return agents sorted by agent_id
     async fn list_agents(&self) -> StorageResult<Vec<AIAgent>>;
     async fn list_agents_by_node(&self, node_id: &str) -> StorageResult<Vec<AIAgent>>;
     async fn update_agent_status(&self, agent_id: &str, status: AgentStatus) -> StorageResult<()>;
@@ -51,9 +64,41 @@ pub trait AgentStorage: Send + Sync {
 #[async_trait]
 pub trait TelemetryStorage: Send + Sync {
     async fn store_telemetry(&self, telemetry: &TelemetryRecord) -> StorageResult<()>;
+    // Stores the records in as few round trips as the backend allows
+    async fn store_telemetry_many(&self, records: &[TelemetryRecord]) -> StorageResult<()> {
+        for record in records {
+            self.store_telemetry(record).await?;
+        }
+        Ok(())
+    }
     async fn get_latest_telemetry(&self, entity_id: &str) -> StorageResult<Option<TelemetryRecord>>;
     async fn get_telemetry_history(&self, entity_id: &str, hours: u32) -> StorageResult<Vec<TelemetryRecord>>;
-    async fn cleanup_old_telemetry(&self, days: u32) -> StorageResult<u64>;
+    // Removes records older than the retention for their entity_type, returning how many
+    async fn cleanup_old_telemetry(&self, retention: &RetentionPolicy) -> StorageResult<u64>;
+}
+
+// Everything the fabric stores, whichever backend holds it
+pub trait Storage: NodeStorage + AgentStorage + TelemetryStorage {}
+
+impl<S: NodeStorage + AgentStorage + TelemetryStorage> Storage for S {}
+
+// Open the backend selected by `config.backend`. Node and agent reads from the hybrid
+// backend go through a CachedStorage sized by the cache settings.
+pub async fn open_storage(config: DatabaseConfig) -> StorageResult<Arc<dyn Storage>> {
+    open_storage_with_pool(config).await.map(|(storage, _)| storage)
+}
+
+// Like open_storage, also returning the backend's PostgreSQL pool when it has one, so
+// health checks probe the connections storage actually uses
+pub async fn open_storage_with_pool(config: DatabaseConfig) -> StorageResult<(Arc<dyn Storage>, Option<PgPool>)> {
+    match config.backend {
+        StorageBackend::Hybrid => {
+            let hybrid = HybridStorage::new(config.clone()).await?;
+            let pool = hybrid.postgres_pool().cloned();
+            Ok((Arc::new(CachedStorage::new(hybrid, &config)), pool))
+        }
+        StorageBackend::Memory => Ok((Arc::new(InMemoryStorage::new()), None)),
+    }
 }
 
 // Data structures
@@ -114,13 +159,44 @@ pub struct TelemetryRecord {
     pub entity_id: String,
     pub entity_type: String, // "node" or "agent"
     pub timestamp: DateTime<Utc>,
-    pub cpu_utilization: f32,
-    pub memory_utilization: f32,
-    pub network_in_kbps: f32,
-    pub network_out_kbps: f32,
+    // None when the entity did not report the metric, which is not the same as zero
+    pub cpu_utilization: Option<f32>,
+    pub memory_utilization: Option<f32>,
+    pub network_in_kbps: Option<f32>,
+    pub network_out_kbps: Option<f32>,
     pub custom_metrics: HashMap<String, f32>,
 }
 
+// The telemetry layout written before the metrics became optional, as plain bincode
+#[derive(Deserialize)]
+struct TelemetryRecordV1 {
+    id: Uuid,
+    entity_id: String,
+    entity_type: String,
+    timestamp: DateTime<Utc>,
+    cpu_utilization: f32,
+    memory_utilization: f32,
+    network_in_kbps: f32,
+    network_out_kbps: f32,
+    custom_metrics: HashMap<String, f32>,
+}
+
+impl From<TelemetryRecordV1> for TelemetryRecord {
+    fn from(record: TelemetryRecordV1) -> Self {
+        Self {
+            id: record.id,
+            entity_id: record.entity_id,
+            entity_type: record.entity_type,
+            timestamp: record.timestamp,
+            cpu_utilization: Some(record.cpu_utilization),
+            memory_utilization: Some(record.memory_utilization),
+            network_in_kbps: Some(record.network_in_kbps),
+            network_out_kbps: Some(record.network_out_kbps),
+            custom_metrics: record.custom_metrics,
+        }
+    }
+}
+
 // Hybrid storage implementation that can use both RocksDB and PostgreSQL
 pub struct HybridStorage {
     config: DatabaseConfig,
@@ -145,23 +221,48 @@ impl HybridStorage {
         };
 
         let postgres = if let Some(url) = &config.postgres_url {
-            let pool = PgPool::connect(url).await?;
-            
-            // Initialize TimescaleDB if enabled
-            if config.use_timescaledb {
-                Self::init_timescaledb(&pool).await?;
-            }
-            
+            let pool = PgPoolOptions::new()
+                .max_connections(config.max_connections)
+                .acquire_timeout(std::time::Duration::from_secs(config.acquire_timeout_secs))
+                .connect_lazy(url)
+                .map_err(|e| StorageError::Config(format!("Invalid database.postgres_url: {}", e)))?;
             Some(pool)
         } else {
             None
         };
 
-        Ok(Self {
+        let storage = Self {
             config,
             rocksdb,
             postgres,
-        })
+        };
+
+        // The pool connects lazily, so check now that PostgreSQL answers
+        storage.ping().await.map_err(|e| {
+            StorageError::Config(format!("PostgreSQL at database.postgres_url is unreachable: {}", e))
+        })?;
+
+        if let Some(pool) = &storage.postgres {
+            // Initialize TimescaleDB if enabled
+            if storage.config.use_timescaledb {
+                Self::init_timescaledb(pool).await?;
+            }
+        }
+
+        Ok(storage)
+    }
+
+    // Round trip to PostgreSQL, for startup and the database health check.
+    // Passes when no PostgreSQL is configured.
+    pub async fn ping(&self) -> StorageResult<()> {
+        if let Some(pg) = &self.postgres {
+            sqlx::query("SELECT 1").execute(pg).await?;
+        }
+        Ok(())
+    }
+
+    pub fn postgres_pool(&self) -> Option<&PgPool> {
+        self.postgres.as_ref()
     }
 
     async fn init_timescaledb(pool: &PgPool) -> StorageResult<()> {
@@ -170,7 +271,8 @@ impl HybridStorage {
             .execute(pool)
             .await?;
 
-        // Create telemetry table as hypertable for efficient time-series storage
+        // Create telemetry table as hypertable for efficient time-series storage.
+        // Metric columns are nullable: NULL means the metric was not reported.
         sqlx::query(r#"
             CREATE TABLE IF NOT EXISTS telemetry (
                 id UUID PRIMARY KEY,
@@ -203,162 +305,888 @@ impl HybridStorage {
         format!("agent:{}", agent_id)
     }
 
-    fn telemetry_key(entity_id: &str, timestamp: DateTime<Utc>) -> String {
-        format!("telemetry:{}:{}", entity_id, timestamp.timestamp())
+    // A stored record that does not decode is corrupt, which is a read error rather
+    // than a failure to serialize
+    fn decode_record<T: serde::de::DeserializeOwned>(key: &[u8], value: &[u8]) -> StorageResult<T> {
+        bincode::deserialize(value).map_err(|e| {
+            StorageError::Deserialization(format!("{} is corrupt: {}", String::from_utf8_lossy(key), e))
+        })
     }
-}
 
-#[async_trait]
-impl NodeStorage for HybridStorage {
-    async fn store_node(&self, node: &FabricNode) -> StorageResult<()> {
-        // Store in RocksDB for fast access
-        if let Some(rocks) = &self.rocksdb {
-            let key = Self::node_key(&node.node_id);
-            let value = bincode::serialize(node)?;
-            rocks.put(key.as_bytes(), value)?;
-        }
+    // Telemetry values use the fabric state encoding, so they are compressed as configured
+    fn encode_telemetry(&self, record: &TelemetryRecord) -> StorageResult<Vec<u8>> {
+        Ok(crate::compression::encode(record, self.config.compression, self.config.compression_level)?)
+    }
 
-        // Store in PostgreSQL for complex queries
-        if let Some(pg) = &self.postgres {
-            sqlx::query(r#"
-                INSERT INTO nodes (node_id, ip_address, proxy_listen_address, capabilities, 
-                                 agent_type, status, last_seen, created_at, metadata)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-                ON CONFLICT (node_id) DO UPDATE SET
-                    ip_address = EXCLUDED.ip_address,
-                    proxy_listen_address = EXCLUDED.proxy_listen_address,
-                    capabilities = EXCLUDED.capabilities,
-                    status = EXCLUDED.status,
-                    last_seen = EXCLUDED.last_seen,
-                    metadata = EXCLUDED.metadata
-            "#)
-            .bind(&node.node_id)
-            .bind(&node.ip_address)
-            .bind(&node.proxy_listen_address)
-            .bind(&node.capabilities)
-            .bind(&node.agent_type)
-            .bind(serde_json::to_string(&node.status).unwrap_or_default())
-            .bind(node.last_seen)
-            .bind(node.created_at)
-            .bind(serde_json::to_value(&node.metadata).unwrap_or_default())
-            .execute(pg)
-            .await?;
+    // Records written as plain bincode come in two layouts, from before and after the
+    // metrics became optional. Bincode is positional and tolerates trailing bytes, so
+    // each layout must consume the whole value to count as a match.
+    fn decode_telemetry(key: &[u8], value: &[u8]) -> Option<TelemetryRecord> {
+        use bincode::Options;
+        let strict = || bincode::DefaultOptions::new().with_fixint_encoding().reject_trailing_bytes();
+        let record = strict().deserialize::<TelemetryRecord>(value).ok()
+            .or_else(|| strict().deserialize::<TelemetryRecordV1>(value).ok().map(TelemetryRecord::from))
+            .or_else(|| crate::compression::decode::<TelemetryRecord>(value).ok());
+        if record.is_none() {
+            tracing::warn!("Skipping undecodable telemetry record {}", String::from_utf8_lossy(key));
         }
+        record
+    }
 
-        Ok(())
+    // The record id keeps records an entity sends within the same second apart. Keys
+    // written without it (`telemetry:{entity}:{timestamp}`) share the entity prefix,
+    // so scans still find them.
+    fn telemetry_key(record: &TelemetryRecord) -> String {
+        format!("telemetry:{}:{}:{}", record.entity_id, record.timestamp.timestamp(), record.id)
     }
 
-    async fn get_node(&self, node_id: &str) -> StorageResult<Option<FabricNode>> {
-        // Try RocksDB first for fast access
-        if let Some(rocks) = &self.rocksdb {
-            let key = Self::node_key(node_id);
-            if let Ok(Some(value)) = rocks.get(key.as_bytes()) {
-                if let Ok(node) = bincode::deserialize(&value) {
-                    return Ok(Some(node));
-                }
+    // Every telemetry record kept in RocksDB for the entity, oldest first
+    fn rocksdb_telemetry(rocks: &DB, entity_id: &str) -> StorageResult<Vec<TelemetryRecord>> {
+        let prefix = format!("telemetry:{}:", entity_id);
+        let mut records = Vec::new();
+        for item in rocks.prefix_iterator(prefix.as_bytes()) {
+            let (key, value) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            if let Some(record) = Self::decode_telemetry(&key, &value) {
+                records.push(record);
             }
         }
+        records.sort_by_key(|record| record.timestamp);
+        Ok(records)
+    }
 
-        // Fallback to PostgreSQL
+    // Agents sorted by agent_id, optionally only those on one node
+    async fn query_agents(&self, node_id: Option<&str>) -> StorageResult<Vec<AIAgent>> {
         if let Some(pg) = &self.postgres {
-            let row = sqlx::query("SELECT * FROM nodes WHERE node_id = $1")
+            let rows = sqlx::query("SELECT * FROM agents WHERE $1::TEXT IS NULL OR node_id = $1 ORDER BY agent_id")
                 .bind(node_id)
-                .fetch_optional(pg)
+                .fetch_all(pg)
                 .await?;
+            return rows.iter().map(Self::agent_from_row).collect();
+        }
 
-            if let Some(row) = row {
-                // Convert row to FabricNode (implementation depends on exact schema)
-                // This is a simplified version
-                return Ok(Some(FabricNode {
-                    node_id: row.get("node_id"),
-                    ip_address: row.get("ip_address"),
-                    proxy_listen_address: row.get("proxy_listen_address"),
-                    capabilities: row.get("capabilities"),
-                    agent_type: row.get("agent_type"),
-                    status: serde_json::from_str(&row.get::<String, _>("status")).unwrap_or(NodeStatus::Offline),
-                    last_seen: row.get("last_seen"),
-                    created_at: row.get("created_at"),
-                    metadata: serde_json::from_value(row.get("metadata")).unwrap_or_default(),
-                }));
+        let mut agents = Vec::new();
+        if let Some(rocks) = &self.rocksdb {
+            for item in rocks.prefix_iterator(b"agent:") {
+                let (key, value) = item?;
+                if !key.starts_with(b"agent:") {
+                    break;
+                }
+                let agent: AIAgent = Self::decode_record(&key, &value)?;
+                if node_id.is_none_or(|node_id| agent.node_id == node_id) {
+                    agents.push(agent);
+                }
             }
         }
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        Ok(agents)
+    }
 
-        Ok(None)
+    fn node_from_row(row: &sqlx::postgres::PgRow) -> StorageResult<FabricNode> {
+        let node_id: String = row.try_get("node_id")?;
+        let corrupt = |column: &str, e: serde_json::Error| {
+            StorageError::Deserialization(format!("node {} has invalid {}: {}", node_id, column, e))
+        };
+
+        Ok(FabricNode {
+            status: serde_json::from_str(&row.try_get::<String, _>("status")?)
+                .map_err(|e| corrupt("status", e))?,
+            metadata: serde_json::from_value(row.try_get("metadata")?)
+                .map_err(|e| corrupt("metadata", e))?,
+            ip_address: row.try_get("ip_address")?,
+            proxy_listen_address: row.try_get("proxy_listen_address")?,
+            capabilities: row.try_get("capabilities")?,
+            agent_type: row.try_get("agent_type")?,
+            last_seen: row.try_get("last_seen")?,
+            created_at: row.try_get("created_at")?,
+            node_id,
+        })
+    }
+
+    fn agent_from_row(row: &sqlx::postgres::PgRow) -> StorageResult<AIAgent> {
+        Ok(AIAgent {
+            agent_id: row.try_get("agent_id")?,
+            node_id: row.try_get("node_id")?,
+            name: row.try_get("name")?,
+            agent_type: row.try_get("agent_type")?,
+            status: row.try_get::<Json<AgentStatus>, _>("status")?.0,
+            created_at: row.try_get("created_at")?,
+            last_active: row.try_get("last_active")?,
+            config: row.try_get::<Json<HashMap<String, String>>, _>("config")?.0,
+            resources: row.try_get::<Json<AgentResources>, _>("resources")?.0,
+        })
+    }
+
+    fn telemetry_from_row(row: &sqlx::postgres::PgRow) -> TelemetryRecord {
+        TelemetryRecord {
+            id: row.get("id"),
+            entity_id: row.get("entity_id"),
+            entity_type: row.get("entity_type"),
+            timestamp: row.get("timestamp"),
+            cpu_utilization: row.get("cpu_utilization"),
+            memory_utilization: row.get("memory_utilization"),
+            network_in_kbps: row.get("network_in_kbps"),
+            network_out_kbps: row.get("network_out_kbps"),
+            custom_metrics: row.get::<Option<serde_json::Value>, _>("custom_metrics")
+                .and_then(|metrics| serde_json::from_value(metrics).ok())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Run one storage call in a span carrying the `db.operation` and `db.table` attributes
+// of the tracer's database spans, so a call made while handling a request shows up as
+// its child. Rows affected and duration are recorded once the call returns.
+async fn traced<T>(
+    operation: &'static str,
+    table: &'static str,
+    call: impl std::future::Future<Output = StorageResult<(T, u64)>>,
+) -> StorageResult<T> {
+    let span = tracing::info_span!(
+        "db",
+        db.operation = operation,
+        db.table = table,
+        db.rows_affected = tracing::field::Empty,
+        duration_ms = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    let started = std::time::Instant::now();
+    let result = call.instrument(span.clone()).await;
+    span.record("duration_ms", started.elapsed().as_millis() as u64);
+    match result {
+        Ok((value, rows)) => {
+            span.record("db.rows_affected", rows);
+            Ok(value)
+        }
+        Err(e) => {
+            span.record("error", tracing::field::display(&e));
+            Err(e)
+        }
+    }
+}
+
+#[async_trait]
+impl NodeStorage for HybridStorage {
+    async fn store_node(&self, node: &FabricNode) -> StorageResult<()> {
+        traced("store", "nodes", async {
+            // Store in RocksDB for fast access
+            if let Some(rocks) = &self.rocksdb {
+                let key = Self::node_key(&node.node_id);
+                let value = bincode::serialize(node)?;
+                rocks.put(key.as_bytes(), value)?;
+            }
+
+            // Store in PostgreSQL for complex queries
+            if let Some(pg) = &self.postgres {
+                sqlx::query(r#"
+                    INSERT INTO nodes (node_id, ip_address, proxy_listen_address, capabilities, 
+                                     agent_type, status, last_seen, created_at, metadata)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    ON CONFLICT (node_id) DO UPDATE SET
+                        ip_address = EXCLUDED.ip_address,
+                        proxy_listen_address = EXCLUDED.proxy_listen_address,
+                        capabilities = EXCLUDED.capabilities,
+                        status = EXCLUDED.status,
+                        last_seen = EXCLUDED.last_seen,
+                        metadata = EXCLUDED.metadata
+                "#)
+                .bind(&node.node_id)
+                .bind(&node.ip_address)
+                .bind(&node.proxy_listen_address)
+                .bind(&node.capabilities)
+                .bind(&node.agent_type)
+                .bind(serde_json::to_string(&node.status).unwrap_or_default())
+                .bind(node.last_seen)
+                .bind(node.created_at)
+                .bind(serde_json::to_value(&node.metadata).unwrap_or_default())
+                .execute(pg)
+                .await?;
+            }
+
+            Ok(((), 1))
+        }).await
+    }
+
+    async fn get_node(&self, node_id: &str) -> StorageResult<Option<FabricNode>> {
+        traced("get", "nodes", async {
+            // Try RocksDB first for fast access
+            if let Some(rocks) = &self.rocksdb {
+                let key = Self::node_key(node_id);
+                if let Some(value) = rocks.get(key.as_bytes())? {
+                    return Ok((Some(Self::decode_record(key.as_bytes(), &value)?), 1));
+                }
+            }
+
+            // Fallback to PostgreSQL only when RocksDB has no record
+            if let Some(pg) = &self.postgres {
+                let row = sqlx::query("SELECT * FROM nodes WHERE node_id = $1")
+                    .bind(node_id)
+                    .fetch_optional(pg)
+                    .await?;
+
+                if let Some(row) = row {
+                    return Ok((Some(Self::node_from_row(&row)?), 1));
+                }
+            }
+
+            Ok((None, 0))
+        }).await
     }
 
     async fn list_nodes(&self) -> StorageResult<Vec<FabricNode>> {
-        // Use PostgreSQL for complex queries if available
-        if let Some(pg) = &self.postgres {
-            let rows = sqlx::query("SELECT * FROM nodes ORDER BY created_at")
-                .fetch_all(pg)
+        traced("list", "nodes", async {
+            // Use PostgreSQL for complex queries if available
+            if let Some(pg) = &self.postgres {
+                let rows = sqlx::query("SELECT * FROM nodes ORDER BY node_id")
+                    .fetch_all(pg)
+                    .await?;
+
+                let nodes = rows.iter().map(Self::node_from_row).collect::<StorageResult<Vec<_>>>()?;
+
+                let count = nodes.len() as u64;
+                return Ok((nodes, count));
+            }
+
+            // Fallback to RocksDB iteration (less efficient for this operation)
+            if let Some(rocks) = &self.rocksdb {
+                let mut nodes = Vec::new();
+                let iter = rocks.iterator(rocksdb::IteratorMode::Start);
+                
+                for item in iter {
+                    let (key, value) = item?;
+                    if let Ok(key_str) = String::from_utf8(key.to_vec()) {
+                        if key_str.starts_with("node:") {
+                            if let Ok(node) = bincode::deserialize::<FabricNode>(&value) {
+                                nodes.push(node);
+                            }
+                        }
+                    }
+                }
+                nodes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+                
+                let count = nodes.len() as u64;
+                return Ok((nodes, count));
+            }
+
+            Ok((vec![], 0))
+        }).await
+    }
+
+    async fn update_node_status(&self, node_id: &str, status: NodeStatus) -> StorageResult<()> {
+        traced("update", "nodes", async {
+            // Update in both stores
+            let Some(mut node) = self.get_node(node_id).await? else {
+                return Ok(((), 0));
+            };
+            node.status = status;
+            node.last_seen = Utc::now();
+            self.store_node(&node).await?;
+            Ok(((), 1))
+        }).await
+    }
+
+    async fn delete_node(&self, node_id: &str) -> StorageResult<()> {
+        traced("delete", "nodes", async {
+            let mut deleted = 0;
+
+            // Delete from RocksDB
+            if let Some(rocks) = &self.rocksdb {
+                let key = Self::node_key(node_id);
+                rocks.delete(key.as_bytes())?;
+                deleted = 1;
+            }
+
+            // Delete from PostgreSQL, which knows whether the node existed
+            if let Some(pg) = &self.postgres {
+                deleted = sqlx::query("DELETE FROM nodes WHERE node_id = $1")
+                    .bind(node_id)
+                    .execute(pg)
+                    .await?
+                    .rows_affected();
+            }
+
+            Ok(((), deleted))
+        }).await
+    }
+}
+
+#[async_trait]
+impl AgentStorage for HybridStorage {
+    async fn store_agent(&self, agent: &AIAgent) -> StorageResult<()> {
+        traced("store", "agents", async {
+            if let Some(rocks) = &self.rocksdb {
+                rocks.put(Self::agent_key(&agent.agent_id).as_bytes(), bincode::serialize(agent)?)?;
+            }
+
+            if let Some(pg) = &self.postgres {
+                sqlx::query(r#"
+                    INSERT INTO agents (agent_id, node_id, name, agent_type, status,
+                                      created_at, last_active, config, resources)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    ON CONFLICT (agent_id) DO UPDATE SET
+                        node_id = EXCLUDED.node_id,
+                        name = EXCLUDED.name,
+                        status = EXCLUDED.status,
+                        last_active = EXCLUDED.last_active,
+                        config = EXCLUDED.config,
+                        resources = EXCLUDED.resources
+                "#)
+                .bind(&agent.agent_id)
+                .bind(&agent.node_id)
+                .bind(&agent.name)
+                .bind(&agent.agent_type)
+                .bind(Json(&agent.status))
+                .bind(agent.created_at)
+                .bind(agent.last_active)
+                .bind(Json(&agent.config))
+                .bind(Json(&agent.resources))
+                .execute(pg)
                 .await?;
+            }
+
+            Ok(((), 1))
+        }).await
+    }
 
-            let nodes = rows.into_iter().map(|row| {
-                FabricNode {
-                    node_id: row.get("node_id"),
-                    ip_address: row.get("ip_address"),
-                    proxy_listen_address: row.get("proxy_listen_address"),
-                    capabilities: row.get("capabilities"),
-                    agent_type: row.get("agent_type"),
-                    status: serde_json::from_str(&row.get::<String, _>("status")).unwrap_or(NodeStatus::Offline),
-                    last_seen: row.get("last_seen"),
-                    created_at: row.get("created_at"),
-                    metadata: serde_json::from_value(row.get("metadata")).unwrap_or_default(),
+    async fn get_agent(&self, agent_id: &str) -> StorageResult<Option<AIAgent>> {
+        traced("get", "agents", async {
+            if let Some(rocks) = &self.rocksdb {
+                let key = Self::agent_key(agent_id);
+                if let Some(value) = rocks.get(key.as_bytes())? {
+                    return Ok((Some(Self::decode_record(key.as_bytes(), &value)?), 1));
                 }
-            }).collect();
+            }
 
-            return Ok(nodes);
-        }
+            if let Some(pg) = &self.postgres {
+                let row = sqlx::query("SELECT * FROM agents WHERE agent_id = $1")
+                    .bind(agent_id)
+                    .fetch_optional(pg)
+                    .await?;
+                if let Some(row) = row {
+                    return Ok((Some(Self::agent_from_row(&row)?), 1));
+                }
+            }
 
-        // Fallback to RocksDB iteration (less efficient for this operation)
-        if let Some(rocks) = &self.rocksdb {
-            let mut nodes = Vec::new();
-            let iter = rocks.iterator(rocksdb::IteratorMode::Start);
-            
-            for (key, value) in iter {
-                if let Ok(key_str) = String::from_utf8(key.to_vec()) {
-                    if key_str.starts_with("node:") {
-                        if let Ok(node) = bincode::deserialize::<FabricNode>(&value) {
-                            nodes.push(node);
+            Ok((None, 0))
+        }).await
+    }
+
+    async fn list_agents(&self) -> StorageResult<Vec<AIAgent>> {
+        traced("list", "agents", async {
+            let agents = self.query_agents(None).await?;
+            let count = agents.len() as u64;
+            Ok((agents, count))
+        }).await
+    }
+
+    async fn list_agents_by_node(&self, node_id: &str) -> StorageResult<Vec<AIAgent>> {
+        traced("list", "agents", async {
+            let agents = self.query_agents(Some(node_id)).await?;
+            let count = agents.len() as u64;
+            Ok((agents, count))
+        }).await
+    }
+
+    async fn update_agent_status(&self, agent_id: &str, status: AgentStatus) -> StorageResult<()> {
+        traced("update", "agents", async {
+            let Some(mut agent) = self.get_agent(agent_id).await? else {
+                return Ok(((), 0));
+            };
+            agent.status = status;
+            agent.last_active = Utc::now();
+            self.store_agent(&agent).await?;
+            Ok(((), 1))
+        }).await
+    }
+
+    async fn delete_agent(&self, agent_id: &str) -> StorageResult<()> {
+        traced("delete", "agents", async {
+            let mut deleted = 0;
+
+            if let Some(rocks) = &self.rocksdb {
+                rocks.delete(Self::agent_key(agent_id).as_bytes())?;
+                deleted = 1;
+            }
+
+            // PostgreSQL knows whether the agent existed
+            if let Some(pg) = &self.postgres {
+                deleted = sqlx::query("DELETE FROM agents WHERE agent_id = $1")
+                    .bind(agent_id)
+                    .execute(pg)
+                    .await?
+                    .rows_affected();
+            }
+
+            Ok(((), deleted))
+        }).await
+    }
+}
+
+#[async_trait]
+impl TelemetryStorage for HybridStorage {
+    async fn store_telemetry(&self, telemetry: &TelemetryRecord) -> StorageResult<()> {
+        traced("store", "telemetry", async {
+            if let Some(rocks) = &self.rocksdb {
+                rocks.put(Self::telemetry_key(telemetry).as_bytes(), self.encode_telemetry(telemetry)?)?;
+            }
+
+            if let Some(pg) = &self.postgres {
+                sqlx::query(r#"
+                    INSERT INTO telemetry (id, entity_id, entity_type, timestamp, cpu_utilization,
+                                           memory_utilization, network_in_kbps, network_out_kbps, custom_metrics)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                "#)
+                .bind(telemetry.id)
+                .bind(&telemetry.entity_id)
+                .bind(&telemetry.entity_type)
+                .bind(telemetry.timestamp)
+                .bind(telemetry.cpu_utilization)
+                .bind(telemetry.memory_utilization)
+                .bind(telemetry.network_in_kbps)
+                .bind(telemetry.network_out_kbps)
+                .bind(serde_json::to_value(&telemetry.custom_metrics).unwrap_or_default())
+                .execute(pg)
+                .await?;
+            }
+
+            Ok(((), 1))
+        }).await
+    }
+
+    async fn store_telemetry_many(&self, records: &[TelemetryRecord]) -> StorageResult<()> {
+        traced("store", "telemetry", async {
+            if let Some(rocks) = &self.rocksdb {
+                let mut batch = rocksdb::WriteBatch::default();
+                for record in records {
+                    batch.put(Self::telemetry_key(record).as_bytes(), self.encode_telemetry(record)?);
+                }
+                rocks.write(batch)?;
+            }
+
+            if let Some(pg) = &self.postgres {
+                for chunk in records.chunks(TELEMETRY_ROWS_PER_INSERT) {
+                    let mut insert = sqlx::QueryBuilder::<Postgres>::new(
+                        "INSERT INTO telemetry (id, entity_id, entity_type, timestamp, cpu_utilization, \
+                         memory_utilization, network_in_kbps, network_out_kbps, custom_metrics) ",
+                    );
+                    insert.push_values(chunk, |mut row, record| {
+                        row.push_bind(record.id)
+                            .push_bind(&record.entity_id)
+                            .push_bind(&record.entity_type)
+                            .push_bind(record.timestamp)
+                            .push_bind(record.cpu_utilization)
+                            .push_bind(record.memory_utilization)
+                            .push_bind(record.network_in_kbps)
+                            .push_bind(record.network_out_kbps)
+                            .push_bind(serde_json::to_value(&record.custom_metrics).unwrap_or_default());
+                    });
+                    insert.build().execute(pg).await?;
+                }
+            }
+
+            Ok(((), records.len() as u64))
+        }).await
+    }
+
+    async fn get_latest_telemetry(&self, entity_id: &str) -> StorageResult<Option<TelemetryRecord>> {
+        traced("get", "telemetry", async {
+            if let Some(pg) = &self.postgres {
+                let row = sqlx::query("SELECT * FROM telemetry WHERE entity_id = $1 ORDER BY timestamp DESC LIMIT 1")
+                    .bind(entity_id)
+                    .fetch_optional(pg)
+                    .await?;
+                let record = row.as_ref().map(Self::telemetry_from_row);
+                let count = record.is_some() as u64;
+                return Ok((record, count));
+            }
+
+            if let Some(rocks) = &self.rocksdb {
+                let record = Self::rocksdb_telemetry(rocks, entity_id)?.pop();
+                let count = record.is_some() as u64;
+                return Ok((record, count));
+            }
+
+            Ok((None, 0))
+        }).await
+    }
+
+    async fn get_telemetry_history(&self, entity_id: &str, hours: u32) -> StorageResult<Vec<TelemetryRecord>> {
+        traced("list", "telemetry", async {
+            let since = Utc::now() - chrono::Duration::hours(i64::from(hours));
+
+            if let Some(pg) = &self.postgres {
+                let rows = sqlx::query("SELECT * FROM telemetry WHERE entity_id = $1 AND timestamp >= $2 ORDER BY timestamp")
+                    .bind(entity_id)
+                    .bind(since)
+                    .fetch_all(pg)
+                    .await?;
+                let records: Vec<TelemetryRecord> = rows.iter().map(Self::telemetry_from_row).collect();
+                let count = records.len() as u64;
+                return Ok((records, count));
+            }
+
+            if let Some(rocks) = &self.rocksdb {
+                let mut records = Self::rocksdb_telemetry(rocks, entity_id)?;
+                records.retain(|record| record.timestamp >= since);
+                let count = records.len() as u64;
+                return Ok((records, count));
+            }
+
+            Ok((vec![], 0))
+        }).await
+    }
+
+    async fn cleanup_old_telemetry(&self, retention: &RetentionPolicy) -> StorageResult<u64> {
+        traced("delete", "telemetry", async {
+            let now = Utc::now();
+            let cutoff = |days: u32| now - chrono::Duration::days(i64::from(days));
+            let mut deleted = 0;
+
+            if let Some(rocks) = &self.rocksdb {
+                let mut batch = rocksdb::WriteBatch::default();
+                for item in rocks.prefix_iterator(b"telemetry:") {
+                    let (key, value) = item?;
+                    if !key.starts_with(b"telemetry:") {
+                        break;
+                    }
+                    if let Some(record) = Self::decode_telemetry(&key, &value) {
+                        if record.timestamp < cutoff(retention.days_for(&record.entity_type)) {
+                            batch.delete(&key);
+                            deleted += 1;
                         }
                     }
                 }
+                rocks.write(batch)?;
+            }
+
+            // PostgreSQL knows how many rows it held, so its count wins
+            if let Some(pg) = &self.postgres {
+                deleted = 0;
+                let mut entity_types = Vec::new();
+                for (entity_type, days) in &retention.days_by_entity_type {
+                    deleted += sqlx::query("DELETE FROM telemetry WHERE entity_type = $1 AND timestamp < $2")
+                        .bind(entity_type)
+                        .bind(cutoff(*days))
+                        .execute(pg)
+                        .await?
+                        .rows_affected();
+                    entity_types.push(entity_type.clone());
+                }
+                deleted += sqlx::query("DELETE FROM telemetry WHERE entity_type <> ALL($1) AND timestamp < $2")
+                    .bind(entity_types)
+                    .bind(cutoff(retention.default_days))
+                    .execute(pg)
+                    .await?
+                    .rows_affected();
+            }
+
+            Ok((deleted, deleted))
+        }).await
+    }
+}
+
+// Read-through cache in front of node/agent storage. Entries expire after the
+// configured TTL and are invalidated once any write or delete for the same id is done.
+// Telemetry passes straight through.
+struct CacheEntry<T> {
+    value: T,
+    inserted_at: std::time::Instant,
+}
+
+pub struct CachedStorage<S> {
+    inner: S,
+    ttl: std::time::Duration,
+    max_entries: usize,
+    nodes: RwLock<HashMap<String, CacheEntry<FabricNode>>>,
+    agents: RwLock<HashMap<String, CacheEntry<AIAgent>>>,
+    generation: AtomicU64, // Bumped by every invalidation; a read that saw one begin does not cache its result
+}
+
+impl<S> CachedStorage<S> {
+    pub fn new(inner: S, config: &DatabaseConfig) -> Self {
+        Self {
+            inner,
+            ttl: std::time::Duration::from_secs(config.cache_ttl_seconds),
+            max_entries: config.cache_max_entries,
+            nodes: RwLock::new(HashMap::new()),
+            agents: RwLock::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    pub async fn invalidate_node(&self, node_id: &str) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.nodes.write().await.remove(node_id);
+    }
+
+    pub async fn invalidate_agent(&self, agent_id: &str) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.agents.write().await.remove(agent_id);
+    }
+
+    async fn cached<T: Clone>(&self, cache: &RwLock<HashMap<String, CacheEntry<T>>>, id: &str) -> Option<T> {
+        let cache = cache.read().await;
+        cache.get(id)
+            .filter(|entry| entry.inserted_at.elapsed() < self.ttl)
+            .map(|entry| entry.value.clone())
+    }
+
+    // Cache a value read from the inner storage, unless something was invalidated since
+    // `generation` was taken before the read, in which case the value may be stale
+    async fn insert_cached<T>(&self, cache: &RwLock<HashMap<String, CacheEntry<T>>>, id: &str, value: T, generation: u64) {
+        if self.max_entries == 0 || self.ttl.is_zero() {
+            return;
+        }
+
+        let mut cache = cache.write().await;
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        cache.retain(|_, entry| entry.inserted_at.elapsed() < self.ttl);
+
+        // Evict the oldest entry once the cache is full
+        if cache.len() >= self.max_entries && !cache.contains_key(id) {
+            let oldest = cache.iter()
+                .min_by_key(|(_, entry)| entry.inserted_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
             }
-            
-            return Ok(nodes);
         }
 
-        Ok(vec![])
+        cache.insert(id.to_string(), CacheEntry {
+            value,
+            inserted_at: std::time::Instant::now(),
+        });
+    }
+}
+
+#[async_trait]
+impl<S: NodeStorage> NodeStorage for CachedStorage<S> {
+    async fn store_node(&self, node: &FabricNode) -> StorageResult<()> {
+        let result = self.inner.store_node(node).await;
+        self.invalidate_node(&node.node_id).await;
+        result
+    }
+
+    async fn get_node(&self, node_id: &str) -> StorageResult<Option<FabricNode>> {
+        if let Some(node) = self.cached(&self.nodes, node_id).await {
+            return Ok(Some(node));
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let node = self.inner.get_node(node_id).await?;
+        if let Some(node) = &node {
+            self.insert_cached(&self.nodes, node_id, node.clone(), generation).await;
+        }
+        Ok(node)
+    }
+
+    async fn list_nodes(&self) -> StorageResult<Vec<FabricNode>> {
+        self.inner.list_nodes().await
     }
 
     async fn update_node_status(&self, node_id: &str, status: NodeStatus) -> StorageResult<()> {
-        // Update in both stores
-        if let Some(mut node) = self.get_node(node_id).await? {
+        let result = self.inner.update_node_status(node_id, status).await;
+        self.invalidate_node(node_id).await;
+        result
+    }
+
+    async fn delete_node(&self, node_id: &str) -> StorageResult<()> {
+        let result = self.inner.delete_node(node_id).await;
+        self.invalidate_node(node_id).await;
+        result
+    }
+}
+
+#[async_trait]
+impl<S: AgentStorage> AgentStorage for CachedStorage<S> {
+    async fn store_agent(&self, agent: &AIAgent) -> StorageResult<()> {
+        let result = self.inner.store_agent(agent).await;
+        self.invalidate_agent(&agent.agent_id).await;
+        result
+    }
+
+    async fn get_agent(&self, agent_id: &str) -> StorageResult<Option<AIAgent>> {
+        if let Some(agent) = self.cached(&self.agents, agent_id).await {
+            return Ok(Some(agent));
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let agent = self.inner.get_agent(agent_id).await?;
+        if let Some(agent) = &agent {
+            self.insert_cached(&self.agents, agent_id, agent.clone(), generation).await;
+        }
+        Ok(agent)
+    }
+
+    async fn list_agents(&self) -> StorageResult<Vec<AIAgent>> {
+        self.inner.list_agents().await
+    }
+
+    async fn list_agents_by_node(&self, node_id: &str) -> StorageResult<Vec<AIAgent>> {
+        self.inner.list_agents_by_node(node_id).await
+    }
+
+    async fn update_agent_status(&self, agent_id: &str, status: AgentStatus) -> StorageResult<()> {
+        let result = self.inner.update_agent_status(agent_id, status).await;
+        self.invalidate_agent(agent_id).await;
+        result
+    }
+
+    async fn delete_agent(&self, agent_id: &str) -> StorageResult<()> {
+        let result = self.inner.delete_agent(agent_id).await;
+        self.invalidate_agent(agent_id).await;
+        result
+    }
+}
+
+#[async_trait]
+impl<S: TelemetryStorage> TelemetryStorage for CachedStorage<S> {
+    async fn store_telemetry(&self, telemetry: &TelemetryRecord) -> StorageResult<()> {
+        self.inner.store_telemetry(telemetry).await
+    }
+
+    async fn store_telemetry_many(&self, records: &[TelemetryRecord]) -> StorageResult<()> {
+        self.inner.store_telemetry_many(records).await
+    }
+
+    async fn get_latest_telemetry(&self, entity_id: &str) -> StorageResult<Option<TelemetryRecord>> {
+        self.inner.get_latest_telemetry(entity_id).await
+    }
+
+    async fn get_telemetry_history(&self, entity_id: &str, hours: u32) -> StorageResult<Vec<TelemetryRecord>> {
+        self.inner.get_telemetry_history(entity_id, hours).await
+    }
+
+    async fn cleanup_old_telemetry(&self, retention: &RetentionPolicy) -> StorageResult<u64> {
+        self.inner.cleanup_old_telemetry(retention).await
+    }
+}
+
+// Keeps everything in this process: nothing touches disk and nothing survives a
+// restart. For tests and single-binary deployments too small to warrant RocksDB.
+#[derive(Default)]
+pub struct InMemoryStorage {
+    nodes: RwLock<HashMap<String, FabricNode>>,
+    agents: RwLock<HashMap<String, AIAgent>>,
+    telemetry: RwLock<Vec<TelemetryRecord>>,
+}
+
+impl InMemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl NodeStorage for InMemoryStorage {
+    async fn store_node(&self, node: &FabricNode) -> StorageResult<()> {
+        self.nodes.write().await.insert(node.node_id.clone(), node.clone());
+        Ok(())
+    }
+
+    async fn get_node(&self, node_id: &str) -> StorageResult<Option<FabricNode>> {
+        Ok(self.nodes.read().await.get(node_id).cloned())
+    }
+
+    async fn list_nodes(&self) -> StorageResult<Vec<FabricNode>> {
+        let mut nodes: Vec<FabricNode> = self.nodes.read().await.values().cloned().collect();
+        nodes.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        Ok(nodes)
+    }
+
+    async fn update_node_status(&self, node_id: &str, status: NodeStatus) -> StorageResult<()> {
+        if let Some(node) = self.nodes.write().await.get_mut(node_id) {
             node.status = status;
             node.last_seen = Utc::now();
-            self.store_node(&node).await?;
         }
         Ok(())
     }
 
     async fn delete_node(&self, node_id: &str) -> StorageResult<()> {
-        // Delete from RocksDB
-        if let Some(rocks) = &self.rocksdb {
-            let key = Self::node_key(node_id);
-            rocks.delete(key.as_bytes())?;
-        }
+        self.nodes.write().await.remove(node_id);
+        Ok(())
+    }
+}
 
-        // Delete from PostgreSQL
-        if let Some(pg) = &self.postgres {
-            sqlx::query("DELETE FROM nodes WHERE node_id = $1")
-                .bind(node_id)
-                .execute(pg)
-                .await?;
+#[async_trait]
+impl AgentStorage for InMemoryStorage {
+    async fn store_agent(&self, agent: &AIAgent) -> StorageResult<()> {
+        self.agents.write().await.insert(agent.agent_id.clone(), agent.clone());
+        Ok(())
+    }
+
+    async fn get_agent(&self, agent_id: &str) -> StorageResult<Option<AIAgent>> {
+        Ok(self.agents.read().await.get(agent_id).cloned())
+    }
+
+    async fn list_agents(&self) -> StorageResult<Vec<AIAgent>> {
+        let mut agents: Vec<AIAgent> = self.agents.read().await.values().cloned().collect();
+        agents.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+        Ok(agents)
+    }
+
+    async fn list_agents_by_node(&self, node_id: &str) -> StorageResult<Vec<AIAgent>> {
+        let mut agents = self.list_agents().await?;
+        agents.retain(|agent| agent.node_id == node_id);
+        Ok(agents)
+    }
+
+    async fn update_agent_status(&self, agent_id: &str, status: AgentStatus) -> StorageResult<()> {
+        if let Some(agent) = self.agents.write().await.get_mut(agent_id) {
+            agent.status = status;
+            agent.last_active = Utc::now();
         }
+        Ok(())
+    }
 
+    async fn delete_agent(&self, agent_id: &str) -> StorageResult<()> {
+        self.agents.write().await.remove(agent_id);
         Ok(())
     }
 }
 
-// Additional implementations for AgentStorage and TelemetryStorage would follow similar patterns
-// For brevity, showing the structure for NodeStorage implementation
+#[async_trait]
+impl TelemetryStorage for InMemoryStorage {
+    async fn store_telemetry(&self, telemetry: &TelemetryRecord) -> StorageResult<()> {
+        self.telemetry.write().await.push(telemetry.clone());
+        Ok(())
+    }
+
+    async fn store_telemetry_many(&self, records: &[TelemetryRecord]) -> StorageResult<()> {
+        self.telemetry.write().await.extend_from_slice(records);
+        Ok(())
+    }
+
+    async fn get_latest_telemetry(&self, entity_id: &str) -> StorageResult<Option<TelemetryRecord>> {
+        Ok(self.telemetry.read().await.iter()
+            .filter(|record| record.entity_id == entity_id)
+            .max_by_key(|record| record.timestamp)
+            .cloned())
+    }
+
+    async fn get_telemetry_history(&self, entity_id: &str, hours: u32) -> StorageResult<Vec<TelemetryRecord>> {
+        let since = Utc::now() - chrono::Duration::hours(i64::from(hours));
+        let mut records: Vec<TelemetryRecord> = self.telemetry.read().await.iter()
+            .filter(|record| record.entity_id == entity_id && record.timestamp >= since)
+            .cloned()
+            .collect();
+        records.sort_by_key(|record| record.timestamp);
+        Ok(records)
+    }
+
+    async fn cleanup_old_telemetry(&self, retention: &RetentionPolicy) -> StorageResult<u64> {
+        let now = Utc::now();
+        let mut telemetry = self.telemetry.write().await;
+        let before = telemetry.len();
+        telemetry.retain(|record| record.timestamp >= now - chrono::Duration::days(i64::from(retention.days_for(&record.entity_type))));
+        Ok((before - telemetry.len()) as u64)
+    }
+}
@@ -1,14 +1,18 @@
 // nexus-prime-core/src/telemetry.rs - Advanced Telemetry and Monitoring
 
 use crate::config::TelemetryConfig;
-use crate::storage::{TelemetryRecord, TelemetryStorage};
+use crate::storage::{StorageResult, TelemetryRecord, TelemetryStorage};
+use crate::watchdog::{Heartbeat, Watchdog};
+use crate::FabricManager;
 use chrono::{DateTime, Utc};
 use metrics::{counter, gauge, histogram, Counter, Gauge, Histogram};
 use metrics_exporter_prometheus::PrometheusBuilder;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use sysinfo::{Disks, Networks, Pid, ProcessesToUpdate, System};
 use tokio::sync::RwLock;
 use tracing::{info, warn, error, debug};
 use uuid::Uuid;
@@ -23,6 +27,8 @@ pub enum TelemetryError {
     Storage(#[from] crate::storage::StorageError),
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("Configuration error: {0}")]
+    Config(String),
 }
 
 // Comprehensive system metrics
@@ -30,12 +36,19 @@ pub enum TelemetryError {
 pub struct SystemMetrics {
     pub timestamp: DateTime<Utc>,
     pub cpu_usage: f32,
+    #[serde(default)]
+    pub process_cpu_usage: f32, // This process alone, where 100.0 is one full core
     pub memory_usage: f32,
     pub memory_total: u64,
     pub memory_available: u64,
     pub disk_usage: f32,
     pub disk_total: u64,
     pub disk_available: u64,
+    // Disk and network byte counts are deltas since the previous collection
+    #[serde(default)]
+    pub disk_read_bytes: u64,
+    #[serde(default)]
+    pub disk_written_bytes: u64,
     pub network_in_bytes: u64,
     pub network_out_bytes: u64,
     pub load_average: [f32; 3], // 1min, 5min, 15min
@@ -60,28 +73,385 @@ pub struct FabricMetrics {
     pub fabric_latency_ms: f32,
 }
 
-// Performance metrics for individual operations
+// Task throughput counters, updated by the FabricManager as agent tasks start and
+// finish and read into FabricMetrics on each collection
+#[derive(Debug, Clone, Default)]
+pub struct TaskCounters {
+    pending: Arc<AtomicU32>,
+    completed: Arc<AtomicU32>,
+    failed: Arc<AtomicU32>,
+}
+
+impl TaskCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn task_started(&self) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.publish_pending();
+    }
+
+    // A task reached a terminal state. `was_pending` is false for tasks first seen
+    // already finished, which were never counted as pending.
+    pub fn task_finished(&self, succeeded: bool, was_pending: bool) {
+        if was_pending {
+            let _ = self.pending.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| Some(n.saturating_sub(1)));
+        }
+        if succeeded {
+            self.completed.fetch_add(1, Ordering::SeqCst);
+            counter!("fabric_tasks_completed_total").increment(1);
+        } else {
+            self.failed.fetch_add(1, Ordering::SeqCst);
+            counter!("fabric_tasks_failed_total").increment(1);
+        }
+        self.publish_pending();
+    }
+
+    pub fn pending(&self) -> u32 {
+        self.pending.load(Ordering::SeqCst)
+    }
+
+    pub fn completed(&self) -> u32 {
+        self.completed.load(Ordering::SeqCst)
+    }
+
+    pub fn failed(&self) -> u32 {
+        self.failed.load(Ordering::SeqCst)
+    }
+
+    // Pending goes up and down, so it is a gauge; the finished totals only grow and
+    // are counters, incremented as tasks finish
+    fn publish_pending(&self) {
+        gauge!("fabric_tasks_pending").set(self.pending() as f64);
+    }
+
+    fn apply_to(&self, metrics: &mut FabricMetrics) {
+        metrics.pending_tasks = self.pending();
+        metrics.completed_tasks = self.completed();
+        metrics.failed_tasks = self.failed();
+    }
+}
+
+// Performance metrics for individual operations. Both the number of operation names
+// and the durations kept per name are bounded, so a burst of unique labels cannot
+// grow memory without limit.
 #[derive(Debug, Clone)]
 pub struct PerformanceMetrics {
     pub operation_counters: HashMap<String, u64>,
     pub operation_histograms: HashMap<String, Vec<Duration>>,
     pub error_counters: HashMap<String, u64>,
+    last_used: HashMap<String, u64>, // Recording tick each operation was last seen at
+    tick: u64,
+    max_operations: usize,
+    max_samples: usize,
+}
+
+impl PerformanceMetrics {
+    pub fn new(max_operations: usize, max_samples: usize) -> Self {
+        Self {
+            operation_counters: HashMap::new(),
+            operation_histograms: HashMap::new(),
+            error_counters: HashMap::new(),
+            last_used: HashMap::new(),
+            tick: 0,
+            max_operations: max_operations.max(1),
+            max_samples: max_samples.max(2),
+        }
+    }
+
+    pub fn record(&mut self, operation: &str, duration: Duration, success: bool) {
+        if !self.last_used.contains_key(operation) && self.last_used.len() >= self.max_operations {
+            self.evict_least_recently_used();
+        }
+        self.tick += 1;
+        self.last_used.insert(operation.to_string(), self.tick);
+
+        *self.operation_counters.entry(operation.to_string()).or_insert(0) += 1;
+        if !success {
+            *self.error_counters.entry(operation.to_string()).or_insert(0) += 1;
+        }
+        let histogram = self.operation_histograms.entry(operation.to_string()).or_default();
+        histogram.push(duration);
+        if histogram.len() > self.max_samples {
+            histogram.drain(0..self.max_samples / 2); // Keep the most recent half
+        }
+    }
+
+    // Number of distinct operation names currently tracked
+    pub fn tracked_operations(&self) -> usize {
+        self.last_used.len()
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let Some(operation) = self.last_used.iter().min_by_key(|(_, tick)| **tick).map(|(operation, _)| operation.clone()) else {
+            return;
+        };
+        debug!(operation = %operation, "Evicting least recently used operation from performance metrics");
+        self.last_used.remove(&operation);
+        self.operation_counters.remove(&operation);
+        self.operation_histograms.remove(&operation);
+        self.error_counters.remove(&operation);
+    }
+}
+
+// Reads system metrics with sysinfo. Keeps the previous readings because CPU usage is
+// measured between refreshes and disk and network counters are reported as deltas,
+// so the first collection reads idle CPU and zero traffic.
+pub struct SystemCollector {
+    state: std::sync::Mutex<CollectorState>,
+}
+
+struct CollectorState {
+    system: System,
+    disks: Disks,
+    networks: Networks,
+    pid: Option<Pid>,
+    previous_counters: Option<IoCounters>,
+}
+
+#[derive(Clone, Copy)]
+struct IoCounters {
+    disk_read: u64,
+    disk_written: u64,
+    network_in: u64,
+    network_out: u64,
+}
+
+impl IoCounters {
+    fn since(self, previous: Option<IoCounters>) -> IoCounters {
+        // Counters can go backwards when a disk or interface goes away
+        let previous = previous.unwrap_or(self);
+        IoCounters {
+            disk_read: self.disk_read.saturating_sub(previous.disk_read),
+            disk_written: self.disk_written.saturating_sub(previous.disk_written),
+            network_in: self.network_in.saturating_sub(previous.network_in),
+            network_out: self.network_out.saturating_sub(previous.network_out),
+        }
+    }
+}
+
+impl SystemCollector {
+    pub fn new() -> Self {
+        Self {
+            state: std::sync::Mutex::new(CollectorState {
+                system: System::new(),
+                disks: Disks::new_with_refreshed_list(),
+                networks: Networks::new_with_refreshed_list(),
+                pid: sysinfo::get_current_pid().ok(),
+                previous_counters: None,
+            }),
+        }
+    }
+
+    pub fn collect(&self) -> SystemMetrics {
+        let mut state = self.state.lock().unwrap();
+        let CollectorState { system, disks, networks, pid, previous_counters } = &mut *state;
+        system.refresh_cpu_usage();
+        system.refresh_memory();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        disks.refresh(true);
+        networks.refresh(true);
+
+        // Tasks are listed next to the processes they belong to
+        let processes: Vec<_> = system.processes().values().filter(|process| process.thread_kind().is_none()).collect();
+        let thread_count: usize = processes.iter().map(|process| process.tasks().map_or(1, |tasks| tasks.len().max(1))).sum();
+
+        // Disks can be listed once per mount point
+        let mut seen_disks = std::collections::HashSet::new();
+        let (mut disk_total, mut disk_available, mut disk_read, mut disk_written) = (0, 0, 0, 0);
+        for disk in disks.list().iter().filter(|disk| seen_disks.insert(disk.name().to_os_string())) {
+            disk_total += disk.total_space();
+            disk_available += disk.available_space();
+            disk_read += disk.usage().total_read_bytes;
+            disk_written += disk.usage().total_written_bytes;
+        }
+
+        let counters = IoCounters {
+            disk_read,
+            disk_written,
+            network_in: networks.values().map(|network| network.total_received()).sum(),
+            network_out: networks.values().map(|network| network.total_transmitted()).sum(),
+        };
+        let deltas = counters.since(*previous_counters);
+        *previous_counters = Some(counters);
+
+        let load_average = System::load_average();
+        SystemMetrics {
+            timestamp: Utc::now(),
+            cpu_usage: system.global_cpu_usage(),
+            process_cpu_usage: pid.and_then(|pid| system.process(pid)).map_or(0.0, |process| process.cpu_usage()),
+            memory_usage: used_percent(system.total_memory().saturating_sub(system.available_memory()), system.total_memory()),
+            memory_total: system.total_memory(),
+            memory_available: system.available_memory(),
+            disk_usage: used_percent(disk_total.saturating_sub(disk_available), disk_total),
+            disk_total,
+            disk_available,
+            disk_read_bytes: deltas.disk_read,
+            disk_written_bytes: deltas.disk_written,
+            network_in_bytes: deltas.network_in,
+            network_out_bytes: deltas.network_out,
+            load_average: [load_average.one as f32, load_average.five as f32, load_average.fifteen as f32],
+            process_count: processes.len() as u32,
+            thread_count: thread_count as u32,
+            file_descriptor_count: open_file_descriptors(),
+        }
+    }
+}
+
+impl Default for SystemCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn used_percent(used: u64, total: u64) -> f32 {
+    if total == 0 {
+        0.0
+    } else {
+        (used as f64 * 100.0 / total as f64) as f32
+    }
+}
+
+// Descriptors held by this process. Only Linux exposes them, elsewhere this reads 0.
+fn open_file_descriptors() -> u32 {
+    std::fs::read_dir("/proc/self/fd").map_or(0, |entries| entries.count() as u32)
+}
+
+// Fills FabricMetrics from the fabric's nodes and agents, the task counters and the
+// recorded operation durations, and publishes the node and agent gauges
+#[derive(Clone)]
+struct FabricCollector {
+    fabric_manager: Option<FabricManager>,
+    task_counters: Option<TaskCounters>,
+    performance_metrics: Arc<RwLock<PerformanceMetrics>>,
+    node_count_gauge: Gauge,
+    agent_count_gauge: Gauge,
+    last_operation_count: Arc<std::sync::Mutex<Option<(Instant, u64)>>>, // Operations recorded as of the previous collection
+}
+
+impl FabricCollector {
+    async fn collect(&self, fabric_metrics: &RwLock<FabricMetrics>) -> FabricMetrics {
+        let mut metrics = fabric_metrics.read().await.clone();
+        metrics.timestamp = Utc::now();
+
+        if let Some(fabric_manager) = &self.fabric_manager {
+            let state = fabric_manager.state.read().await;
+            metrics.total_nodes = state.compute_nodes.len() as u32;
+            metrics.online_nodes = state.compute_nodes.values().filter(|node| node.status == "Online").count() as u32;
+            metrics.total_agents = state.ai_agents.len() as u32;
+            metrics.running_agents = state.ai_agents.values().filter(|agent| agent.status == "Running").count() as u32;
+        }
+        if let Some(task_counters) = &self.task_counters {
+            task_counters.apply_to(&mut metrics);
+        }
+
+        {
+            let performance_metrics = self.performance_metrics.read().await;
+            let (samples, total) = performance_metrics.operation_histograms.values().flatten()
+                .fold((0u32, Duration::ZERO), |(samples, total), duration| (samples + 1, total + *duration));
+            if samples > 0 {
+                metrics.average_task_duration_ms = (total.as_secs_f64() * 1000.0 / samples as f64) as f32;
+            }
+
+            // Evicted operations take their counts with them, so the total can shrink
+            let operations: u64 = performance_metrics.operation_counters.values().sum();
+            let now = Instant::now();
+            let mut last_operation_count = self.last_operation_count.lock().unwrap();
+            if let Some((at, count)) = *last_operation_count {
+                let elapsed = now.duration_since(at).as_secs_f64();
+                if elapsed > 0.0 {
+                    metrics.fabric_throughput_ops_per_sec = (operations.saturating_sub(count) as f64 / elapsed) as f32;
+                }
+            }
+            *last_operation_count = Some((now, operations));
+        }
+
+        self.node_count_gauge.set(metrics.total_nodes as f64);
+        self.agent_count_gauge.set(metrics.total_agents as f64);
+        gauge!("fabric_nodes_online").set(metrics.online_nodes as f64);
+        gauge!("fabric_agents_running").set(metrics.running_agents as f64);
+
+        *fabric_metrics.write().await = metrics.clone();
+        metrics
+    }
+}
+
+// How many batches of records are kept for retrying while writes fail; the oldest
+// records beyond that are dropped
+const PENDING_BATCHES_KEPT: usize = 10;
+
+// Telemetry records waiting to be written. The next system collection writes them all
+// in one batch, or the record that fills the batch does so right away.
+#[derive(Clone)]
+struct PendingTelemetry {
+    storage: Arc<dyn TelemetryStorage>,
+    records: Arc<tokio::sync::Mutex<Vec<TelemetryRecord>>>,
+    batch_size: usize,
+    retrying: Arc<AtomicBool>, // Set while writes fail; retries then wait for the next collection
+}
+
+impl PendingTelemetry {
+    async fn push(&self, record: TelemetryRecord) {
+        let full = {
+            let mut records = self.records.lock().await;
+            records.push(record);
+            records.len() >= self.batch_size && !self.retrying.load(Ordering::SeqCst)
+        };
+        if full {
+            if let Err(e) = self.flush().await {
+                warn!("Failed to store telemetry batch, keeping it for a retry: {}", e);
+            }
+        }
+    }
+
+    // Write every waiting record in one batch. A batch that fails is put back in
+    // front of the records that arrived meanwhile.
+    async fn flush(&self) -> StorageResult<()> {
+        let records = std::mem::take(&mut *self.records.lock().await);
+        if records.is_empty() {
+            return Ok(());
+        }
+        match self.storage.store_telemetry_many(&records).await {
+            Ok(()) => {
+                self.retrying.store(false, Ordering::SeqCst);
+                Ok(())
+            }
+            Err(e) => {
+                self.retrying.store(true, Ordering::SeqCst);
+                let mut pending = self.records.lock().await;
+                let arrived = std::mem::replace(&mut *pending, records);
+                pending.extend(arrived);
+                let kept = self.batch_size.saturating_mul(PENDING_BATCHES_KEPT);
+                if pending.len() > kept {
+                    let dropped = pending.len() - kept;
+                    pending.drain(..dropped);
+                    error!(dropped, "Dropped the oldest telemetry records after repeated write failures");
+                }
+                Err(e)
+            }
+        }
+    }
 }
 
 // Telemetry manager for collecting, processing, and exporting metrics
 pub struct TelemetryManager {
     config: TelemetryConfig,
     storage: Arc<dyn TelemetryStorage>,
+    pending_telemetry: PendingTelemetry,
     system_metrics: Arc<RwLock<SystemMetrics>>,
+    system_collector: Arc<SystemCollector>,
     fabric_metrics: Arc<RwLock<FabricMetrics>>,
     performance_metrics: Arc<RwLock<PerformanceMetrics>>,
     
+    fabric_collector: FabricCollector,
+
     // Prometheus metrics
-    node_count_gauge: Gauge,
-    agent_count_gauge: Gauge,
     task_duration_histogram: Histogram,
     operation_counter: Counter,
     error_counter: Counter,
+
+    watchdog: Option<Watchdog>,
 }
 
 impl TelemetryManager {
@@ -89,6 +459,10 @@ impl TelemetryManager {
         config: TelemetryConfig,
         storage: Arc<dyn TelemetryStorage>,
     ) -> TelemetryResult<Self> {
+        if config.system_metrics_interval_seconds == 0 || config.fabric_metrics_interval_seconds == 0 {
+            return Err(TelemetryError::Config("Telemetry collection intervals must be positive".to_string()));
+        }
+
         // Initialize Prometheus exporter if enabled
         if config.enable_prometheus {
             PrometheusBuilder::new()
@@ -107,98 +481,118 @@ impl TelemetryManager {
         }
 
         // Initialize Prometheus metrics
-        let node_count_gauge = gauge!("fabric_nodes_total");
-        let agent_count_gauge = gauge!("fabric_agents_total");
         let task_duration_histogram = histogram!("fabric_task_duration_seconds");
         let operation_counter = counter!("fabric_operations_total");
         let error_counter = counter!("fabric_errors_total");
+        let performance_metrics = Arc::new(RwLock::new(PerformanceMetrics::new(
+            config.max_tracked_operations as usize,
+            config.max_operation_samples as usize,
+        )));
+        let fabric_collector = FabricCollector {
+            fabric_manager: None,
+            task_counters: None,
+            performance_metrics: Arc::clone(&performance_metrics),
+            node_count_gauge: gauge!("fabric_nodes_total"),
+            agent_count_gauge: gauge!("fabric_agents_total"),
+            last_operation_count: Arc::new(std::sync::Mutex::new(None)),
+        };
+
+        let pending_telemetry = PendingTelemetry {
+            storage: Arc::clone(&storage),
+            records: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            batch_size: config.telemetry_batch_size.max(1) as usize,
+            retrying: Arc::new(AtomicBool::new(false)),
+        };
 
         let manager = Self {
             config,
             storage,
+            pending_telemetry,
             system_metrics: Arc::new(RwLock::new(Self::default_system_metrics())),
+            system_collector: Arc::new(SystemCollector::new()),
             fabric_metrics: Arc::new(RwLock::new(Self::default_fabric_metrics())),
-            performance_metrics: Arc::new(RwLock::new(PerformanceMetrics {
-                operation_counters: HashMap::new(),
-                operation_histograms: HashMap::new(),
-                error_counters: HashMap::new(),
-            })),
-            node_count_gauge,
-            agent_count_gauge,
+            performance_metrics,
+            fabric_collector,
             task_duration_histogram,
             operation_counter,
             error_counter,
+            watchdog: None,
         };
 
         Ok(manager)
     }
 
+    // Report collection task heartbeats to a watchdog
+    pub fn with_watchdog(mut self, watchdog: Watchdog) -> Self {
+        self.watchdog = Some(watchdog);
+        self
+    }
+
+    // Report task throughput from the FabricManager's counters
+    pub fn with_task_counters(mut self, task_counters: TaskCounters) -> Self {
+        self.fabric_collector.task_counters = Some(task_counters);
+        self
+    }
+
+    // Count the FabricManager's nodes and agents on each fabric collection
+    pub fn with_fabric_manager(mut self, fabric_manager: FabricManager) -> Self {
+        self.fabric_collector.fabric_manager = Some(fabric_manager);
+        self
+    }
+
+    fn heartbeat(&self, task_name: &str, interval: Duration) -> Option<Heartbeat> {
+        // Allow one missed tick before the watchdog flags the task
+        self.watchdog.as_ref().map(|watchdog| watchdog.register(task_name, interval * 2))
+    }
+
     // Start telemetry collection background tasks
     pub fn start_collection_tasks(&self) -> Vec<tokio::task::JoinHandle<()>> {
         let mut tasks = Vec::new();
 
         // System metrics collection task
         let system_metrics = Arc::clone(&self.system_metrics);
-        let storage = Arc::clone(&self.storage);
+        let system_collector = Arc::clone(&self.system_collector);
+        let pending_telemetry = self.pending_telemetry.clone();
+        let instance_id = self.config.instance_id.clone();
+        let system_interval = Duration::from_secs(self.config.system_metrics_interval_seconds);
+        let heartbeat = self.heartbeat("telemetry.system", system_interval);
         tasks.push(tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(30));
+            let mut interval = tokio::time::interval(system_interval);
             
             loop {
                 interval.tick().await;
+                if let Some(heartbeat) = &heartbeat {
+                    heartbeat.beat();
+                }
                 
-                match Self::collect_system_metrics().await {
-                    Ok(metrics) => {
-                        // Update in-memory metrics
-                        {
-                            let mut system_metrics = system_metrics.write().await;
-                            *system_metrics = metrics.clone();
-                        }
-
-                        // Store to persistent storage
-                        let telemetry_record = TelemetryRecord {
-                            id: Uuid::new_v4(),
-                            entity_id: "system".to_string(),
-                            entity_type: "system".to_string(),
-                            timestamp: metrics.timestamp,
-                            cpu_utilization: metrics.cpu_usage,
-                            memory_utilization: metrics.memory_usage,
-                            network_in_kbps: metrics.network_in_bytes as f32 / 1024.0,
-                            network_out_kbps: metrics.network_out_bytes as f32 / 1024.0,
-                            custom_metrics: HashMap::new(), // Could include more detailed metrics
-                        };
-
-                        if let Err(e) = storage.store_telemetry(&telemetry_record).await {
-                            error!("Failed to store system telemetry: {}", e);
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to collect system metrics: {}", e);
-                    }
+                if let Err(e) = Self::collect_system_cycle(&instance_id, &system_collector, &system_metrics, &pending_telemetry).await {
+                    error!(instance_id = %instance_id, "Failed to collect system metrics: {}", e);
                 }
             }
         }));
 
         // Fabric metrics collection task
         let fabric_metrics = Arc::clone(&self.fabric_metrics);
+        let fabric_interval = Duration::from_secs(self.config.fabric_metrics_interval_seconds);
+        let heartbeat = self.heartbeat("telemetry.fabric", fabric_interval);
+        let fabric_collector = self.fabric_collector.clone();
         tasks.push(tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            let mut interval = tokio::time::interval(fabric_interval);
             
             loop {
                 interval.tick().await;
+                if let Some(heartbeat) = &heartbeat {
+                    heartbeat.beat();
+                }
                 
-                // Collect fabric-specific metrics
-                // This would integrate with the FabricManager to get current state
-                info!("Collecting fabric metrics...");
-                
-                // Update Prometheus metrics
-                // These values would come from the actual fabric state
-                // gauge!("fabric_nodes_total").set(online_nodes as f64);
-                // gauge!("fabric_agents_total").set(running_agents as f64);
+                let metrics = fabric_collector.collect(&fabric_metrics).await;
+                debug!(nodes = metrics.total_nodes, agents = metrics.total_agents, "Collected fabric metrics");
             }
         }));
 
         // Metrics cleanup task
         let storage_clone = Arc::clone(&self.storage);
+        let retention = self.config.retention.clone();
         tasks.push(tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(3600 * 24)); // Daily
             
@@ -206,7 +600,7 @@ impl TelemetryManager {
                 interval.tick().await;
                 
                 info!("Cleaning up old telemetry data...");
-                match storage_clone.cleanup_old_telemetry(30).await { // Keep 30 days
+                match storage_clone.cleanup_old_telemetry(&retention).await {
                     Ok(cleaned) => {
                         info!("Cleaned up {} old telemetry records", cleaned);
                     }
@@ -220,6 +614,58 @@ impl TelemetryManager {
         tasks
     }
 
+    // Run a collection cycle immediately instead of waiting for the next interval
+    pub async fn collect_now(&self) -> TelemetryResult<(SystemMetrics, FabricMetrics)> {
+        let system_metrics = Self::collect_system_cycle(&self.config.instance_id, &self.system_collector, &self.system_metrics, &self.pending_telemetry).await?;
+
+        let fabric_metrics = self.fabric_collector.collect(&self.fabric_metrics).await;
+
+        Ok((system_metrics, fabric_metrics))
+    }
+
+    // Collect system metrics, update the in-memory snapshot and persist a record along
+    // with the others recorded since the previous cycle
+    async fn collect_system_cycle(
+        instance_id: &str,
+        system_collector: &Arc<SystemCollector>,
+        system_metrics: &RwLock<SystemMetrics>,
+        pending_telemetry: &PendingTelemetry,
+    ) -> TelemetryResult<SystemMetrics> {
+        // Refreshing the process list reads /proc, so keep it off the runtime threads
+        let system_collector = Arc::clone(system_collector);
+        let metrics = tokio::task::spawn_blocking(move || system_collector.collect())
+            .await
+            .map_err(|e| TelemetryError::Metrics(format!("System metrics collection failed: {}", e)))?;
+
+        // Update in-memory metrics. The byte counts cover the time since the snapshot replaced here.
+        let interval_secs = {
+            let mut system_metrics = system_metrics.write().await;
+            let interval = metrics.timestamp - system_metrics.timestamp;
+            *system_metrics = metrics.clone();
+            interval.num_milliseconds() as f64 / 1000.0
+        };
+
+        // Store to persistent storage
+        let telemetry_record = TelemetryRecord {
+            id: Uuid::new_v4(),
+            entity_id: instance_id.to_string(),
+            entity_type: "system".to_string(),
+            timestamp: metrics.timestamp,
+            cpu_utilization: Some(metrics.cpu_usage),
+            memory_utilization: Some(metrics.memory_usage),
+            network_in_kbps: Some(kbps(metrics.network_in_bytes, interval_secs)),
+            network_out_kbps: Some(kbps(metrics.network_out_bytes, interval_secs)),
+            custom_metrics: HashMap::new(), // Could include more detailed metrics
+        };
+
+        pending_telemetry.push(telemetry_record).await;
+        if let Err(e) = pending_telemetry.flush().await {
+            warn!("Failed to store telemetry batch, keeping it for a retry: {}", e);
+        }
+
+        Ok(metrics)
+    }
+
     // Record operation metrics
     pub async fn record_operation(&self, operation: &str, duration: Duration, success: bool) {
         // Update Prometheus metrics
@@ -231,44 +677,30 @@ impl TelemetryManager {
         }
 
         // Update internal performance metrics
-        let mut perf_metrics = self.performance_metrics.write().await;
-        
-        *perf_metrics.operation_counters.entry(operation.to_string()).or_insert(0) += 1;
-        
-        perf_metrics.operation_histograms
-            .entry(operation.to_string())
-            .or_insert_with(Vec::new)
-            .push(duration);
-
-        if !success {
-            *perf_metrics.error_counters.entry(operation.to_string()).or_insert(0) += 1;
-        }
-
-        // Keep histogram size manageable
-        if let Some(histogram) = perf_metrics.operation_histograms.get_mut(operation) {
-            if histogram.len() > 1000 {
-                histogram.drain(0..500); // Keep most recent 500 entries
-            }
-        }
+        self.performance_metrics.write().await.record(operation, duration, success);
     }
 
-    // Record custom metric
+    // Record custom metric. It is written with the next system collection or batch.
     pub async fn record_custom_metric(&self, entity_id: &str, metric_name: &str, value: f32) {
         let telemetry_record = TelemetryRecord {
             id: Uuid::new_v4(),
             entity_id: entity_id.to_string(),
             entity_type: "custom".to_string(),
             timestamp: Utc::now(),
-            cpu_utilization: 0.0,
-            memory_utilization: 0.0,
-            network_in_kbps: 0.0,
-            network_out_kbps: 0.0,
+            cpu_utilization: None,
+            memory_utilization: None,
+            network_in_kbps: None,
+            network_out_kbps: None,
             custom_metrics: [(metric_name.to_string(), value)].into_iter().collect(),
         };
 
-        if let Err(e) = self.storage.store_telemetry(&telemetry_record).await {
-            error!("Failed to store custom metric: {}", e);
-        }
+        self.pending_telemetry.push(telemetry_record).await;
+    }
+
+    // Write records still waiting for the next collection, e.g. before shutting down.
+    // On failure they stay queued for the next attempt.
+    pub async fn flush(&self) -> TelemetryResult<()> {
+        Ok(self.pending_telemetry.flush().await?)
     }
 
     // Get current system metrics
@@ -281,6 +713,12 @@ impl TelemetryManager {
         self.fabric_metrics.read().await.clone()
     }
 
+    // Average of each metric an entity reported over the last `hours`
+    pub async fn get_telemetry_averages(&self, entity_id: &str, hours: u32) -> TelemetryResult<TelemetryAverages> {
+        let records = self.storage.get_telemetry_history(entity_id, hours).await?;
+        Ok(TelemetryAverages::from_records(&records))
+    }
+
     // Get performance summary
     pub async fn get_performance_summary(&self) -> HashMap<String, OperationSummary> {
         let perf_metrics = self.performance_metrics.read().await;
@@ -316,38 +754,19 @@ impl TelemetryManager {
         summary
     }
 
-    // Collect system metrics (would integrate with system monitoring libraries)
-    async fn collect_system_metrics() -> TelemetryResult<SystemMetrics> {
-        // This is a simplified implementation
-        // In production, you'd use libraries like `sysinfo` or platform-specific APIs
-        Ok(SystemMetrics {
-            timestamp: Utc::now(),
-            cpu_usage: 45.2,
-            memory_usage: 62.1,
-            memory_total: 16 * 1024 * 1024 * 1024, // 16GB
-            memory_available: 6 * 1024 * 1024 * 1024, // 6GB
-            disk_usage: 78.5,
-            disk_total: 1024 * 1024 * 1024 * 1024, // 1TB
-            disk_available: 220 * 1024 * 1024 * 1024, // 220GB
-            network_in_bytes: 1024 * 1024 * 50, // 50MB
-            network_out_bytes: 1024 * 1024 * 30, // 30MB
-            load_average: [1.2, 1.5, 1.8],
-            process_count: 245,
-            thread_count: 1200,
-            file_descriptor_count: 8192,
-        })
-    }
-
     fn default_system_metrics() -> SystemMetrics {
         SystemMetrics {
             timestamp: Utc::now(),
             cpu_usage: 0.0,
+            process_cpu_usage: 0.0,
             memory_usage: 0.0,
             memory_total: 0,
             memory_available: 0,
             disk_usage: 0.0,
             disk_total: 0,
             disk_available: 0,
+            disk_read_bytes: 0,
+            disk_written_bytes: 0,
             network_in_bytes: 0,
             network_out_bytes: 0,
             load_average: [0.0, 0.0, 0.0],
@@ -374,6 +793,81 @@ impl TelemetryManager {
     }
 }
 
+// Kilobits per second for `bytes` transferred over `interval_secs`
+pub fn kbps(bytes: u64, interval_secs: f64) -> f32 {
+    if interval_secs <= 0.0 {
+        return 0.0;
+    }
+    (bytes as f64 * 8.0 / 1000.0 / interval_secs) as f32
+}
+
+impl From<&SystemMetrics> for crate::fabric_proto::fabric::SystemMetricsSnapshot {
+    fn from(metrics: &SystemMetrics) -> Self {
+        Self {
+            timestamp: metrics.timestamp.to_rfc3339(),
+            cpu_usage: metrics.cpu_usage,
+            memory_usage: metrics.memory_usage,
+            memory_total: metrics.memory_total,
+            memory_available: metrics.memory_available,
+            disk_usage: metrics.disk_usage,
+            disk_total: metrics.disk_total,
+            disk_available: metrics.disk_available,
+            network_in_bytes: metrics.network_in_bytes,
+            network_out_bytes: metrics.network_out_bytes,
+            load_average: metrics.load_average.to_vec(),
+            process_count: metrics.process_count,
+            thread_count: metrics.thread_count,
+            file_descriptor_count: metrics.file_descriptor_count,
+        }
+    }
+}
+
+impl From<&FabricMetrics> for crate::fabric_proto::fabric::FabricMetricsSnapshot {
+    fn from(metrics: &FabricMetrics) -> Self {
+        Self {
+            timestamp: metrics.timestamp.to_rfc3339(),
+            total_nodes: metrics.total_nodes,
+            online_nodes: metrics.online_nodes,
+            total_agents: metrics.total_agents,
+            running_agents: metrics.running_agents,
+            pending_tasks: metrics.pending_tasks,
+            completed_tasks: metrics.completed_tasks,
+            failed_tasks: metrics.failed_tasks,
+            average_task_duration_ms: metrics.average_task_duration_ms,
+            fabric_throughput_ops_per_sec: metrics.fabric_throughput_ops_per_sec,
+            fabric_latency_ms: metrics.fabric_latency_ms,
+        }
+    }
+}
+
+// Per-metric means over a set of telemetry records. Records that did not report a
+// metric are left out of its mean instead of counting as zero; a metric no record
+// reported stays None.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryAverages {
+    pub cpu_utilization: Option<f32>,
+    pub memory_utilization: Option<f32>,
+    pub network_in_kbps: Option<f32>,
+    pub network_out_kbps: Option<f32>,
+}
+
+impl TelemetryAverages {
+    pub fn from_records(records: &[TelemetryRecord]) -> Self {
+        let mean = |metric: fn(&TelemetryRecord) -> Option<f32>| {
+            let (sum, count) = records.iter()
+                .filter_map(metric)
+                .fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+            (count > 0).then(|| sum / count as f32)
+        };
+        Self {
+            cpu_utilization: mean(|record| record.cpu_utilization),
+            memory_utilization: mean(|record| record.memory_utilization),
+            network_in_kbps: mean(|record| record.network_in_kbps),
+            network_out_kbps: mean(|record| record.network_out_kbps),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperationSummary {
     pub total_count: u64,
@@ -0,0 +1,79 @@
+// nexus-prime-core/src/validation.rs - Length limits on free-form string fields of incoming requests
+
+use crate::fabric_proto::fabric::{AgentRegistrationRequest, AgentStatusUpdate, FabricCommand, SetMaintenanceModeRequest, UpdateNodeMetadataRequest};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{field} is {length} bytes, over the {max_length} byte limit")]
+pub struct FieldTooLong {
+    pub field: &'static str,
+    pub length: usize,
+    pub max_length: usize,
+}
+
+// Free-form string fields of a request that end up in fabric state or events
+pub trait StringFields {
+    fn string_fields(&self) -> Vec<(&'static str, &str)>;
+}
+
+pub fn check_field_lengths(message: &impl StringFields, max_length: usize) -> Result<(), FieldTooLong> {
+    for (field, value) in message.string_fields() {
+        if value.len() > max_length {
+            return Err(FieldTooLong { field, length: value.len(), max_length });
+        }
+    }
+    Ok(())
+}
+
+fn map_fields<'a>(keys: &'static str, values: &'static str, map: &'a HashMap<String, String>) -> impl Iterator<Item = (&'static str, &'a str)> {
+    map.iter().flat_map(move |(key, value)| [(keys, key.as_str()), (values, value.as_str())])
+}
+
+impl StringFields for AgentRegistrationRequest {
+    fn string_fields(&self) -> Vec<(&'static str, &str)> {
+        let mut fields = vec![
+            ("ip_address", self.ip_address.as_str()),
+            ("capabilities", self.capabilities.as_str()),
+            ("proxy_listen_address", self.proxy_listen_address.as_str()),
+        ];
+        fields.extend(self.features.iter().map(|feature| ("features", feature.as_str())));
+        fields
+    }
+}
+
+impl StringFields for AgentStatusUpdate {
+    fn string_fields(&self) -> Vec<(&'static str, &str)> {
+        let mut fields = vec![("node_id", self.node_id.as_str()), ("status_value", self.status_value.as_str())];
+        if let Some(current_task) = &self.current_task {
+            fields.push(("current_task", current_task));
+        }
+        fields
+    }
+}
+
+impl StringFields for FabricCommand {
+    fn string_fields(&self) -> Vec<(&'static str, &str)> {
+        let mut fields = vec![
+            ("command_id", self.command_id.as_str()),
+            ("command_type", self.command_type.as_str()),
+            ("target_id", self.target_id.as_str()),
+        ];
+        fields.extend(map_fields("parameter name", "parameter value", &self.parameters));
+        fields
+    }
+}
+
+impl StringFields for UpdateNodeMetadataRequest {
+    fn string_fields(&self) -> Vec<(&'static str, &str)> {
+        let mut fields = vec![("node_id", self.node_id.as_str())];
+        fields.extend(map_fields("label name", "label value", &self.labels));
+        fields.extend(map_fields("metadata name", "metadata value", &self.metadata));
+        fields
+    }
+}
+
+impl StringFields for SetMaintenanceModeRequest {
+    fn string_fields(&self) -> Vec<(&'static str, &str)> {
+        vec![("reason", self.reason.as_str())]
+    }
+}
@@ -0,0 +1,155 @@
+// nexus-prime-core/src/watchdog.rs - Detects background tasks that died or stopped making progress
+
+use metrics::{counter, gauge};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{error, info};
+
+type RestartFn = Arc<dyn Fn(Heartbeat) -> JoinHandle<()> + Send + Sync>;
+
+struct WatchedTask {
+    expected_interval: Duration,
+    last_heartbeat: Arc<Mutex<Instant>>,
+    healthy: bool,
+    handle: Option<JoinHandle<()>>,
+    restart: Option<RestartFn>,
+}
+
+// Handle a background task uses to report that it is still making progress
+#[derive(Clone)]
+pub struct Heartbeat {
+    task_name: String,
+    last_heartbeat: Arc<Mutex<Instant>>,
+}
+
+impl Heartbeat {
+    pub fn beat(&self) {
+        *self.last_heartbeat.lock().unwrap() = Instant::now();
+    }
+
+    pub fn task_name(&self) -> &str {
+        &self.task_name
+    }
+}
+
+// Tracks heartbeats from long-running tasks (pruner, command processor, telemetry
+// collectors) and flags any task that exits or misses its expected interval.
+#[derive(Clone, Default)]
+pub struct Watchdog {
+    tasks: Arc<Mutex<HashMap<String, WatchedTask>>>,
+}
+
+impl Watchdog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Watch a task the caller spawns itself. The task must call `beat` at least once
+    // per `expected_interval`.
+    pub fn register(&self, task_name: &str, expected_interval: Duration) -> Heartbeat {
+        self.insert(task_name, expected_interval, None)
+    }
+
+    // Spawn and watch a task. If it exits or stops heartbeating, it is aborted and
+    // spawned again with a fresh heartbeat.
+    pub fn spawn_restartable<F>(&self, task_name: &str, expected_interval: Duration, spawn: F)
+    where
+        F: Fn(Heartbeat) -> JoinHandle<()> + Send + Sync + 'static,
+    {
+        let spawn: RestartFn = Arc::new(spawn);
+        let heartbeat = self.insert(task_name, expected_interval, Some(Arc::clone(&spawn)));
+        let handle = spawn(heartbeat);
+        if let Some(task) = self.tasks.lock().unwrap().get_mut(task_name) {
+            task.handle = Some(handle);
+        }
+    }
+
+    fn insert(&self, task_name: &str, expected_interval: Duration, restart: Option<RestartFn>) -> Heartbeat {
+        let last_heartbeat = Arc::new(Mutex::new(Instant::now()));
+        self.tasks.lock().unwrap().insert(task_name.to_string(), WatchedTask {
+            expected_interval,
+            last_heartbeat: Arc::clone(&last_heartbeat),
+            healthy: true,
+            handle: None,
+            restart,
+        });
+        gauge!("background_task_healthy", "task" => task_name.to_string()).set(1.0);
+        Heartbeat { task_name: task_name.to_string(), last_heartbeat }
+    }
+
+    // Re-evaluate every watched task and return the ones that are currently unhealthy
+    pub fn check(&self) -> Vec<String> {
+        let now = Instant::now();
+        let mut tasks = self.tasks.lock().unwrap();
+        let mut unhealthy = Vec::new();
+
+        for (name, task) in tasks.iter_mut() {
+            let silent_for = now.duration_since(*task.last_heartbeat.lock().unwrap());
+            let exited = task.handle.as_ref().is_some_and(|handle| handle.is_finished());
+            let stalled = silent_for > task.expected_interval;
+
+            if !exited && !stalled {
+                if !task.healthy {
+                    info!("[Watchdog] Task {} is heartbeating again", name);
+                    gauge!("background_task_healthy", "task" => name.clone()).set(1.0);
+                }
+                task.healthy = true;
+                continue;
+            }
+
+            if task.healthy {
+                error!(
+                    "[Watchdog] Task {} is unhealthy (exited: {}, last heartbeat {:?} ago, expected every {:?})",
+                    name, exited, silent_for, task.expected_interval
+                );
+                counter!("background_task_stalls_total", "task" => name.clone()).increment(1);
+                gauge!("background_task_healthy", "task" => name.clone()).set(0.0);
+            }
+            task.healthy = false;
+            unhealthy.push(name.clone());
+
+            if let Some(restart) = task.restart.clone() {
+                info!("[Watchdog] Restarting task {}", name);
+                if let Some(handle) = task.handle.take() {
+                    handle.abort();
+                }
+                *task.last_heartbeat.lock().unwrap() = now;
+                task.handle = Some(restart(Heartbeat {
+                    task_name: name.clone(),
+                    last_heartbeat: Arc::clone(&task.last_heartbeat),
+                }));
+            }
+        }
+
+        unhealthy
+    }
+
+    pub fn is_healthy(&self, task_name: &str) -> bool {
+        self.tasks.lock().unwrap().get(task_name).is_some_and(|task| task.healthy)
+    }
+
+    // Tasks the last check found unhealthy, sorted by name
+    pub fn unhealthy_tasks(&self) -> Vec<String> {
+        let mut unhealthy: Vec<String> = self.tasks.lock().unwrap().iter()
+            .filter(|(_, task)| !task.healthy)
+            .map(|(name, _)| name.clone())
+            .collect();
+        unhealthy.sort();
+        unhealthy
+    }
+
+    // Periodically check all watched tasks
+    pub fn start(&self, check_interval: Duration) -> JoinHandle<()> {
+        let watchdog = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(check_interval);
+            loop {
+                interval.tick().await;
+                watchdog.check();
+            }
+        })
+    }
+}
@@ -0,0 +1,128 @@
+// nexus-prime-core/src/webhook.rs - Push notifications of selected fabric events to HTTP endpoints
+
+use crate::config::{EventSeverity, WebhookConfig};
+use crate::fabric_proto::fabric::FabricEvent;
+use crate::EventSink;
+use metrics::counter;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+const QUEUE_CAPACITY: usize = 256; // Events waiting per webhook before new ones are dropped
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+// Severity from the event's "severity" metadata; events without one are info
+pub fn event_severity(event: &FabricEvent) -> EventSeverity {
+    match event.metadata.get("severity").map(|severity| severity.to_ascii_uppercase()).as_deref() {
+        Some("CRITICAL") => EventSeverity::Critical,
+        Some("WARNING") => EventSeverity::Warning,
+        _ => EventSeverity::Info,
+    }
+}
+
+struct Webhook {
+    config: WebhookConfig,
+    queue: mpsc::Sender<FabricEvent>,
+}
+
+impl Webhook {
+    fn accepts(&self, event: &FabricEvent) -> bool {
+        (self.config.event_types.is_empty() || self.config.event_types.contains(&event.event_type))
+            && self.config.min_severity.is_none_or(|min| event_severity(event) >= min)
+    }
+}
+
+// Event sink POSTing matching events to the configured webhooks. Each webhook has its
+// own queue and delivery task, so a slow or failing endpoint only holds up its own
+// notifications; while its queue is full, further events for it are dropped.
+pub struct WebhookNotifier {
+    webhooks: Vec<Webhook>,
+}
+
+impl WebhookNotifier {
+    // Start one delivery task per webhook. Must be called within a tokio runtime.
+    // Fails when the HTTP client cannot be built, e.g. without usable TLS roots.
+    pub fn spawn(configs: Vec<WebhookConfig>) -> Result<Self, reqwest::Error> {
+        let client = reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        let webhooks = configs.into_iter()
+            .map(|config| {
+                let (queue, events) = mpsc::channel(QUEUE_CAPACITY);
+                tokio::spawn(deliver(client.clone(), config.clone(), events));
+                Webhook { config, queue }
+            })
+            .collect();
+        Ok(Self { webhooks })
+    }
+}
+
+impl EventSink for WebhookNotifier {
+    fn publish(&self, event: &FabricEvent) {
+        for webhook in self.webhooks.iter().filter(|webhook| webhook.accepts(event)) {
+            if webhook.queue.try_send(event.clone()).is_err() {
+                counter!("webhook_notifications_dropped_total", "reason" => "queue_full").increment(1);
+                warn!("[Webhook] Queue for {} is full, dropping {} event", webhook.config.url, event.event_type);
+            }
+        }
+    }
+}
+
+// Deliver queued events in order, dropping those past `max_per_minute`
+async fn deliver(client: reqwest::Client, config: WebhookConfig, mut events: mpsc::Receiver<FabricEvent>) {
+    let mut sent: VecDeque<Instant> = VecDeque::new();
+    while let Some(event) = events.recv().await {
+        let now = Instant::now();
+        while sent.front().is_some_and(|at| now.duration_since(*at) >= RATE_WINDOW) {
+            sent.pop_front();
+        }
+        if sent.len() >= config.max_per_minute as usize {
+            counter!("webhook_notifications_dropped_total", "reason" => "rate_limited").increment(1);
+            warn!("[Webhook] Rate limit of {} per minute reached for {}, dropping {} event", config.max_per_minute, config.url, event.event_type);
+            continue;
+        }
+        sent.push_back(now);
+        if let Err(e) = post_with_retry(&client, &config, &payload(&event)).await {
+            counter!("webhook_notifications_failed_total").increment(1);
+            warn!("[Webhook] Giving up on {} event for {}: {}", event.event_type, config.url, e);
+        }
+    }
+}
+
+fn payload(event: &FabricEvent) -> serde_json::Value {
+    serde_json::json!({
+        "event_id": event.event_id,
+        "event_type": event.event_type,
+        "severity": event_severity(event),
+        "message": event.message,
+        "timestamp": event.timestamp,
+        "sequence": event.sequence,
+        "metadata": event.metadata,
+    })
+}
+
+// POST the payload, retrying connection errors, timeouts and 429 or 5xx responses
+// with a doubling delay, up to `max_attempts` tries
+async fn post_with_retry(client: &reqwest::Client, config: &WebhookConfig, payload: &serde_json::Value) -> Result<(), String> {
+    let mut delay = RETRY_BASE_DELAY;
+    let mut attempt = 1;
+    loop {
+        let error = match client.post(&config.url).json(payload).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) if response.status().is_server_error() || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                format!("endpoint returned {}", response.status())
+            }
+            Ok(response) => return Err(format!("endpoint returned {}", response.status())),
+            Err(e) => e.to_string(),
+        };
+        if attempt >= config.max_attempts {
+            return Err(error);
+        }
+        debug!("[Webhook] Attempt {} to notify {} failed: {}; retrying in {:?}", attempt, config.url, error, delay);
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    }
+}
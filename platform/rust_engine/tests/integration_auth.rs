@@ -0,0 +1,89 @@
+// Integration test for authorization in the nexus-prime-core binary
+
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use tonic::transport::Channel;
+use tonic::{Code, Request};
+use tokio::time::{sleep, Duration};
+use nexus_prime_core::fabric_proto::fabric::fabric_service_client::FabricServiceClient;
+use nexus_prime_core::fabric_proto::fabric::*;
+
+const AUTH_SECRET: &str = "integration-test-secret-0123456789abcdef";
+
+// Stops the server when the test ends, whether it passed or not
+struct ServerProcess(Child);
+
+impl Drop for ServerProcess {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// The binary with a test secret, keeping its sled database in `data_dir`
+fn core_command(data_dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_nexus-prime-core"));
+    command.current_dir(data_dir).env("NEXUS_SECURITY__AUTH_TOKEN_SECRET", AUTH_SECRET);
+    command
+}
+
+fn start_server(mut command: Command) -> ServerProcess {
+    ServerProcess(command.stdout(Stdio::null()).stderr(Stdio::null()).spawn().unwrap())
+}
+
+fn issue_token(data_dir: &Path, role: &str) -> String {
+    let output = core_command(data_dir).args(["issue-token", role, "integration-test"]).output().unwrap();
+    assert!(output.status.success(), "issue-token failed: {}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+async fn connect() -> FabricServiceClient<Channel> {
+    for _ in 0..100 {
+        if let Ok(client) = FabricServiceClient::connect("http://[::1]:50053").await {
+            return client;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    panic!("server did not start listening on [::1]:50053");
+}
+
+fn with_token<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+    request
+}
+
+// Both servers bind the same fixed ports, so they run one after the other in one test
+#[tokio::test]
+async fn integration_binary_rejects_calls_without_permission() {
+    let data_dir = std::env::temp_dir().join(format!("nexus-auth-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&data_dir).unwrap();
+
+    let server = start_server(core_command(&data_dir));
+    let mut client = connect().await;
+    let status = client.list_nodes(Request::new(ListNodesRequest::default())).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    // Tokens issued by the binary are honoured for their role's permissions only
+    let viewer = issue_token(&data_dir, "viewer");
+    assert!(client.list_nodes(with_token(ListNodesRequest::default(), &viewer)).await.is_ok());
+    let command = || FabricCommand { command_type: "REBOOT_NODE".to_string(), ..Default::default() };
+    let status = client.send_fabric_command(with_token(command(), &viewer)).await.unwrap_err();
+    assert_eq!(status.code(), Code::PermissionDenied);
+    let admin = issue_token(&data_dir, "admin");
+    if let Err(status) = client.send_fabric_command(with_token(command(), &admin)).await {
+        assert_ne!(status.code(), Code::PermissionDenied);
+        assert_ne!(status.code(), Code::Unauthenticated);
+    }
+    drop(server);
+
+    // With enforcement switched off, calls without a token are served
+    let mut command = core_command(&data_dir);
+    command.env("NEXUS_SECURITY__ENFORCE_AUTH", "false");
+    let server = start_server(command);
+    let mut client = connect().await;
+    assert!(client.list_nodes(Request::new(ListNodesRequest::default())).await.is_ok());
+
+    drop(server);
+    let _ = std::fs::remove_dir_all(data_dir);
+}
@@ -20,13 +20,14 @@ async fn integration_nexus_prime_grpc() {
     let mut client = FabricServiceClient::connect("http://[::1]:50051").await.unwrap();
 
     // Subscribe to StreamFabricEvents before sending any events
-    let mut event_stream = client.stream_fabric_events(Request::new(())).await.unwrap().into_inner();
+    let mut event_stream = client.stream_fabric_events(Request::new(StreamFabricEventsRequest::default())).await.unwrap().into_inner();
 
     // 1. RegisterAgent for a PC type
     let reg_req = AgentRegistrationRequest {
         agent_type: 1, // AGENT_TYPE_PC
         ip_address: "127.0.0.1".to_string(),
         capabilities: "CPU:4,RAM:16GB".to_string(),
+        ..Default::default()
     };
     let reg_resp = client.register_agent(Request::new(reg_req)).await.unwrap().into_inner();
     assert!(!reg_resp.node_id.is_empty());
@@ -0,0 +1,78 @@
+// Integration test for the gRPC server over mutual TLS
+//
+// The certificates in tests/fixtures/tls were made with openssl: a test CA, a server
+// certificate for localhost/127.0.0.1/::1 and a client certificate, all P-256.
+
+use std::path::PathBuf;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig};
+use tonic::Request;
+use tokio::sync::oneshot;
+use tokio::time::{sleep, Duration};
+use nexus_prime_core::config::SecurityConfig;
+use nexus_prime_core::fabric_proto::fabric::fabric_service_client::FabricServiceClient;
+use nexus_prime_core::fabric_proto::fabric::ListNodesRequest;
+use nexus_prime_core::{NexusConfig, SecurityManager};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/tls").join(name)
+}
+
+fn mtls_config() -> SecurityConfig {
+    let mut security = NexusConfig::default().security;
+    security.enable_mtls = true;
+    security.ca_cert_path = Some(fixture("ca.pem"));
+    security.server_cert_path = Some(fixture("server.pem"));
+    security.server_key_path = Some(fixture("server.key"));
+    security.client_cert_path = Some(fixture("client.pem"));
+    security.client_key_path = Some(fixture("client.key"));
+    security
+}
+
+async fn list_nodes(port: u16, tls: ClientTlsConfig) -> Result<usize, String> {
+    let channel = Channel::from_shared(format!("https://127.0.0.1:{}", port))
+        .unwrap()
+        .tls_config(tls)
+        .map_err(|e| e.to_string())?
+        .connect()
+        .await
+        .map_err(|e| e.to_string())?;
+    let response = FabricServiceClient::new(channel)
+        .list_nodes(Request::new(ListNodesRequest::default()))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(response.into_inner().nodes.len())
+}
+
+#[tokio::test]
+async fn integration_grpc_server_requires_client_certificate() {
+    let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    let server_handle = tokio::spawn(async move {
+        nexus_prime_core::spawn_server_with_security(mtls_config(), ([127, 0, 0, 1], port).into(), Some(shutdown_rx))
+            .await
+            .unwrap();
+    });
+
+    // The client certificate from the config is accepted once the server is up
+    let client_tls = SecurityManager::new(mtls_config())
+        .create_client_tls_config("localhost")
+        .unwrap()
+        .expect("mTLS is enabled");
+    let mut result = Err(String::new());
+    for _ in 0..50 {
+        result = list_nodes(port, client_tls.clone()).await;
+        if result.is_ok() {
+            break;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    assert!(result.is_ok(), "mTLS call failed: {:?}", result);
+
+    // A client that trusts the server but has no certificate of its own is turned away
+    let ca = Certificate::from_pem(std::fs::read(fixture("ca.pem")).unwrap());
+    let anonymous_tls = ClientTlsConfig::new().domain_name("localhost").ca_certificate(ca);
+    assert!(list_nodes(port, anonymous_tls).await.is_err());
+
+    let _ = shutdown_tx.send(());
+    let _ = server_handle.await;
+}
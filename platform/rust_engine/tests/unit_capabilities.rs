@@ -0,0 +1,35 @@
+// Unit tests for parsing node capabilities and matching them against requirements
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::*;
+
+    #[test]
+    fn test_capability_strings_parse_in_several_formats() {
+        assert_eq!(NodeCapabilities::parse("CPU:4,RAM:16GB,GPU:1"), NodeCapabilities {
+            cpu_cores: 4,
+            memory_bytes: 16 << 30,
+            gpus: 1,
+            features: vec![],
+        });
+        assert_eq!(NodeCapabilities::parse(" cpu: 8 , mem:512mb, avx512 "), NodeCapabilities {
+            cpu_cores: 8,
+            memory_bytes: 512 << 20,
+            gpus: 0,
+            features: vec!["avx512".to_string()],
+        });
+        assert_eq!(NodeCapabilities::parse("MEMORY:1073741824,Memory:2T").memory_bytes, 2 << 40);
+        assert_eq!(NodeCapabilities::parse(""), NodeCapabilities::default());
+    }
+
+    #[test]
+    fn test_malformed_capabilities_count_as_zero() {
+        let parsed = NodeCapabilities::parse("CPU:lots,RAM:16XB,GPU:-1,,RAM");
+        assert_eq!((parsed.cpu_cores, parsed.memory_bytes, parsed.gpus), (0, 0, 0));
+        assert_eq!(parsed.features, vec!["CPU:lots", "RAM:16XB", "GPU:-1", "RAM"]);
+
+        let requirement = CapabilityRequirement { cpu_cores: 1, ..Default::default() };
+        assert!(!requirement.is_met_by(&parsed));
+        assert!(CapabilityRequirement::default().is_met_by(&parsed));
+    }
+}
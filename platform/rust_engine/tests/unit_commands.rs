@@ -0,0 +1,141 @@
+// Unit tests for typed FabricCommand parsing
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use nexus_prime_core::commands::*;
+    use nexus_prime_core::CapabilityRequirement;
+    use nexus_prime_core::fabric_proto::fabric::FabricCommand;
+
+    fn command(command_type: &str, target_id: &str, parameters: &[(&str, &str)]) -> FabricCommand {
+        FabricCommand {
+            command_id: "cmd-1".to_string(),
+            command_type: command_type.to_string(),
+            target_id: target_id.to_string(),
+            parameters: parameters.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect::<HashMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn test_parse_deploy_agent() {
+        let cmd = command("DEPLOY_AGENT", "node-1", &[("name", " Worker "), ("type", "Synthesizer"), ("env.API_URL", "http://api:8080")]);
+        assert_eq!(TypedCommand::try_from(&cmd), Ok(TypedCommand::DeployAgent(DeployAgentParams {
+            target_node_id: Some("node-1".to_string()),
+            name: "Worker".to_string(),
+            agent_type: "Synthesizer".to_string(),
+            pinned: false,
+            idempotency_key: None,
+            env: HashMap::from([("API_URL".to_string(), "http://api:8080".to_string())]),
+            fleet_id: None,
+            requirement: Default::default(),
+        })));
+    }
+
+    #[test]
+    fn test_deploy_agent_without_target_carries_requirement() {
+        let cmd = command("DEPLOY_AGENT", " ", &[("name", "Trainer"), ("type", "Synthesizer"), ("min_cpu_cores", "8"), ("min_memory", "32GB"), ("min_gpus", "1")]);
+        let Ok(TypedCommand::DeployAgent(params)) = TypedCommand::try_from(&cmd) else {
+            panic!("DEPLOY_AGENT without a target must parse");
+        };
+        assert_eq!(params.target_node_id, None);
+        assert_eq!(params.requirement, CapabilityRequirement { cpu_cores: 8, memory_bytes: 32 << 30, gpus: 1 });
+
+        let cmd = command("DEPLOY_AGENT", "", &[("name", "Trainer"), ("type", "Synthesizer"), ("min_memory", "lots")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "min_memory", .. })));
+        let cmd = command("DEPLOY_AGENT", "", &[("name", "Trainer"), ("type", "Synthesizer"), ("min_gpus", "-1")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "min_gpus", .. })));
+    }
+
+    #[test]
+    fn test_deploy_agent_missing_and_invalid_parameters() {
+        let cmd = command("DEPLOY_AGENT", "node-1", &[("type", "Synthesizer")]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "DEPLOY_AGENT", parameter: "name" }));
+
+        let cmd = command("DEPLOY_AGENT", "node 1", &[("name", "Worker"), ("type", "Synthesizer")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "target_id", .. })));
+
+        let cmd = command("DEPLOY_AGENT", "node-1", &[("name", "Worker"), ("type", "   ")]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "DEPLOY_AGENT", parameter: "type" }));
+
+        let cmd = command("DEPLOY_AGENT", "node-1", &[("name", "Worker"), ("type", "Data Miner")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "type", .. })));
+    }
+
+    #[test]
+    fn test_stop_agent_parameters() {
+        let cmd = command("STOP_AGENT", "agent-1", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Ok(TypedCommand::StopAgent(StopAgentParams { agent_id: "agent-1".to_string() })));
+
+        let cmd = command("STOP_AGENT", "", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "STOP_AGENT", parameter: "target_id" }));
+
+        let cmd = command("STOP_AGENT", "agent 1", &[]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "target_id", .. })));
+    }
+
+    #[test]
+    fn test_migrate_agent_parameters() {
+        let cmd = command("MIGRATE_AGENT", "agent-1", &[("destination_node", "node-2")]);
+        assert_eq!(TypedCommand::try_from(&cmd), Ok(TypedCommand::MigrateAgent(MigrateAgentParams {
+            agent_id: "agent-1".to_string(),
+            destination_node_id: "node-2".to_string(),
+            force: false,
+        })));
+
+        let cmd = command("MIGRATE_AGENT", "agent-1", &[("destination_node", "node-2"), ("force", "true")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Ok(TypedCommand::MigrateAgent(MigrateAgentParams { force: true, .. }))));
+
+        let cmd = command("MIGRATE_AGENT", "agent-1", &[("destination_node", "node-2"), ("force", "yes")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "force", .. })));
+
+        let cmd = command("MIGRATE_AGENT", "agent-1", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "MIGRATE_AGENT", parameter: "destination_node" }));
+
+        let cmd = command("MIGRATE_AGENT", "", &[("destination_node", "node-2")]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "MIGRATE_AGENT", parameter: "target_id" }));
+    }
+
+    #[test]
+    fn test_drain_node_parameters() {
+        let cmd = command("DRAIN_NODE", "node-1", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Ok(TypedCommand::DrainNode(DrainNodeParams {
+            node_id: "node-1".to_string(),
+            force: false,
+        })));
+
+        let cmd = command("DRAIN_NODE", "", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "DRAIN_NODE", parameter: "target_id" }));
+    }
+
+    #[test]
+    fn test_undrain_node_parameters() {
+        let cmd = command("UNDRAIN_NODE", "node-1", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Ok(TypedCommand::UndrainNode(UndrainNodeParams { node_id: "node-1".to_string() })));
+
+        let cmd = command("UNDRAIN_NODE", "", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::MissingParameter { command: "UNDRAIN_NODE", parameter: "target_id" }));
+    }
+
+    #[test]
+    fn test_rolling_update_parameters() {
+        let cmd = command("ROLLING_UPDATE", "fleet-1", &[("type", "Worker"), ("batch_size", "2")]);
+        assert_eq!(TypedCommand::try_from(&cmd), Ok(TypedCommand::RollingUpdate(RollingUpdateParams {
+            fleet_id: "fleet-1".to_string(),
+            agent_type: "Worker".to_string(),
+            batch_size: 2,
+            env: HashMap::new(),
+        })));
+
+        let cmd = command("ROLLING_UPDATE", "fleet-1", &[("type", "Worker"), ("batch_size", "0")]);
+        assert!(matches!(TypedCommand::try_from(&cmd), Err(CommandParseError::InvalidParameter { parameter: "batch_size", .. })));
+    }
+
+    #[test]
+    fn test_unknown_and_mismatched_command_types() {
+        let cmd = command("REBOOT_NODE", "node-1", &[]);
+        assert_eq!(TypedCommand::try_from(&cmd), Err(CommandParseError::UnknownCommand("REBOOT_NODE".to_string())));
+
+        let cmd = command("STOP_AGENT", "agent-1", &[]);
+        assert!(matches!(DeployAgentParams::try_from(&cmd), Err(CommandParseError::WrongCommandType { expected: "DEPLOY_AGENT", .. })));
+    }
+}
@@ -0,0 +1,293 @@
+// Unit tests for compressed, per-entity fabric state persistence
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use nexus_prime_core::config::Compression;
+    use nexus_prime_core::*;
+
+    fn large_state() -> FabricState {
+        let mut state = FabricState::default();
+        for i in 0..1000 {
+            let agent = AIAgent {
+                id: format!("agent-{:04}", i),
+                name: format!("Worker {}", i),
+                agent_type: "Synthesizer".to_string(),
+                assigned_node_id: Some(format!("node-{}", i % 20)),
+                status: "Running".to_string(),
+                current_task: Some("Indexing shard".to_string()),
+                task_progress: Some(0.5),
+                pinned: false,
+                env: Default::default(),
+                last_active: Utc::now(),
+                fleet_id: Some("fleet-1".to_string()),
+                orphaned_at: None,
+            };
+            state.ai_agents.insert(agent.id.clone(), agent);
+        }
+        state
+    }
+
+    #[tokio::test]
+    async fn test_compressed_state_round_trips_and_is_smaller() {
+        let state = large_state();
+        let plain = compression::encode(&state, Compression::None, 0).unwrap();
+        let compressed = compression::encode(&state, Compression::Zstd, 3).unwrap();
+        assert!(compressed.len() * 4 < plain.len(), "{} bytes plain, {} bytes with zstd", plain.len(), compressed.len());
+
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db).with_compression(Compression::Zstd, 3);
+        store.save_state(&state).await.unwrap();
+        let loaded = store.load_state().unwrap().unwrap();
+        assert_eq!(serde_json::to_value(&loaded).unwrap(), serde_json::to_value(&state).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_state_saved_before_compression_still_loads() {
+        let state = large_state();
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        db.insert("fabric_state", bincode::serialize(&state).unwrap()).unwrap();
+
+        let store = SledStateStore::new(db).with_compression(Compression::Zstd, 3);
+        assert_eq!(store.load_state().unwrap().unwrap().ai_agents.len(), 1000);
+    }
+
+    #[tokio::test]
+    async fn test_saving_after_one_update_rewrites_only_that_key() {
+        let mut state = FabricState::default();
+        for i in 0..100 {
+            let node = ComputeNode {
+                id: format!("node-{:03}", i),
+                node_type: "PC".to_string(),
+                last_seen: Utc::now(),
+                status: "Online".to_string(),
+                capabilities: "CPU:4,RAM:16GB".to_string(),
+                ip_address: format!("10.0.0.{}", i),
+                proxy_listen_address: None,
+                last_error: None,
+                last_error_at: None,
+                labels: Default::default(),
+                metadata: Default::default(),
+                resources: Default::default(),
+                registered_by: None,
+                draining: false,
+            };
+            state.compute_nodes.insert(node.id.clone(), node);
+        }
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db.clone());
+        store.save_state(&state).await.unwrap();
+
+        let mut writes = db.watch_prefix("");
+        state.compute_nodes.get_mut("node-042").unwrap().status = "Busy".to_string();
+        state.compute_nodes.remove("node-099");
+        store.save_state(&state).await.unwrap();
+
+        let mut changed = Vec::new();
+        while let Ok(event) = writes.next_timeout(std::time::Duration::from_millis(200)) {
+            changed.push(String::from_utf8(event.key().to_vec()).unwrap());
+        }
+        changed.sort();
+        assert_eq!(changed, vec!["node:node-042", "node:node-099"]);
+
+        let loaded = store.load_state().unwrap().unwrap();
+        assert_eq!(loaded.compute_nodes.len(), 99);
+        assert_eq!(loaded.compute_nodes["node-042"].status, "Busy");
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_entity_is_skipped_and_the_rest_load() {
+        let mut state = large_state();
+        state.ai_agents.retain(|id, _| id == "agent-0000");
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db.clone());
+        store.save_state(&state).await.unwrap();
+        db.insert("agent:agent-corrupt", b"not an agent".to_vec()).unwrap();
+
+        let loaded = store.load_state().unwrap().unwrap();
+        assert_eq!(loaded.ai_agents.keys().collect::<Vec<_>>(), vec!["agent-0000"]);
+        assert_eq!(loaded.ai_agents["agent-0000"].name, "Worker 0");
+    }
+
+    #[tokio::test]
+    async fn test_unreadable_entity_is_quarantined_not_deleted_by_the_next_save() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db.clone());
+        db.insert("agent:agent-newer", b"written by a newer version".to_vec()).unwrap();
+
+        let state = store.load_state().unwrap().unwrap_or_default();
+        assert!(state.ai_agents.is_empty());
+        store.save_state(&state).await.unwrap();
+
+        let quarantine = db.open_tree("fabric_state_quarantine").unwrap();
+        assert_eq!(quarantine.get("agent:agent-newer").unwrap().unwrap(), b"written by a newer version".as_ref());
+        assert!(db.get("agent:agent-newer").unwrap().is_none());
+    }
+
+    // The layouts the first release wrote, before any field was added
+    #[derive(serde::Serialize)]
+    struct NodeV1 {
+        id: String,
+        node_type: String,
+        last_seen: chrono::DateTime<Utc>,
+        status: String,
+        capabilities: String,
+        ip_address: String,
+        proxy_listen_address: Option<String>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct AgentV1 {
+        id: String,
+        name: String,
+        agent_type: String,
+        assigned_node_id: Option<String>,
+        status: String,
+        current_task: Option<String>,
+        task_progress: Option<f32>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct StateV1 {
+        compute_nodes: std::collections::HashMap<String, NodeV1>,
+        ai_agents: std::collections::HashMap<String, AgentV1>,
+    }
+
+    fn node_v1() -> NodeV1 {
+        NodeV1 {
+            id: "node-old".to_string(),
+            node_type: "PC".to_string(),
+            last_seen: Utc::now(),
+            status: "Online".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            ip_address: "10.0.0.1".to_string(),
+            proxy_listen_address: None,
+        }
+    }
+
+    fn agent_v1() -> AgentV1 {
+        AgentV1 {
+            id: "agent-old".to_string(),
+            name: "Worker".to_string(),
+            agent_type: "Synthesizer".to_string(),
+            assigned_node_id: Some("node-old".to_string()),
+            status: "Running".to_string(),
+            current_task: None,
+            task_progress: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_first_release_state_loads_and_survives_a_save() {
+        let legacy = StateV1 {
+            compute_nodes: [("node-old".to_string(), node_v1())].into(),
+            ai_agents: [("agent-old".to_string(), agent_v1())].into(),
+        };
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        db.insert("fabric_state", bincode::serialize(&legacy).unwrap()).unwrap();
+        let store = SledStateStore::new(db.clone());
+
+        let state = store.load_state().unwrap().unwrap();
+        assert_eq!(state.compute_nodes["node-old"].resources.cpu_cores, 4);
+        assert_eq!(state.ai_agents["agent-old"].assigned_node_id.as_deref(), Some("node-old"));
+        store.save_state(&state).await.unwrap();
+
+        let reloaded = store.load_state().unwrap().unwrap();
+        assert_eq!(reloaded.compute_nodes.len(), 1);
+        assert_eq!(reloaded.ai_agents.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_first_release_entities_load_from_their_own_keys() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        db.insert("node:node-old", bincode::serialize(&node_v1()).unwrap()).unwrap();
+        db.insert("agent:agent-old", bincode::serialize(&agent_v1()).unwrap()).unwrap();
+
+        let state = SledStateStore::new(db).load_state().unwrap().unwrap();
+        assert_eq!(state.compute_nodes["node-old"].ip_address, "10.0.0.1");
+        assert_eq!(state.ai_agents["agent-old"].name, "Worker");
+    }
+
+    #[tokio::test]
+    async fn test_entity_saved_before_a_field_was_added_still_loads() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db.clone());
+        let mut state = large_state();
+        state.ai_agents.retain(|id, _| id == "agent-0000");
+        store.save_state(&state).await.unwrap();
+
+        // Rewrite the agent as a version without `fleet_id` and `env` would have
+        let mut agent = serde_json::to_value(&state.ai_agents["agent-0000"]).unwrap();
+        agent.as_object_mut().unwrap().remove("fleet_id");
+        agent.as_object_mut().unwrap().remove("env");
+        let older: serde_json::Value = agent;
+        db.insert("agent:agent-0000", compression::encode(&older, Compression::None, 0).unwrap()).unwrap();
+
+        let loaded = store.load_state().unwrap().unwrap();
+        assert_eq!(loaded.ai_agents["agent-0000"].fleet_id, None);
+        assert_eq!(loaded.ai_agents["agent-0000"].name, "Worker 0");
+    }
+
+    fn state_with_secret() -> FabricState {
+        let mut state = large_state();
+        state.ai_agents.retain(|id, _| id == "agent-0000");
+        state.agent_secrets.insert("agent-0000".to_string(), [("API_TOKEN".to_string(), "hunter2".to_string())].into());
+        state
+    }
+
+    #[tokio::test]
+    async fn test_agent_secrets_are_sealed_at_rest() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db.clone()).with_secrets_key("secrets-key");
+        store.save_state(&state_with_secret()).await.unwrap();
+
+        let stored = db.get("agent_secrets:agent-0000").unwrap().unwrap();
+        assert!(!stored.windows(b"hunter2".len()).any(|window| window == b"hunter2"));
+        let loaded = store.load_state().unwrap().unwrap();
+        assert_eq!(loaded.agent_secrets["agent-0000"]["API_TOKEN"], "hunter2");
+
+        // Saving unchanged secrets does not reseal them
+        store.save_state(&loaded).await.unwrap();
+        assert_eq!(db.get("agent_secrets:agent-0000").unwrap().unwrap(), stored);
+
+        // Under another key the secrets are quarantined rather than misread
+        let other = SledStateStore::new(db.clone()).with_secrets_key("another-key");
+        assert!(other.load_state().unwrap().unwrap().agent_secrets.is_empty());
+        let quarantine = db.open_tree("fabric_state_quarantine").unwrap();
+        assert_eq!(quarantine.get("agent_secrets:agent-0000").unwrap().unwrap(), stored);
+    }
+
+    #[tokio::test]
+    async fn test_secrets_stored_in_the_clear_are_sealed_by_the_next_save() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        SledStateStore::new(db.clone()).save_state(&state_with_secret()).await.unwrap();
+        let clear = db.get("agent_secrets:agent-0000").unwrap().unwrap();
+
+        let store = SledStateStore::new(db.clone()).with_secrets_key("secrets-key");
+        let state = store.load_state().unwrap().unwrap();
+        assert_eq!(state.agent_secrets["agent-0000"]["API_TOKEN"], "hunter2");
+        store.save_state(&state).await.unwrap();
+        assert_ne!(db.get("agent_secrets:agent-0000").unwrap().unwrap(), clear);
+        assert_eq!(store.load_state().unwrap().unwrap().agent_secrets["agent-0000"]["API_TOKEN"], "hunter2");
+    }
+
+    #[tokio::test]
+    async fn test_secrets_are_deleted_with_their_agent() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let store = SledStateStore::new(db.clone()).with_secrets_key("secrets-key");
+        let mut state = state_with_secret();
+        store.save_state(&state).await.unwrap();
+
+        // The agent goes but its secrets are still in the state
+        state.ai_agents.clear();
+        store.save_state(&state).await.unwrap();
+        assert!(db.get("agent_secrets:agent-0000").unwrap().is_none());
+
+        // Secrets stranded on disk by an earlier version are dropped on load
+        db.insert("agent_secrets:agent-gone", compression::encode(&state.agent_secrets["agent-0000"], Compression::None, 0).unwrap()).unwrap();
+        let reloaded = store.load_state().unwrap().unwrap_or_default();
+        assert!(reloaded.agent_secrets.is_empty());
+        store.save_state(&reloaded).await.unwrap();
+        assert!(db.get("agent_secrets:agent-gone").unwrap().is_none());
+    }
+}
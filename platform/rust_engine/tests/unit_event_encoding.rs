@@ -0,0 +1,44 @@
+// Unit tests for WebSocket event stream encodings
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::fabric_proto::fabric::FabricEvent;
+    use nexus_prime_core::*;
+    use prost::Message;
+
+    #[test]
+    fn test_json_is_the_default_encoding() {
+        let requested: EventEncoding = serde_json::from_str("\"protobuf\"").unwrap();
+        assert_eq!(requested, EventEncoding::Protobuf);
+        assert_eq!(EventEncoding::default(), EventEncoding::Json);
+
+        let encoded = EventEncoding::default().encode(&InternalFabricEvent::NodePruned("node-1".to_string()));
+        assert_eq!(encoded, EncodedEvent::Text("{\"NodePruned\":\"node-1\"}".to_string()));
+    }
+
+    #[test]
+    fn test_protobuf_frames_decode_to_fabric_events() {
+        let events = [
+            InternalFabricEvent::NodePruned("node-1".to_string()),
+            InternalFabricEvent::AgentPinChanged("agent-1".to_string(), true),
+        ];
+
+        // A client reading a byte stream splits it back into events by their length prefixes
+        let mut stream = Vec::new();
+        for event in &events {
+            let EncodedEvent::Binary(frame) = EventEncoding::Protobuf.encode(event) else {
+                panic!("protobuf events must be sent as binary frames");
+            };
+            stream.extend(frame);
+        }
+        let mut buf = stream.as_slice();
+        let first = FabricEvent::decode_length_delimited(&mut buf).unwrap();
+        let second = FabricEvent::decode_length_delimited(&mut buf).unwrap();
+        assert!(buf.is_empty());
+
+        assert_eq!(first.event_type, "NODE_PRUNED");
+        assert_eq!(first.message, FabricManager::convert_event(&events[0]).message);
+        assert_eq!(second.event_type, FabricManager::convert_event(&events[1]).event_type);
+        assert_eq!(second.metadata, FabricManager::convert_event(&events[1]).metadata);
+    }
+}
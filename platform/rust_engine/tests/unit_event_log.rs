@@ -0,0 +1,91 @@
+// Unit tests for the signed fabric event log
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::event_log::EventLog;
+    use nexus_prime_core::*;
+    use tokio::sync::{broadcast, mpsc};
+
+    fn signed_log(db: &sled::Db) -> EventLog {
+        EventLog::open(db).unwrap().with_signing_key(b"test-secret".to_vec())
+    }
+
+    fn append_events(log: &EventLog, count: usize) {
+        for i in 0..count {
+            log.append(&InternalFabricEvent::NodePruned(format!("node-{}", i))).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fabric_events_are_persisted_and_verify() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db.clone())
+            .with_event_log(signed_log(&db));
+
+        manager.issue_command(Default::default()).await.unwrap();
+        manager.prune_stale_entities().await;
+        manager.issue_command(Default::default()).await.unwrap();
+
+        let event_log = manager.event_log().unwrap();
+        assert_eq!(event_log.entries().unwrap().len(), 2);
+        assert_eq!(event_log.verify_event_log().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_tampered_event_fails_verification() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let log = signed_log(&db);
+        append_events(&log, 3);
+        assert_eq!(log.verify_event_log().unwrap(), 3);
+
+        let tree = db.open_tree("fabric_event_log").unwrap();
+        let key = 1u64.to_be_bytes();
+        let mut entry: EventLogEntry = compression::decode(&tree.get(key).unwrap().unwrap()).unwrap();
+        entry.event = InternalFabricEvent::NodePruned("node-forged".to_string());
+        tree.insert(key, compression::encode(&entry, config::Compression::None, 0).unwrap()).unwrap();
+
+        assert!(matches!(log.verify_event_log(), Err(EventLogError::InvalidSignature(1))));
+    }
+
+    #[test]
+    fn test_removed_event_is_reported_as_gap() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let log = signed_log(&db);
+        append_events(&log, 3);
+
+        db.open_tree("fabric_event_log").unwrap().remove(1u64.to_be_bytes()).unwrap();
+
+        assert!(matches!(log.verify_event_log(), Err(EventLogError::Gap { expected: 1, found: 2 })));
+    }
+
+    #[test]
+    fn test_log_keeps_newest_entries_and_still_verifies() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let log = signed_log(&db).with_max_entries(3);
+        append_events(&log, 5);
+
+        let sequences: Vec<u64> = log.entries().unwrap().iter().map(|entry| entry.sequence).collect();
+        assert_eq!(sequences, vec![2, 3, 4]);
+        assert_eq!(log.verify_event_log().unwrap(), 3);
+
+        // Reopened, the log continues the chain from the kept entries
+        let log = signed_log(&db).with_max_entries(3);
+        append_events(&log, 1);
+        assert_eq!(log.entries().unwrap().first().unwrap().sequence, 3);
+        assert_eq!(log.verify_event_log().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_events_are_stored_by_variant_name() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let log = signed_log(&db);
+        log.append(&InternalFabricEvent::AgentPruned("agent-1".to_string())).unwrap();
+
+        let stored = db.open_tree("fabric_event_log").unwrap().get(0u64.to_be_bytes()).unwrap().unwrap();
+        let entry: serde_json::Value = compression::decode(&stored).unwrap();
+        assert_eq!(entry["event"]["AgentPruned"], "agent-1");
+    }
+}
@@ -0,0 +1,38 @@
+// Unit tests for the persisted event replay backlog
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::fabric_proto::fabric::FabricEvent;
+    use nexus_prime_core::EventReplay;
+    use std::time::Duration;
+
+    fn event(message: &str) -> FabricEvent {
+        FabricEvent { message: message.to_string(), ..Default::default() }
+    }
+
+    fn messages(events: Vec<FabricEvent>) -> Vec<String> {
+        events.into_iter().map(|event| event.message).collect()
+    }
+
+    #[test]
+    fn test_backlog_keeps_only_newest_events_across_reopen() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let replay = EventReplay::open(&db, 2, Duration::from_secs(3600)).unwrap();
+        for message in ["first", "second", "third"] {
+            replay.record(&mut event(message)).unwrap();
+        }
+        assert_eq!(messages(replay.events_after(0).unwrap()), vec!["second", "third"]);
+
+        let reopened = EventReplay::open(&db, 1, Duration::from_secs(3600)).unwrap();
+        assert_eq!(messages(reopened.events_after(0).unwrap()), vec!["third"]);
+    }
+
+    #[test]
+    fn test_expired_events_are_not_replayed() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let replay = EventReplay::open(&db, 10, Duration::ZERO).unwrap();
+        replay.record(&mut event("stale")).unwrap();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(replay.events_after(0).unwrap().is_empty());
+    }
+}
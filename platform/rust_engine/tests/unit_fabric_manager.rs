@@ -2,19 +2,23 @@
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use std::sync::Arc;
-    use tokio::sync::{broadcast, mpsc, Mutex};
+    use tokio::sync::{broadcast, mpsc};
     use nexus_prime_core::*;
-    use nexus_prime_core::fabric_proto::fabric::FabricCommand;
+    use nexus_prime_core::fabric_proto::fabric::*;
+    use nexus_prime_core::fabric_proto::fabric::fabric_service_server::FabricService;
+    use nexus_prime_core::fabric_proto::fabric::node_proxy_service_server::{NodeProxyService, NodeProxyServiceServer};
+    use std::sync::Arc;
     use chrono::Utc;
 
+    fn temp_db() -> sled::Db {
+        sled::Config::new().temporary(true).open().unwrap()
+    }
+
     fn setup_manager() -> FabricManager {
-        let state = Arc::new(Mutex::new(FabricState::default()));
         let (event_bus_tx, _) = broadcast::channel(10);
         let (event_stream_tx, _) = broadcast::channel(10);
         let (command_tx, _command_rx) = mpsc::channel(10);
-        FabricManager::new(state, event_bus_tx, event_stream_tx, command_tx)
+        FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
     }
 
     #[tokio::test]
@@ -27,9 +31,17 @@ mod tests {
             status: "Online".to_string(),
             capabilities: "CPU:4,RAM:16GB".to_string(),
             ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: None,
+            last_error: None,
+            last_error_at: None,
+            labels: Default::default(),
+            metadata: Default::default(),
+            resources: Default::default(),
+            registered_by: None,
+            draining: false,
         };
-        manager.register_node(node.clone()).await;
-        let state = manager.state.lock().await;
+        manager.register_node(node.clone()).await.unwrap();
+        let state = manager.state.read().await;
         assert!(state.compute_nodes.contains_key("node-1"));
     }
 
@@ -43,10 +55,18 @@ mod tests {
             status: "Online".to_string(),
             capabilities: "CPU:4,RAM:16GB".to_string(),
             ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: None,
+            last_error: None,
+            last_error_at: None,
+            labels: Default::default(),
+            metadata: Default::default(),
+            resources: Default::default(),
+            registered_by: None,
+            draining: false,
         };
-        manager.register_node(node.clone()).await;
-        manager.update_node_status("node-2".to_string(), "Degraded".to_string(), None).await;
-        let state = manager.state.lock().await;
+        manager.register_node(node.clone()).await.unwrap();
+        manager.update_node_status("node-2".to_string(), "Degraded".to_string(), None).await.unwrap();
+        let state = manager.state.read().await;
         assert_eq!(state.compute_nodes["node-2"].status, "Degraded");
     }
 
@@ -61,9 +81,14 @@ mod tests {
             status: "Idle".to_string(),
             current_task: None,
             task_progress: None,
+            pinned: false,
+            env: Default::default(),
+            last_active: Utc::now(),
+            fleet_id: None,
+            orphaned_at: None,
         };
-        manager.register_ai_agent(agent.clone()).await;
-        let state = manager.state.lock().await;
+        manager.register_ai_agent(agent.clone()).await.unwrap();
+        let state = manager.state.read().await;
         assert!(state.ai_agents.contains_key("agent-1"));
     }
 
@@ -78,10 +103,15 @@ mod tests {
             status: "Idle".to_string(),
             current_task: None,
             task_progress: None,
+            pinned: false,
+            env: Default::default(),
+            last_active: Utc::now(),
+            fleet_id: None,
+            orphaned_at: None,
         };
-        manager.register_ai_agent(agent.clone()).await;
-        manager.update_ai_agent_status("agent-2".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.5)).await;
-        let state = manager.state.lock().await;
+        manager.register_ai_agent(agent.clone()).await.unwrap();
+        manager.update_ai_agent_status("agent-2".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.5)).await.unwrap();
+        let state = manager.state.read().await;
         assert_eq!(state.ai_agents["agent-2"].status, "Processing");
         assert_eq!(state.ai_agents["agent-2"].current_task, Some("TaskA".to_string()));
         assert_eq!(state.ai_agents["agent-2"].task_progress, Some(0.5));
@@ -89,18 +119,17 @@ mod tests {
 
     #[tokio::test]
     async fn test_issue_command_sends_to_channel() {
-        let state = Arc::new(Mutex::new(FabricState::default()));
         let (event_bus_tx, _) = broadcast::channel(10);
         let (event_stream_tx, _) = broadcast::channel(10);
         let (command_tx, mut command_rx) = mpsc::channel(10);
-        let manager = FabricManager::new(state, event_bus_tx, event_stream_tx, command_tx);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
         let command = FabricCommand {
             command_id: "cmd-1".to_string(),
             target_id: "node-1".to_string(),
             command_type: "REBOOT_NODE".to_string(),
             parameters: Default::default(),
         };
-        manager.issue_command(command.clone()).await;
+        manager.issue_command(command.clone()).await.unwrap();
         let received = command_rx.recv().await.unwrap();
         assert_eq!(received.command_id, "cmd-1");
     }
@@ -116,10 +145,2830 @@ mod tests {
             status: "Online".to_string(),
             capabilities: "CPU:4,RAM:16GB".to_string(),
             ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: None,
+            last_error: None,
+            last_error_at: None,
+            labels: Default::default(),
+            metadata: Default::default(),
+            resources: Default::default(),
+            registered_by: None,
+            draining: false,
         };
-        manager.register_node(node.clone()).await;
+        manager.register_node(node.clone()).await.unwrap();
         manager.prune_stale_entities().await;
-        let state = manager.state.lock().await;
+        let state = manager.state.read().await;
         assert!(!state.compute_nodes.contains_key("node-stale"));
     }
+
+    #[tokio::test]
+    async fn test_prune_now_removes_stale_entities_and_reports_counts() {
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let operator = security.generate_token("operator".to_string(), EntityType::User, vec![Permission::ManageFabric]).await.unwrap();
+        let viewer = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), manager.event_stream_tx.clone()).with_security(security);
+        manager.register_node(stale_node("node-stale", None)).await.unwrap();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-live", "") }).await.unwrap();
+        manager.register_ai_agent(AIAgent {
+            last_active: Utc::now() - chrono::Duration::hours(1),
+            status: "Stopped".to_string(),
+            ..running_agent("agent-stale", "node-live")
+        }).await.unwrap();
+        let request = |token: &str| {
+            let mut request = tonic::Request::new(());
+            request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+            request
+        };
+
+        let status = service.prune_now(request(&viewer)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert!(manager.get_node("node-stale").await.is_some());
+
+        let response = service.prune_now(request(&operator)).await.unwrap().into_inner();
+        assert_eq!((response.pruned_nodes, response.pruned_agents), (1, 1));
+        assert!(manager.get_node("node-stale").await.is_none());
+        assert!(manager.get_node("node-live").await.is_some());
+        assert!(manager.get_agent("agent-stale").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_removed_nodes_and_revoked_entities_lose_their_tokens() {
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let admin = security.generate_token_for_role("admin".to_string(), EntityType::User, Role::Admin).await.unwrap();
+        let node = security.generate_token_for_role("node-gone".to_string(), EntityType::Node, Role::NodeAgent).await.unwrap();
+        let user = security.generate_token_for_role("ui".to_string(), EntityType::User, Role::Viewer).await.unwrap();
+        let manager = setup_manager().with_security(Arc::new(security.clone()));
+        let service = FabricServiceServerImpl::new(manager.clone(), manager.event_stream_tx.clone()).with_security(security.clone());
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-gone", "") }).await.unwrap();
+
+        manager.deregister_node("node-gone").await.unwrap();
+        assert!(security.validate_token(&node).await.is_err());
+
+        let request = RevokeEntityRequest { entity_id: "ui".to_string() };
+        let status = service.revoke_entity(authorized(request.clone(), Some(&user))).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        service.revoke_entity(authorized(request, Some(&admin))).await.unwrap();
+        assert!(security.validate_token(&user).await.is_err());
+        assert!(security.validate_token(&admin).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_must_come_from_the_node_or_its_registrant() {
+        use nexus_prime_core::fabric_proto::fabric::HeartbeatRequest;
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let registrant = security.generate_token("node-agent".to_string(), EntityType::Node, vec![Permission::RegisterNode]).await.unwrap();
+        let other = security.generate_token("other-agent".to_string(), EntityType::Node, vec![Permission::RegisterNode]).await.unwrap();
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), manager.event_stream_tx.clone()).with_security(security.clone());
+        let registration = AgentRegistrationRequest { ip_address: "10.0.0.8".to_string(), ..Default::default() };
+        let node_id = service.register_agent(authorized(registration, Some(&registrant))).await.unwrap().into_inner().node_id;
+        let own = security.generate_token(node_id.clone(), EntityType::Node, vec![Permission::RegisterNode]).await.unwrap();
+        let heartbeat = || HeartbeatRequest { node_id: node_id.clone() };
+
+        let status = service.heartbeat(authorized(heartbeat(), Some(&other))).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        service.heartbeat(authorized(heartbeat(), Some(&registrant))).await.unwrap();
+        service.heartbeat(authorized(heartbeat(), Some(&own))).await.unwrap();
+    }
+
+    fn authorized<T>(message: T, token: Option<&str>) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        if let Some(token) = token {
+            request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_rpcs_over_the_wire_require_a_token_with_their_permission() {
+        use nexus_prime_core::fabric_proto::fabric::fabric_service_client::FabricServiceClient;
+        use nexus_prime_core::fabric_proto::fabric::fabric_service_server::FabricServiceServer;
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let node = security.generate_token("node-agent".to_string(), EntityType::Node, vec![Permission::RegisterNode]).await.unwrap();
+        let operator = security.generate_token("operator".to_string(), EntityType::User, vec![Permission::ManageFabric]).await.unwrap();
+        let (command_tx, mut command_rx) = mpsc::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let manager = FabricManager::new(broadcast::channel(10).0, event_stream_tx.clone(), command_tx, temp_db());
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx).with_security(security);
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        tokio::spawn(tonic::transport::Server::builder().add_service(FabricServiceServer::new(service)).serve(addr));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let mut client = FabricServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+        let registration = || AgentRegistrationRequest { ip_address: "10.0.0.7".to_string(), ..Default::default() };
+        let command = || FabricCommand {
+            command_id: "cmd-auth".to_string(),
+            target_id: "node-1".to_string(),
+            command_type: "REBOOT_NODE".to_string(),
+            parameters: Default::default(),
+        };
+
+        let status = client.register_agent(authorized(registration(), None)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        let status = client.register_agent(authorized(registration(), Some("not-a-token"))).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+        assert!(manager.list_nodes().await.is_empty());
+        client.register_agent(authorized(registration(), Some(&node))).await.unwrap();
+        assert_eq!(manager.list_nodes().await.len(), 1);
+
+        // A node's token may register but not command the fabric
+        let status = client.send_fabric_command(authorized(command(), Some(&node))).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert!(command_rx.try_recv().is_err());
+        client.send_fabric_command(authorized(command(), Some(&operator))).await.unwrap();
+        assert_eq!(command_rx.recv().await.unwrap().command_id, "cmd-auth");
+    }
+
+    #[tokio::test]
+    async fn test_state_snapshots_list_and_get() {
+        let manager = setup_manager();
+        assert!(manager.list_nodes().await.is_empty());
+        assert!(manager.list_agents().await.is_empty());
+
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-b", "") }).await.unwrap();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-a", "") }).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-2", "node-a")).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-1", "node-b")).await.unwrap();
+
+        let node_ids: Vec<String> = manager.list_nodes().await.into_iter().map(|n| n.id).collect();
+        assert_eq!(node_ids, vec!["node-a", "node-b"]);
+        let agent_ids: Vec<String> = manager.list_agents().await.into_iter().map(|a| a.id).collect();
+        assert_eq!(agent_ids, vec!["agent-1", "agent-2"]);
+        assert_eq!(manager.get_node("node-a").await.unwrap().id, "node-a");
+        assert_eq!(manager.get_agent("agent-2").await.unwrap().assigned_node_id.as_deref(), Some("node-a"));
+        assert!(manager.get_node("node-missing").await.is_none());
+        assert!(manager.get_agent("agent-missing").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_subscription_sees_later_changes_as_events() {
+        let manager = setup_manager();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-before", "") }).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-before", "node-before")).await.unwrap();
+
+        let (snapshot, mut rx) = manager.subscribe_with_snapshot().await;
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-after", "") }).await.unwrap();
+
+        assert_eq!(snapshot.nodes.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec!["node-before"]);
+        assert_eq!(snapshot.agents.iter().map(|a| a.id.as_str()).collect::<Vec<_>>(), vec!["agent-before"]);
+        match rx.try_recv().unwrap() {
+            InternalFabricEvent::NodeRegistered(node) => assert_eq!(node.id, "node-after"),
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_short_node_timeout_prunes_node_sooner() {
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.node_timeout_seconds = 5;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        let default_manager = setup_manager();
+        let node = ComputeNode {
+            last_seen: Utc::now() - chrono::Duration::seconds(30),
+            proxy_listen_address: None,
+            ..proxied_node("node-quiet", "")
+        };
+        manager.register_node(node.clone()).await.unwrap();
+        default_manager.register_node(node).await.unwrap();
+
+        manager.prune_stale_entities().await;
+        default_manager.prune_stale_entities().await;
+
+        assert!(!manager.state.read().await.compute_nodes.contains_key("node-quiet"));
+        assert!(default_manager.state.read().await.compute_nodes.contains_key("node-quiet"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_clock_advance_triggers_pruning() {
+        let clock = MockClock::default();
+        let manager = setup_manager().with_clock(Arc::new(clock.clone()));
+        manager.register_node(ComputeNode {
+            last_seen: clock.now(),
+            proxy_listen_address: None,
+            ..proxied_node("node-idle", "")
+        }).await.unwrap();
+        manager.register_ai_agent(AIAgent {
+            last_active: clock.now(),
+            status: "Failed".to_string(),
+            ..running_agent("agent-idle", "node-unregistered")
+        }).await.unwrap();
+
+        manager.prune_stale_entities().await;
+        assert!(manager.get_node("node-idle").await.is_some());
+
+        clock.advance(chrono::Duration::seconds(NexusConfig::default().fabric.node_timeout_seconds as i64 + 1));
+        manager.prune_stale_entities().await;
+        assert!(manager.get_node("node-idle").await.is_none());
+        assert!(manager.get_agent("agent-idle").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pruned_agent_is_announced_on_the_bus_and_the_stream() {
+        let (event_bus_tx, mut bus_rx) = broadcast::channel(32);
+        let (event_stream_tx, mut stream_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let clock = MockClock::default();
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
+            .with_clock(Arc::new(clock.clone()));
+        manager.register_ai_agent(AIAgent {
+            last_active: clock.now(),
+            status: "Stopped".to_string(),
+            ..running_agent("agent-idle", "node-1")
+        }).await.unwrap();
+
+        clock.advance(chrono::Duration::seconds(NexusConfig::default().fabric.agent_timeout_seconds as i64 + 1));
+        manager.prune_stale_entities().await;
+
+        assert!(manager.get_agent("agent-idle").await.is_none());
+        assert!(std::iter::from_fn(|| bus_rx.try_recv().ok())
+            .any(|event| matches!(event, InternalFabricEvent::AgentPruned(agent_id) if agent_id == "agent-idle")));
+        let event = std::iter::from_fn(|| stream_rx.try_recv().ok())
+            .find(|event| event.event_type == "AGENT_PRUNED")
+            .expect("AGENT_PRUNED on the event stream");
+        assert_eq!(event.metadata["agent_id"], "agent-idle");
+    }
+
+    #[tokio::test]
+    async fn test_status_update_events_carry_the_recorded_timestamp() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        manager.register_node(stale_node("node-timed", None)).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-timed", "node-timed")).await.unwrap();
+        while event_rx.try_recv().is_ok() {}
+
+        manager.update_node_status("node-timed".to_string(), "Busy".to_string(), None).await.unwrap();
+        manager.update_ai_agent_status("agent-timed".to_string(), "Idle".to_string(), None, None).await.unwrap();
+
+        let node_event = event_rx.try_recv().unwrap();
+        assert_eq!(node_event.event_type, "NODE_STATUS_UPDATE");
+        assert_eq!(node_event.timestamp, manager.get_node("node-timed").await.unwrap().last_seen.to_rfc3339());
+        let agent_event = event_rx.try_recv().unwrap();
+        assert_eq!(agent_event.event_type, "AGENT_STATUS_UPDATE");
+        assert_eq!(agent_event.timestamp, manager.get_agent("agent-timed").await.unwrap().last_active.to_rfc3339());
+    }
+
+    #[tokio::test]
+    async fn test_repeated_node_status_is_broadcast_once() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        manager.register_node(stale_node("node-heartbeat", None)).await.unwrap();
+        let registered_at = manager.get_node("node-heartbeat").await.unwrap().last_seen;
+        while event_rx.try_recv().is_ok() {}
+
+        manager.update_node_status("node-heartbeat".to_string(), "Busy".to_string(), None).await.unwrap();
+        manager.update_node_status("node-heartbeat".to_string(), "Busy".to_string(), None).await.unwrap();
+        let telemetry = TelemetryData { cpu_utilization: 0.5, ..Default::default() };
+        manager.update_node_status("node-heartbeat".to_string(), String::new(), Some(telemetry)).await.unwrap();
+
+        let mut status_events = 0;
+        while let Ok(event) = event_rx.try_recv() {
+            status_events += usize::from(event.event_type == "NODE_STATUS_UPDATE");
+        }
+        assert_eq!(status_events, 1);
+        let node = manager.get_node("node-heartbeat").await.unwrap();
+        assert_eq!(node.status, "Busy");
+        assert!(node.last_seen > registered_at);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_keeps_node_alive_without_events() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let clock = MockClock::default();
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
+            .with_clock(Arc::new(clock.clone()));
+        manager.register_node(ComputeNode {
+            last_seen: clock.now(),
+            proxy_listen_address: None,
+            ..proxied_node("node-beating", "")
+        }).await.unwrap();
+        while event_rx.try_recv().is_ok() {}
+
+        let timeout = NexusConfig::default().fabric.node_timeout_seconds as i64;
+        clock.advance(chrono::Duration::seconds(timeout - 1));
+        manager.record_heartbeat("node-beating", None).await.unwrap();
+        clock.advance(chrono::Duration::seconds(2));
+        manager.prune_stale_entities().await;
+
+        let node = manager.get_node("node-beating").await.unwrap();
+        assert_eq!(node.last_seen, clock.now() - chrono::Duration::seconds(2));
+        assert_eq!(node.status, "Online");
+        assert!(event_rx.try_recv().is_err());
+        assert!(matches!(manager.record_heartbeat("node-missing", None).await, Err(FabricError::NodeNotFound(_))));
+    }
+
+    fn stale_node(id: &str, proxy_addr: Option<&str>) -> ComputeNode {
+        ComputeNode {
+            last_seen: Utc::now() - chrono::Duration::minutes(10),
+            proxy_listen_address: proxy_addr.map(str::to_string),
+            ..proxied_node(id, "")
+        }
+    }
+
+    fn running_agent(id: &str, node_id: &str) -> AIAgent {
+        AIAgent {
+            id: id.to_string(),
+            name: "Worker".to_string(),
+            agent_type: "Worker".to_string(),
+            assigned_node_id: Some(node_id.to_string()),
+            status: "Running".to_string(),
+            current_task: None,
+            task_progress: None,
+            pinned: false,
+            env: Default::default(),
+            last_active: Utc::now(),
+            fleet_id: None,
+            orphaned_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graceful_prune_stops_agents_before_removing_node() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let manager = setup_manager();
+        manager.register_node(stale_node("node-stale-agents", Some(&proxy_addr))).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-on-stale", "node-stale-agents")).await.unwrap();
+
+        manager.prune_stale_entities().await;
+
+        let state = manager.state.read().await;
+        assert!(!state.compute_nodes.contains_key("node-stale-agents"));
+        let agent = &state.ai_agents["agent-on-stale"];
+        assert_eq!(agent.status, "Stopped");
+        assert_eq!(agent.assigned_node_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_prune_of_unreachable_node_with_agents_blocked_or_forced() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        manager.register_node(stale_node("node-gone", None)).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-stranded", "node-gone")).await.unwrap();
+
+        // Graceful: the agent cannot be stopped, so the node is kept
+        manager.prune_stale_entities().await;
+        assert!(manager.state.read().await.compute_nodes.contains_key("node-gone"));
+        let mut blocked = None;
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type == "NODE_PRUNE_BLOCKED" {
+                blocked = Some(event);
+            }
+        }
+        assert_eq!(blocked.unwrap().metadata["active_agents"], "agent-stranded");
+
+        // Force: the node goes and the agent is marked Orphaned rather than left dangling
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.node_prune_policy = nexus_prime_core::config::NodePrunePolicy::Force;
+        let manager = manager.with_fabric_config(fabric_config);
+        manager.prune_stale_entities().await;
+        let state = manager.state.read().await;
+        assert!(!state.compute_nodes.contains_key("node-gone"));
+        assert_eq!(state.ai_agents["agent-stranded"].status, "Orphaned");
+        assert_eq!(state.ai_agents["agent-stranded"].assigned_node_id, None);
+    }
+
+    #[tokio::test]
+    async fn test_agents_orphaned_by_node_removal_are_reclaimed_or_failed() {
+        let proxy = MockProxy::default().with_checkpoints();
+        let deployed = Arc::clone(&proxy.deployed);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(64);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let clock = MockClock::default();
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.max_agents_per_node = 1;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
+            .with_fabric_config(fabric_config.clone())
+            .with_clock(Arc::new(clock.clone()));
+        manager.register_node(proxied_node("node-crashed", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-healthy", &proxy_addr)).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-a", "node-crashed")).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-b", "node-crashed")).await.unwrap();
+
+        // The node goes away without its agents having been stopped
+        manager.deregister_node("node-crashed").await.unwrap();
+        for agent_id in ["agent-a", "agent-b"] {
+            let agent = manager.get_agent(agent_id).await.unwrap();
+            assert_eq!(agent.status, "Orphaned");
+            assert_eq!(agent.assigned_node_id, None);
+        }
+        let mut orphaned: Vec<String> = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .filter(|event| event.event_type == "AGENT_ORPHANED")
+            .map(|event| event.metadata["agent_id"].clone())
+            .collect();
+        orphaned.sort();
+        assert_eq!(orphaned, vec!["agent-a", "agent-b"]);
+
+        // The healthy node has one slot: the first orphan is redeployed there, the second keeps waiting
+        assert_eq!(manager.reclaim_orphans().await, ReclaimCounts { reclaimed: 1, failed: 0 });
+        let reclaimed = manager.get_agent("agent-a").await.unwrap();
+        assert_eq!(reclaimed.status, "Running");
+        assert_eq!(reclaimed.assigned_node_id.as_deref(), Some("node-healthy"));
+        assert_eq!(deployed.lock().await.iter().map(|req| req.agent_id.as_str()).collect::<Vec<_>>(), vec!["agent-a"]);
+        let event = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .find(|event| event.event_type == "AGENT_RECLAIMED")
+            .unwrap();
+        assert_eq!(event.metadata["node_id"], "node-healthy");
+        assert_eq!(manager.get_agent("agent-b").await.unwrap().status, "Orphaned");
+
+        // Once the timeout passes without room, the remaining orphan fails
+        clock.advance(chrono::Duration::seconds(fabric_config.orphan_reclaim_timeout_secs as i64));
+        assert_eq!(manager.reclaim_orphans().await, ReclaimCounts { reclaimed: 0, failed: 1 });
+        assert_eq!(manager.get_agent("agent-b").await.unwrap().status, "Failed");
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok()).any(|event| event.event_type == "AGENT_ORPHAN_EXPIRED"));
+    }
+
+    #[tokio::test]
+    async fn test_reclaim_skips_pooled_agents_waits_out_maintenance_and_times_from_orphaning() {
+        let proxy = MockProxy::default().with_checkpoints();
+        let deployed = Arc::clone(&proxy.deployed);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(64);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let clock = MockClock::default();
+        let fabric_config = NexusConfig::default().fabric;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
+            .with_fabric_config(fabric_config.clone())
+            .with_clock(Arc::new(clock.clone()));
+        manager.register_node(proxied_node("node-crashed", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-healthy", &proxy_addr)).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-busy", "node-crashed")).await.unwrap();
+        manager.register_ai_agent(AIAgent { status: "Pooled".to_string(), ..running_agent("agent-pooled", "node-crashed") }).await.unwrap();
+        manager.deregister_node("node-crashed").await.unwrap();
+
+        // The idle pool member is stopped rather than queued for a relaunch as Running
+        let pooled = manager.get_agent("agent-pooled").await.unwrap();
+        assert_eq!(pooled.status, "Stopped");
+        assert_eq!(pooled.orphaned_at, None);
+        assert_eq!(manager.get_agent("agent-busy").await.unwrap().orphaned_at, Some(clock.now()));
+
+        // Maintenance holds orphans where they are, without expiring them
+        manager.set_maintenance_mode(true, "kernel upgrade".to_string()).await.unwrap();
+        clock.advance(chrono::Duration::seconds(fabric_config.orphan_reclaim_timeout_secs as i64));
+        assert_eq!(manager.reclaim_orphans().await, ReclaimCounts::default());
+        assert_eq!(manager.get_agent("agent-busy").await.unwrap().status, "Orphaned");
+        assert!(deployed.lock().await.is_empty());
+
+        // A late status report refreshes last_active, but the orphan still expires on time
+        manager.set_maintenance_mode(false, String::new()).await.unwrap();
+        manager.state.write().await.ai_agents.get_mut("agent-busy").unwrap().last_active = clock.now();
+        assert_eq!(manager.reclaim_orphans().await, ReclaimCounts { reclaimed: 0, failed: 1 });
+        assert_eq!(manager.get_agent("agent-busy").await.unwrap().status, "Failed");
+        assert!(deployed.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_agent_progress_updates_respect_threshold() {
+        let (event_bus_tx, _) = broadcast::channel(128);
+        let (event_stream_tx, _) = broadcast::channel(128);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        let agent = AIAgent {
+            id: "agent-progress".to_string(),
+            name: "Synthesizer".to_string(),
+            agent_type: "Synthesizer".to_string(),
+            assigned_node_id: Some("node-1".to_string()),
+            status: "Processing".to_string(),
+            current_task: Some("TaskA".to_string()),
+            task_progress: None,
+            pinned: false,
+            env: Default::default(),
+            last_active: Utc::now(),
+            fleet_id: None,
+            orphaned_at: None,
+        };
+        manager.register_ai_agent(agent).await.unwrap();
+
+        let mut events = manager.event_stream_tx.subscribe();
+        for percent in 1..=100 {
+            manager.update_ai_agent_status(
+                "agent-progress".to_string(),
+                "Processing".to_string(),
+                Some("TaskA".to_string()),
+                Some(percent as f32 / 100.0),
+            ).await.unwrap();
+        }
+
+        let mut progress_events = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            assert_eq!(event.event_type, "AGENT_STATUS_UPDATE");
+            progress_events.push(event.metadata["task_progress"].parse::<f32>().unwrap());
+        }
+        // First report (1%), every 5% step up to 96%, then completion
+        assert_eq!(progress_events.len(), 21);
+        assert_eq!(progress_events.first(), Some(&0.01));
+        assert_eq!(progress_events.last(), Some(&1.0));
+        let state = manager.state.read().await;
+        assert_eq!(state.ai_agents["agent-progress"].task_progress, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_task_transitions_are_broadcast_whatever_the_progress_delta() {
+        let manager = setup_manager();
+        manager.register_ai_agent(running_agent("agent-switch", "node-1")).await.unwrap();
+        let mut events = manager.event_stream_tx.subscribe();
+        manager.update_ai_agent_status("agent-switch".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.50)).await.unwrap();
+        manager.update_ai_agent_status("agent-switch".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.51)).await.unwrap();
+        manager.update_ai_agent_status("agent-switch".to_string(), "Processing".to_string(), Some("TaskB".to_string()), Some(0.52)).await.unwrap();
+        manager.update_ai_agent_status("agent-switch".to_string(), "Idle".to_string(), Some("TaskB".to_string()), Some(0.53)).await.unwrap();
+
+        let mut broadcast = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            broadcast.push((event.message, event.metadata["current_task"].clone(), event.metadata["task_progress"].clone()));
+        }
+        assert_eq!(broadcast, vec![
+            ("Agent agent-switch status updated: Processing".to_string(), "TaskA".to_string(), "0.5".to_string()),
+            ("Agent agent-switch status updated: Processing".to_string(), "TaskB".to_string(), "0.52".to_string()),
+            ("Agent agent-switch status updated: Idle".to_string(), "TaskB".to_string(), "0.53".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_agent_progress_threshold_is_configurable() {
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.agent_progress_event_threshold = 0.25;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        manager.register_ai_agent(AIAgent {
+            status: "Processing".to_string(),
+            current_task: Some("TaskA".to_string()),
+            ..running_agent("agent-coarse", "node-1")
+        }).await.unwrap();
+        let mut events = manager.event_stream_tx.subscribe();
+        for percent in [10, 20, 34, 40, 59, 60, 99] {
+            manager.update_ai_agent_status(
+                "agent-coarse".to_string(),
+                "Processing".to_string(),
+                Some("TaskA".to_string()),
+                Some(percent as f32 / 100.0),
+            ).await.unwrap();
+        }
+
+        let mut progress_events = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            progress_events.push(event.metadata["task_progress"].clone());
+        }
+        assert_eq!(progress_events, vec!["0.1", "0.4", "0.99"]);
+    }
+
+    #[tokio::test]
+    async fn test_stopped_agent_progress_is_forgotten() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let manager = setup_manager();
+        manager.register_node(stale_node("node-progress", Some(&proxy_addr))).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-stopping", "node-progress")).await.unwrap();
+        manager.update_ai_agent_status("agent-stopping".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.5)).await.unwrap();
+        manager.stop_agent("agent-stopping".to_string()).await.unwrap();
+
+        // Redeployed under the same id, its first progress report goes out like a new agent's
+        manager.register_ai_agent(AIAgent {
+            status: "Processing".to_string(),
+            current_task: Some("TaskA".to_string()),
+            ..running_agent("agent-stopping", "node-progress")
+        }).await.unwrap();
+        let mut events = manager.event_stream_tx.subscribe();
+        manager.update_ai_agent_status("agent-stopping".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.51)).await.unwrap();
+        assert_eq!(events.try_recv().unwrap().metadata["task_progress"], "0.51");
+    }
+
+    #[tokio::test]
+    async fn test_progress_of_an_agent_reported_failed_is_forgotten() {
+        let manager = setup_manager();
+        manager.register_ai_agent(running_agent("agent-crashing", "node-1")).await.unwrap();
+        manager.update_ai_agent_status("agent-crashing".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.5)).await.unwrap();
+        manager.update_ai_agent_status("agent-crashing".to_string(), "Failed".to_string(), Some("TaskA".to_string()), Some(0.5)).await.unwrap();
+
+        manager.register_ai_agent(AIAgent {
+            status: "Processing".to_string(),
+            current_task: Some("TaskA".to_string()),
+            ..running_agent("agent-crashing", "node-1")
+        }).await.unwrap();
+        let mut events = manager.event_stream_tx.subscribe();
+        manager.update_ai_agent_status("agent-crashing".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.51)).await.unwrap();
+        assert_eq!(events.try_recv().unwrap().metadata["task_progress"], "0.51");
+    }
+
+    #[tokio::test]
+    async fn test_task_counters_follow_assign_and_complete() {
+        let manager = setup_manager();
+        manager.register_ai_agent(running_agent("agent-tasks", "node-1")).await.unwrap();
+        let counters = manager.task_counters().clone();
+
+        manager.update_ai_agent_status("agent-tasks".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.0)).await.unwrap();
+        assert_eq!(counters.pending(), 1);
+        manager.update_ai_agent_status("agent-tasks".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(0.5)).await.unwrap();
+        assert_eq!(counters.pending(), 1);
+        manager.update_ai_agent_status("agent-tasks".to_string(), "Processing".to_string(), Some("TaskA".to_string()), Some(1.0)).await.unwrap();
+
+        assert_eq!(counters.completed(), 1);
+        assert_eq!(counters.pending(), 0);
+        assert_eq!(counters.failed(), 0);
+
+        manager.update_ai_agent_status("agent-tasks".to_string(), "Processing".to_string(), Some("TaskB".to_string()), Some(0.2)).await.unwrap();
+        manager.update_ai_agent_status("agent-tasks".to_string(), "Failed".to_string(), Some("TaskB".to_string()), Some(0.2)).await.unwrap();
+        assert_eq!(counters.failed(), 1);
+        assert_eq!(counters.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_agent_rejected_when_node_full() {
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.max_agents_per_node = 2;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        let node = ComputeNode {
+            id: "node-full".to_string(),
+            node_type: "PC".to_string(),
+            last_seen: Utc::now(),
+            status: "Online".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: None,
+            last_error: None,
+            last_error_at: None,
+            labels: Default::default(),
+            metadata: Default::default(),
+            resources: Default::default(),
+            registered_by: None,
+            draining: false,
+        };
+        manager.register_node(node).await.unwrap();
+        for i in 0..2 {
+            manager.register_ai_agent(AIAgent {
+                id: format!("agent-full-{}", i),
+                name: "Worker".to_string(),
+                agent_type: "Worker".to_string(),
+                assigned_node_id: Some("node-full".to_string()),
+                status: "Running".to_string(),
+                current_task: None,
+                task_progress: None,
+                pinned: false,
+                env: Default::default(),
+                last_active: Utc::now(),
+                fleet_id: None,
+                orphaned_at: None,
+            }).await.unwrap();
+        }
+
+        let result = manager.deploy_agent("node-full".to_string(), "Worker".to_string(), "Worker".to_string()).await;
+        assert!(matches!(result, Err(FabricError::NodeFull { max_agents: 2, .. })));
+        let state = manager.state.read().await;
+        assert_eq!(state.ai_agents.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_deploys_fill_node_up_to_agent_limit() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.max_agents_per_node = 3;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        manager.register_node(proxied_node("node-edge", &proxy_addr)).await.unwrap();
+        let deploy = || manager.deploy_agent("node-edge".to_string(), "Worker".to_string(), "Worker".to_string());
+
+        let mut agent_ids = Vec::new();
+        for _ in 0..3 {
+            agent_ids.push(deploy().await.unwrap().agent_id);
+        }
+        assert!(matches!(deploy().await, Err(FabricError::NodeFull { max_agents: 3, .. })));
+        assert_eq!(manager.agents_on_node("node-edge").await.unwrap().len(), 3);
+
+        // A stopped agent no longer takes up a slot
+        manager.stop_agent(agent_ids[0].clone()).await.unwrap();
+        assert!(deploy().await.is_ok());
+        assert!(matches!(deploy().await, Err(FabricError::NodeFull { .. })));
+    }
+
+    struct FailingStore;
+
+    #[tonic::async_trait]
+    impl FabricStateStore for FailingStore {
+        fn load_state(&self) -> FabricResult<Option<FabricState>> {
+            Ok(None)
+        }
+
+        async fn save_state(&self, _state: &FabricState) -> FabricResult<()> {
+            Err(FabricError::Persistence(sled::Error::Unsupported("read-only store".to_string())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_agent_fails_when_persistence_fails() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::with_store(event_bus_tx, event_stream_tx.clone(), command_tx, Arc::new(FailingStore));
+        let service = FabricServiceServerImpl::new(manager, event_stream_tx);
+        let request = AgentRegistrationRequest {
+            ip_address: "127.0.0.1".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            agent_type: 1,
+            proxy_listen_address: String::new(),
+            ..Default::default()
+        };
+        let status = service.register_agent(tonic::Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Internal);
+    }
+
+    #[derive(Default)]
+    struct FlakyStore {
+        failing: std::sync::atomic::AtomicBool,
+    }
+
+    #[tonic::async_trait]
+    impl FabricStateStore for FlakyStore {
+        fn load_state(&self) -> FabricResult<Option<FabricState>> {
+            Ok(None)
+        }
+
+        async fn save_state(&self, _state: &FabricState) -> FabricResult<()> {
+            if self.failing.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(FabricError::Persistence(sled::Error::Unsupported("disk unavailable".to_string())));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_ends_after_shutdown_announcement() {
+        use tokio_stream::StreamExt;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx.clone(), command_tx, temp_db());
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx);
+        let mut stream = service.stream_fabric_events(tonic::Request::new(StreamFabricEventsRequest::default())).await.unwrap().into_inner();
+
+        manager.announce_shutdown("maintenance").await;
+
+        let event = stream.next().await.unwrap().unwrap();
+        assert_eq!(event.event_type, SERVER_SHUTTING_DOWN_EVENT);
+        assert_eq!(event.metadata["reason"], "maintenance");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resumed_event_stream_replays_backlog_after_restart() {
+        use tokio_stream::StreamExt;
+        let db = temp_db();
+        let start_manager = || {
+            let (event_bus_tx, _) = broadcast::channel(10);
+            let (event_stream_tx, _) = broadcast::channel(10);
+            let (command_tx, _) = mpsc::channel(10);
+            let event_replay = EventReplay::open(&db, 100, std::time::Duration::from_secs(3600)).unwrap();
+            let manager = FabricManager::new(event_bus_tx, event_stream_tx.clone(), command_tx, db.clone())
+                .with_event_replay(event_replay);
+            (manager.clone(), FabricServiceServerImpl::new(manager, event_stream_tx))
+        };
+
+        let (manager, _service) = start_manager();
+        for id in ["node-a", "node-b", "node-c"] {
+            manager.register_node(stale_node(id, None)).await.unwrap();
+        }
+        let last_seen = manager.event_replay().unwrap().events_after(0).unwrap()[0].sequence;
+        drop(manager);
+
+        let (manager, service) = start_manager();
+        let request = StreamFabricEventsRequest { resume_after: Some(last_seen) };
+        let mut stream = service.stream_fabric_events(tonic::Request::new(request)).await.unwrap().into_inner();
+        manager.announce_shutdown("maintenance").await;
+
+        let mut received = Vec::new();
+        while let Some(event) = stream.next().await {
+            received.push(event.unwrap().message);
+        }
+        assert_eq!(received, vec![
+            "Node registered: node-b".to_string(),
+            "Node registered: node-c".to_string(),
+            "Server is shutting down: maintenance".to_string(),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_save_failures_enter_degraded_mode() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let store = Arc::new(FlakyStore::default());
+        store.failing.store(true, std::sync::atomic::Ordering::SeqCst);
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.save_failure_threshold = 2;
+        let manager = FabricManager::with_store(event_bus_tx, event_stream_tx.clone(), command_tx, store.clone())
+            .with_fabric_config(fabric_config);
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx);
+        let request = || tonic::Request::new(AgentRegistrationRequest {
+            ip_address: "127.0.0.1".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            agent_type: 1,
+            proxy_listen_address: String::new(),
+            ..Default::default()
+        });
+
+        for _ in 0..2 {
+            let status = service.register_agent(request()).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::Internal);
+        }
+        assert!(manager.is_degraded());
+
+        let status = service.register_agent(request()).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        let command = FabricCommand::default();
+        let status = service.send_fabric_command(tonic::Request::new(command)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+
+        let mut event_types = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            event_types.push(event.event_type);
+        }
+        assert_eq!(event_types.iter().filter(|t| *t == "PERSISTENCE_DEGRADED").count(), 1);
+
+        // Writes are accepted again once a save succeeds
+        store.failing.store(false, std::sync::atomic::Ordering::SeqCst);
+        service.register_agent(request()).await.unwrap();
+        assert!(!manager.is_degraded());
+        let mut recovered = false;
+        while let Ok(event) = event_rx.try_recv() {
+            recovered |= event.event_type == "PERSISTENCE_RECOVERED";
+        }
+        assert!(recovered);
+    }
+
+    #[tokio::test]
+    async fn test_unsaved_changes_are_rolled_back_without_events() {
+        let (event_bus_tx, mut event_rx) = broadcast::channel(32);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let store = Arc::new(FlakyStore::default());
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.save_failure_threshold = 0;
+        let manager = FabricManager::with_store(event_bus_tx, event_stream_tx, command_tx, store.clone())
+            .with_fabric_config(fabric_config);
+        manager.register_node(stale_node("node-saved", None)).await.unwrap();
+        while event_rx.try_recv().is_ok() {}
+
+        store.failing.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(manager.register_node(stale_node("node-unsaved", None)).await.is_err());
+        assert!(manager.get_node("node-unsaved").await.is_none());
+        assert!(manager.update_node_status("node-saved".to_string(), "Offline".to_string(), None).await.is_err());
+        assert_eq!(manager.get_node("node-saved").await.unwrap().status, "Online");
+        assert!(manager.register_ai_agent(running_agent("agent-unsaved", "node-saved")).await.is_err());
+        assert!(manager.get_agent("agent-unsaved").await.is_none());
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    // Stand-in node proxy shared by these tests. Stops and deploys are logged as
+    // "<node>:<command>:<agent id>" and the agents it runs are tracked; deploys and stops
+    // succeed unless configured otherwise, and checkpoints and agent reports are opt-in
+    #[derive(Clone)]
+    struct MockProxy {
+        node: &'static str,
+        log: Arc<tokio::sync::Mutex<Vec<String>>>,
+        deployed: Arc<tokio::sync::Mutex<Vec<DeployAgentRequest>>>,
+        shutdown_notices: Arc<tokio::sync::Mutex<Vec<String>>>,
+        agents: Arc<std::sync::Mutex<std::collections::HashMap<String, String>>>,
+        reject: Arc<std::sync::Mutex<Option<String>>>, // Deploys of this agent name or id, and stops of this id, report FAILED
+        reject_deploys: bool,
+        deploy_error: Option<&'static str>,
+        deploy_delay: Option<std::time::Duration>,
+        checkpoints: bool,
+        agent_reports: bool,
+    }
+
+    impl Default for MockProxy {
+        fn default() -> Self {
+            Self {
+                node: "proxy",
+                log: Default::default(),
+                deployed: Default::default(),
+                shutdown_notices: Default::default(),
+                agents: Default::default(),
+                reject: Default::default(),
+                reject_deploys: false,
+                deploy_error: None,
+                deploy_delay: None,
+                checkpoints: false,
+                agent_reports: false,
+            }
+        }
+    }
+
+    impl MockProxy {
+        fn with_node(mut self, node: &'static str) -> Self {
+            self.node = node;
+            self
+        }
+
+        fn with_log(mut self, log: Arc<tokio::sync::Mutex<Vec<String>>>) -> Self {
+            self.log = log;
+            self
+        }
+
+        fn with_rejected_deploys(mut self, reject_deploys: bool) -> Self {
+            self.reject_deploys = reject_deploys;
+            self
+        }
+
+        // Deploys fail at the transport level instead of reporting FAILED
+        fn with_deploy_error(mut self, message: &'static str) -> Self {
+            self.deploy_error = Some(message);
+            self
+        }
+
+        // Deploys take a while, like a real agent start-up
+        fn with_deploy_delay(mut self, delay: std::time::Duration) -> Self {
+            self.deploy_delay = Some(delay);
+            self
+        }
+
+        fn with_checkpoints(mut self) -> Self {
+            self.checkpoints = true;
+            self
+        }
+
+        fn with_agent_reports(mut self) -> Self {
+            self.agent_reports = true;
+            self
+        }
+
+        fn rejects(&self, id: &str) -> bool {
+            self.reject.lock().unwrap().as_deref() == Some(id)
+        }
+    }
+
+    #[tonic::async_trait]
+    impl NodeProxyService for MockProxy {
+        async fn deploy_agent(&self, request: tonic::Request<DeployAgentRequest>) -> Result<tonic::Response<CommandResponse>, tonic::Status> {
+            let req = request.into_inner();
+            if let Some(delay) = self.deploy_delay {
+                tokio::time::sleep(delay).await;
+            }
+            if let Some(message) = self.deploy_error {
+                return Err(tonic::Status::internal(message));
+            }
+            self.log.lock().await.push(format!("{}:deploy:{}", self.node, req.agent_id));
+            let rejected = self.reject_deploys || self.rejects(&req.name) || self.rejects(&req.agent_id);
+            if !rejected {
+                self.agents.lock().unwrap().insert(req.agent_id.clone(), "Running".to_string());
+            }
+            self.deployed.lock().await.push(req);
+            let status = if rejected { "FAILED" } else { "SUCCESS" };
+            Ok(tonic::Response::new(CommandResponse { status: status.to_string(), message: "deployed".to_string() }))
+        }
+
+        async fn stop_agent(&self, request: tonic::Request<StopAgentRequest>) -> Result<tonic::Response<CommandResponse>, tonic::Status> {
+            let agent_id = request.into_inner().agent_id;
+            self.log.lock().await.push(format!("{}:stop:{}", self.node, agent_id));
+            if self.rejects(&agent_id) {
+                return Ok(tonic::Response::new(CommandResponse { status: "FAILED".to_string(), message: "stop".to_string() }));
+            }
+            self.agents.lock().unwrap().remove(&agent_id);
+            Ok(tonic::Response::new(CommandResponse { status: "SUCCESS".to_string(), message: "stopped".to_string() }))
+        }
+
+        async fn checkpoint_agent(&self, request: tonic::Request<CheckpointAgentRequest>) -> Result<tonic::Response<CheckpointAgentResponse>, tonic::Status> {
+            if !self.checkpoints {
+                return Err(tonic::Status::unimplemented("checkpoint"));
+            }
+            let req = request.into_inner();
+            Ok(tonic::Response::new(CheckpointAgentResponse {
+                status: "SUCCESS".to_string(),
+                message: "checkpointed".to_string(),
+                checkpoint: Some(AgentCheckpoint {
+                    agent_id: req.agent_id,
+                    agent_type: "Synthesizer".to_string(),
+                    state: b"progress=42".to_vec(),
+                    resources: None,
+                    created_at: Utc::now().to_rfc3339(),
+                }),
+            }))
+        }
+
+        async fn notify_core_shutdown(&self, request: tonic::Request<CoreShutdownNotice>) -> Result<tonic::Response<CommandResponse>, tonic::Status> {
+            self.shutdown_notices.lock().await.push(request.into_inner().reason);
+            Ok(tonic::Response::new(CommandResponse { status: "SUCCESS".to_string(), message: "noted".to_string() }))
+        }
+
+        async fn list_agents(&self, _request: tonic::Request<()>) -> Result<tonic::Response<NodeAgentList>, tonic::Status> {
+            if !self.agent_reports {
+                return Err(tonic::Status::unimplemented("list agents"));
+            }
+            let agents = self.agents.lock().unwrap().iter()
+                .map(|(agent_id, status)| NodeAgentState { agent_id: agent_id.clone(), status: status.clone() })
+                .collect();
+            Ok(tonic::Response::new(NodeAgentList { agents }))
+        }
+    }
+
+    async fn spawn_mock_proxy<S: NodeProxyService>(proxy: S) -> String {
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        tokio::spawn(tonic::transport::Server::builder()
+            .add_service(NodeProxyServiceServer::new(proxy))
+            .serve(addr));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        addr.to_string()
+    }
+
+    fn proxied_node(id: &str, proxy_addr: &str) -> ComputeNode {
+        ComputeNode {
+            id: id.to_string(),
+            node_type: "PC".to_string(),
+            last_seen: Utc::now(),
+            status: "Online".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: Some(proxy_addr.to_string()),
+            last_error: None,
+            last_error_at: None,
+            labels: Default::default(),
+            metadata: Default::default(),
+            resources: Default::default(),
+            registered_by: None,
+            draining: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_agent_transfers_checkpoint() {
+        let proxy = MockProxy::default().with_checkpoints();
+        let deployed = Arc::clone(&proxy.deployed);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-src", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-dst", &proxy_addr)).await.unwrap();
+        manager.register_ai_agent(AIAgent {
+            id: "agent-migrating".to_string(),
+            name: "Synthesizer".to_string(),
+            agent_type: "Synthesizer".to_string(),
+            assigned_node_id: Some("node-src".to_string()),
+            status: "Running".to_string(),
+            current_task: Some("TaskA".to_string()),
+            task_progress: Some(0.42),
+            pinned: false,
+            env: Default::default(),
+            last_active: Utc::now(),
+            fleet_id: None,
+            orphaned_at: None,
+        }).await.unwrap();
+
+        manager.migrate_agent("agent-migrating".to_string(), "node-dst".to_string(), false).await.unwrap();
+
+        let deployed = deployed.lock().await;
+        assert_eq!(deployed.len(), 1);
+        let checkpoint = deployed[0].checkpoint.as_ref().expect("checkpoint should be transferred");
+        assert_eq!(checkpoint.state, b"progress=42".to_vec());
+        let state = manager.state.read().await;
+        assert_eq!(state.ai_agents["agent-migrating"].assigned_node_id.as_deref(), Some("node-dst"));
+        assert_eq!(state.ai_agents["agent-migrating"].status, "Running");
+    }
+
+    // A manager with "agent-moving" running on node-src; either proxy rejects deploys if asked to
+    async fn migration_manager(source_rejects: bool, destination_rejects: bool) -> (FabricManager, Arc<tokio::sync::Mutex<Vec<String>>>) {
+        let log = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+        let source_addr = spawn_mock_proxy(MockProxy::default().with_node("src").with_log(Arc::clone(&log)).with_rejected_deploys(source_rejects)).await;
+        let destination_addr = spawn_mock_proxy(MockProxy::default().with_node("dst").with_log(Arc::clone(&log)).with_rejected_deploys(destination_rejects)).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-src", &source_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-dst", &destination_addr)).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-moving", "node-src")).await.unwrap();
+        (manager, log)
+    }
+
+    #[tokio::test]
+    async fn test_migrate_agent_stops_on_source_before_deploying_on_destination() {
+        let (manager, log) = migration_manager(false, false).await;
+
+        manager.migrate_agent("agent-moving".to_string(), "node-dst".to_string(), false).await.unwrap();
+
+        assert_eq!(*log.lock().await, vec!["src:stop:agent-moving", "dst:deploy:agent-moving"]);
+        let agent = manager.get_agent("agent-moving").await.unwrap();
+        assert_eq!(agent.assigned_node_id.as_deref(), Some("node-dst"));
+        assert_eq!(agent.status, "Running");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_agent_rolls_back_when_destination_rejects() {
+        let (manager, log) = migration_manager(false, true).await;
+
+        let result = manager.migrate_agent("agent-moving".to_string(), "node-dst".to_string(), false).await;
+
+        assert!(matches!(result, Err(FabricError::ProxyError { node_id, .. }) if node_id == "node-dst"));
+        assert_eq!(*log.lock().await, vec!["src:stop:agent-moving", "dst:deploy:agent-moving", "src:deploy:agent-moving"]);
+        let agent = manager.get_agent("agent-moving").await.unwrap();
+        assert_eq!(agent.assigned_node_id.as_deref(), Some("node-src"));
+        assert_eq!(agent.status, "Running");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_agent_marks_agent_failed_when_it_cannot_be_restored() {
+        let (manager, log) = migration_manager(true, true).await;
+
+        let result = manager.migrate_agent("agent-moving".to_string(), "node-dst".to_string(), false).await;
+
+        assert!(matches!(result, Err(FabricError::ProxyError { node_id, .. }) if node_id == "node-dst"));
+        assert_eq!(*log.lock().await, vec!["src:stop:agent-moving", "dst:deploy:agent-moving", "src:deploy:agent-moving"]);
+        let agent = manager.get_agent("agent-moving").await.unwrap();
+        assert_eq!(agent.assigned_node_id, None);
+        assert_eq!(agent.status, "Failed");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_agent_requires_an_online_destination_with_a_free_slot() {
+        let (manager, log) = migration_manager(false, false).await;
+        manager.state.write().await.compute_nodes.get_mut("node-dst").unwrap().status = "Unreachable".to_string();
+        let result = manager.migrate_agent("agent-moving".to_string(), "node-dst".to_string(), false).await;
+        assert!(matches!(result, Err(FabricError::NodeOffline { node_id, .. }) if node_id == "node-dst"));
+
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.max_agents_per_node = 1;
+        let manager = manager.with_fabric_config(fabric_config);
+        manager.state.write().await.compute_nodes.get_mut("node-dst").unwrap().status = "Online".to_string();
+        manager.register_ai_agent(running_agent("agent-resident", "node-dst")).await.unwrap();
+        let result = manager.migrate_agent("agent-moving".to_string(), "node-dst".to_string(), false).await;
+        assert!(matches!(result, Err(FabricError::NodeFull { node_id, .. }) if node_id == "node-dst"));
+
+        // Neither proxy was touched and the agent stays where it was
+        assert!(log.lock().await.is_empty());
+        let agent = manager.get_agent("agent-moving").await.unwrap();
+        assert_eq!(agent.assigned_node_id.as_deref(), Some("node-src"));
+        assert_eq!(agent.status, "Running");
+    }
+
+    #[tokio::test]
+    async fn test_close_all_clients_notifies_proxies_and_removes_clients() {
+        let proxy = MockProxy::default().with_checkpoints();
+        let notices = Arc::clone(&proxy.shutdown_notices);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-a", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-b", &proxy_addr)).await.unwrap();
+        assert_eq!(manager.connected_node_ids().await, vec!["node-a", "node-b"]);
+
+        manager.close_all_clients("maintenance").await;
+
+        assert!(manager.connected_node_ids().await.is_empty());
+        assert_eq!(*notices.lock().await, vec!["maintenance", "maintenance"]);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_deploy_failures_raise_one_critical_event() {
+        let manager = setup_manager();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-no-client", "") }).await.unwrap();
+        let mut events = manager.event_stream_tx.subscribe();
+        let deploy = |target: &str| manager.deploy(DeployAgentParams {
+            target_node_id: Some(target.to_string()),
+            name: "Worker".to_string(),
+            agent_type: "Worker".to_string(),
+            pinned: false,
+            idempotency_key: None,
+            env: Default::default(),
+            fleet_id: None,
+            requirement: Default::default(),
+        });
+        // A deploy to a missing node is the caller's mistake and neither ends nor extends the run
+        for target in ["node-no-client", "node-no-client", "missing-node", "node-no-client", "node-no-client"] {
+            assert!(deploy(target).await.is_err());
+        }
+
+        let mut repeated = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if event.event_type == "DEPLOY_FAILURES_REPEATED" {
+                repeated.push(event);
+            }
+        }
+        assert_eq!(repeated.len(), 1);
+        assert_eq!(repeated[0].metadata["severity"], "CRITICAL");
+        assert_eq!(repeated[0].metadata["consecutive_failures"], "3");
+    }
+
+    #[tokio::test]
+    async fn test_only_server_side_deploy_failures_count_against_slos_which_are_checked_on_demand() {
+        let manager = setup_manager().with_slo_evaluator(SloEvaluator::new(vec![config::SloConfig {
+            name: "deploy_success".to_string(),
+            metric: config::SloMetric::DeploySuccess,
+            target: 0.9,
+            latency_threshold_ms: None,
+        }], 10));
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-no-client", "") }).await.unwrap();
+        let mut events = manager.event_stream_tx.subscribe();
+        let deploy = |target: &str, agent_type: &str| manager.deploy(DeployAgentParams {
+            target_node_id: Some(target.to_string()),
+            name: "Worker".to_string(),
+            agent_type: agent_type.to_string(),
+            pinned: false,
+            idempotency_key: None,
+            env: Default::default(),
+            fleet_id: None,
+            requirement: Default::default(),
+        });
+        assert!(matches!(deploy("missing-node", "Worker").await, Err(FabricError::NodeNotFound(_))));
+        assert!(matches!(deploy("node-no-client", "NoSuchType").await, Err(FabricError::UnknownAgentType(_))));
+        assert_eq!(manager.slo_evaluator().unwrap().evaluate()[0].samples, 0);
+
+        assert!(matches!(deploy("node-no-client", "Worker").await, Err(FabricError::NoProxyClient(_))));
+        let status = &manager.slo_evaluator().unwrap().evaluate()[0];
+        assert_eq!((status.samples, status.met), (1, false));
+
+        // The breach is reported by the SLO check, not by the deploy itself
+        let breached = |events: &mut broadcast::Receiver<FabricEvent>| {
+            std::iter::from_fn(|| events.try_recv().ok()).filter(|event| event.event_type == "SLO_BREACHED").count()
+        };
+        assert_eq!(breached(&mut events), 0);
+        manager.check_slos().await;
+        assert_eq!(breached(&mut events), 1);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_env_reaches_proxy_and_is_stored_redacted() {
+        let proxy = MockProxy::default().with_checkpoints();
+        let deployed = Arc::clone(&proxy.deployed);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-env", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-env-2", &proxy_addr)).await.unwrap();
+        let env = std::collections::HashMap::from([
+            ("API_URL".to_string(), "http://api:8080".to_string()),
+            ("DB_PASSWORD".to_string(), "hunter2".to_string()),
+        ]);
+
+        let agent_id = manager.deploy(DeployAgentParams {
+            target_node_id: Some("node-env".to_string()),
+            name: "Worker".to_string(),
+            agent_type: "Worker".to_string(),
+            pinned: false,
+            idempotency_key: None,
+            env: env.clone(),
+            fleet_id: None,
+            requirement: Default::default(),
+        }).await.unwrap().agent_id;
+
+        let stored = manager.state.read().await.ai_agents[&agent_id].env.clone();
+        assert_eq!(stored["API_URL"], "http://api:8080");
+        assert_eq!(stored["DB_PASSWORD"], nexus_prime_core::config::REDACTED);
+
+        // Migration redeploys with the real values
+        manager.migrate_agent(agent_id, "node-env-2".to_string(), false).await.unwrap();
+        let deployed = deployed.lock().await;
+        assert_eq!(deployed.len(), 2);
+        assert!(deployed.iter().all(|request| request.env == env));
+    }
+
+    #[tokio::test]
+    async fn test_drain_node_streams_progress_per_agent() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-draining", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-spare", &proxy_addr)).await.unwrap();
+        for agent_id in ["agent-d1", "agent-d2", "agent-d3"] {
+            manager.register_ai_agent(running_agent(agent_id, "node-draining")).await.unwrap();
+        }
+
+        let mut updates = manager.execute_command_with_progress(FabricCommand {
+            command_id: "drain-1".to_string(),
+            command_type: "DRAIN_NODE".to_string(),
+            target_id: "node-draining".to_string(),
+            parameters: Default::default(),
+        }).await;
+        let mut received = Vec::new();
+        while let Some(update) = updates.recv().await {
+            received.push(update);
+        }
+
+        let (last, progress) = received.split_last().unwrap();
+        assert_eq!(progress.iter().map(|update| update.percent.round()).collect::<Vec<_>>(), vec![33.0, 67.0, 100.0]);
+        assert_eq!(progress[0].agent_status["agent-d1"], "Migrated to node-spare");
+        assert_eq!(progress[0].agent_status["agent-d3"], "Pending");
+        assert_eq!(progress[2].agent_status["agent-d3"], "Migrated to node-spare");
+        assert!(received.iter().all(|update| update.command_id == "drain-1"));
+        assert_eq!(last.result.as_ref().unwrap().status, "SUCCESS");
+        assert_eq!(manager.get_node("node-draining").await.unwrap().status, "Draining");
+        assert!(manager.agents_on_node("node-draining").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_drained_node_stays_draining_until_undrained() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let db = temp_db();
+        let manager = FabricManager::new(event_bus_tx.clone(), event_stream_tx.clone(), command_tx.clone(), db.clone());
+        let node = ComputeNode { proxy_listen_address: None, ..proxied_node("node-drained", "") };
+        manager.register_node(node.clone()).await.unwrap();
+        let command = |command_type: &str| FabricCommand {
+            command_id: format!("{}-1", command_type),
+            command_type: command_type.to_string(),
+            target_id: "node-drained".to_string(),
+            parameters: Default::default(),
+        };
+        manager.execute_command(command("DRAIN_NODE")).await.unwrap();
+
+        // Reporting Online, registering again and restarting the core all leave it Draining
+        manager.update_node_status("node-drained".to_string(), "Online".to_string(), None).await.unwrap();
+        assert_eq!(manager.get_node("node-drained").await.unwrap().status, "Draining");
+        manager.register_node(node).await.unwrap();
+        assert_eq!(manager.get_node("node-drained").await.unwrap().status, "Draining");
+        let restarted = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db);
+        let restored = restarted.get_node("node-drained").await.unwrap();
+        assert!(restored.draining);
+        assert_eq!(restored.status, "Draining");
+        assert!(matches!(
+            restarted.deploy_agent("node-drained".to_string(), "Worker".to_string(), "Worker".to_string()).await,
+            Err(FabricError::NodeOffline { .. })
+        ));
+
+        restarted.execute_command(command("UNDRAIN_NODE")).await.unwrap();
+        let undrained = restarted.get_node("node-drained").await.unwrap();
+        assert!(!undrained.draining);
+        assert_eq!(undrained.status, "Online");
+        let missing = FabricCommand { target_id: "node-missing".to_string(), ..command("UNDRAIN_NODE") };
+        assert!(restarted.execute_command(missing).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reconnected_node_is_excluded_from_placement_during_probation() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.reconnect_jitter_ms = 0;
+        fabric_config.reconnect_probation_ms = 200;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        manager.register_node(proxied_node("node-blip", &proxy_addr)).await.unwrap();
+        manager.update_node_status("node-blip".to_string(), "Unreachable".to_string(), None).await.unwrap();
+
+        assert!(manager.reconnect_node("node-blip").await);
+        assert_eq!(manager.state.read().await.compute_nodes["node-blip"].status, "Recovering");
+        let result = manager.deploy_agent("node-blip".to_string(), "Worker".to_string(), "Worker".to_string()).await;
+        assert!(matches!(result, Err(FabricError::NodeOffline { .. })));
+        assert!(manager.state.read().await.ai_agents.is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+        assert_eq!(manager.state.read().await.compute_nodes["node-blip"].status, "Online");
+        manager.deploy_agent("node-blip".to_string(), "Worker".to_string(), "Worker".to_string()).await.unwrap();
+        assert_eq!(manager.state.read().await.ai_agents.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pinned_agent_is_not_rebalanced_or_migrated() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-busy", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-idle", &proxy_addr)).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-pinned", "node-busy")).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-free", "node-busy")).await.unwrap();
+        manager.set_agent_pinned("agent-pinned", true).await.unwrap();
+
+        assert_eq!(manager.rebalance_agents().await, 1);
+        {
+            let state = manager.state.read().await;
+            assert_eq!(state.ai_agents["agent-pinned"].assigned_node_id.as_deref(), Some("node-busy"));
+            assert_eq!(state.ai_agents["agent-free"].assigned_node_id.as_deref(), Some("node-idle"));
+        }
+
+        let result = manager.migrate_agent("agent-pinned".to_string(), "node-idle".to_string(), false).await;
+        assert!(matches!(result, Err(FabricError::AgentPinned(_))));
+        manager.migrate_agent("agent-pinned".to_string(), "node-idle".to_string(), true).await.unwrap();
+        let state = manager.state.read().await;
+        assert_eq!(state.ai_agents["agent-pinned"].assigned_node_id.as_deref(), Some("node-idle"));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_rejects_unregistered_agent_type() {
+        let proxy = MockProxy::default().with_checkpoints();
+        let deployed = Arc::clone(&proxy.deployed);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-typed", &proxy_addr)).await.unwrap();
+
+        manager.deploy_agent("node-typed".to_string(), "Worker".to_string(), "Synthesizer".to_string()).await.unwrap();
+        let result = manager.deploy_agent("node-typed".to_string(), "Worker".to_string(), "Synthesiser".to_string()).await;
+        assert!(matches!(result, Err(FabricError::UnknownAgentType(agent_type)) if agent_type == "Synthesiser"));
+
+        let deployed = deployed.lock().await;
+        assert_eq!(deployed.len(), 1);
+        assert_eq!(deployed[0].parameters["memory_mb"], "2048");
+        assert_eq!(manager.state.read().await.ai_agents.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keyed_deploy_retry_returns_existing_agent() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_deploy_delay(std::time::Duration::from_millis(300))).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-retry", &proxy_addr)).await.unwrap();
+        let params = DeployAgentParams {
+            target_node_id: Some("node-retry".to_string()),
+            name: "Worker".to_string(),
+            agent_type: "Worker".to_string(),
+            pinned: false,
+            idempotency_key: Some("deploy-42".to_string()),
+            env: Default::default(),
+            fleet_id: None,
+            requirement: Default::default(),
+        };
+
+        // A retry while the first deploy is still in flight, then one after it completed
+        let (first, in_flight) = tokio::join!(manager.deploy(params.clone()), manager.deploy(params.clone()));
+        let completed = manager.deploy(params).await.unwrap();
+
+        let agent_id = first.unwrap().agent_id;
+        assert_eq!(in_flight.unwrap().agent_id, agent_id);
+        assert_eq!(completed.agent_id, agent_id);
+        assert_eq!(manager.state.read().await.ai_agents.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_command_accepted_then_executed() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, mut command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        manager.register_node(proxied_node("node-cmd", &proxy_addr)).await.unwrap();
+
+        let mut command = FabricCommand {
+            command_id: "cmd-1".to_string(),
+            command_type: "DEPLOY_AGENT".to_string(),
+            target_id: "node-cmd".to_string(),
+            ..Default::default()
+        };
+        command.parameters.insert("name".to_string(), "Worker".to_string());
+        command.parameters.insert("type".to_string(), "Synthesizer".to_string());
+        manager.issue_command(command).await.unwrap();
+        let queued = command_rx.recv().await.unwrap();
+        assert!(manager.execute_command(queued).await.is_ok());
+
+        let mut lifecycle = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type.starts_with("COMMAND_") {
+                assert_eq!(event.metadata["command_id"], "cmd-1");
+                lifecycle.push(event.event_type);
+            }
+        }
+        assert_eq!(lifecycle, vec!["COMMAND_ACCEPTED", "COMMAND_EXECUTED"]);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_fails_when_the_node_goes_offline_while_it_is_in_flight() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_deploy_delay(std::time::Duration::from_millis(300))).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-flaky", &proxy_addr)).await.unwrap();
+
+        let deploying = manager.clone();
+        let deploy = tokio::spawn(async move {
+            deploying.deploy_agent("node-flaky".to_string(), "Worker".to_string(), "Worker".to_string()).await
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        manager.update_node_status("node-flaky".to_string(), "Offline".to_string(), None).await.unwrap();
+
+        let result = deploy.await.unwrap();
+        assert!(matches!(result, Err(FabricError::NodeOffline { status, .. }) if status == "Offline"));
+        let agents = manager.list_agents().await;
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].status, "Failed");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_queued_commands_and_persists_state() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_deploy_delay(std::time::Duration::from_millis(300))).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(32);
+        let (command_tx, mut command_rx) = mpsc::channel(10);
+        let db = temp_db();
+        let manager = FabricManager::new(event_bus_tx.clone(), event_stream_tx.clone(), command_tx.clone(), db.clone());
+        manager.register_node(proxied_node("node-closing", &proxy_addr)).await.unwrap();
+        let processor = manager.clone();
+        tokio::spawn(async move {
+            while let Some(command) = command_rx.recv().await {
+                let _ = processor.execute_command(command).await;
+            }
+        });
+
+        let mut deploy = FabricCommand {
+            command_type: "DEPLOY_AGENT".to_string(),
+            target_id: "node-closing".to_string(),
+            ..Default::default()
+        };
+        deploy.parameters.insert("name".to_string(), "Worker".to_string());
+        deploy.parameters.insert("type".to_string(), "Worker".to_string());
+        manager.issue_command(deploy.clone()).await.unwrap();
+        manager.shutdown().await.unwrap();
+
+        // The deploy still in flight finished before shutdown returned, and later commands are refused
+        assert_eq!(manager.list_agents().await.len(), 1);
+        assert!(matches!(manager.issue_command(deploy).await, Err(FabricError::ShuttingDown)));
+        tokio::time::sleep(std::time::Duration::from_millis(400)).await;
+        assert_eq!(manager.list_agents().await.len(), 1);
+
+        let restarted = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db);
+        let agents = restarted.list_agents().await;
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].assigned_node_id.as_deref(), Some("node-closing"));
+    }
+
+    // A manager with a four-agent Synthesizer fleet on one node; returns the fleet's
+    // agent ids sorted, which is the order the rolling update works through them
+    async fn fleet_manager(proxy: MockProxy) -> (FabricManager, Vec<String>) {
+        let proxy_addr = spawn_mock_proxy(proxy.clone()).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-fleet", &proxy_addr)).await.unwrap();
+        let mut agent_ids = Vec::new();
+        for i in 0..4 {
+            let agent_id = manager.deploy(DeployAgentParams {
+                target_node_id: Some("node-fleet".to_string()),
+                name: format!("worker-{}", i),
+                agent_type: "Synthesizer".to_string(),
+                pinned: false,
+                idempotency_key: None,
+                env: Default::default(),
+                fleet_id: Some("fleet-1".to_string()),
+                requirement: Default::default(),
+            }).await.unwrap().agent_id;
+            agent_ids.push(agent_id);
+        }
+        agent_ids.sort();
+        proxy.log.lock().await.clear();
+        (manager, agent_ids)
+    }
+
+    fn rolling_update_params(batch_size: usize) -> RollingUpdateParams {
+        RollingUpdateParams {
+            fleet_id: "fleet-1".to_string(),
+            agent_type: "Worker".to_string(),
+            batch_size,
+            env: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rolling_update_replaces_fleet_batch_by_batch() {
+        let proxy = MockProxy::default();
+        let log = Arc::clone(&proxy.log);
+        let (manager, agent_ids) = fleet_manager(proxy).await;
+        let mut names = std::collections::HashMap::new();
+        for agent_id in &agent_ids {
+            names.insert(agent_id.clone(), manager.get_agent(agent_id).await.unwrap().name);
+        }
+
+        assert_eq!(manager.rolling_update(rolling_update_params(2)).await.unwrap(), 4);
+
+        // Each batch is deployed and its old agents stopped before the next batch starts
+        let replacements: std::collections::HashMap<String, String> = manager.list_agents().await.into_iter()
+            .filter(|agent| agent.agent_type == "Worker")
+            .map(|agent| (agent.name, agent.id))
+            .collect();
+        let log = log.lock().await.clone();
+        assert_eq!(log.len(), 8);
+        for (batch, old_ids) in log.chunks(4).zip(agent_ids.chunks(2)) {
+            let mut expected: Vec<String> = old_ids.iter()
+                .flat_map(|id| [format!("proxy:deploy:{}", replacements[&names[id]]), format!("proxy:stop:{}", id)])
+                .collect();
+            let mut batch = batch.to_vec();
+            expected.sort();
+            batch.sort();
+            assert_eq!(batch, expected);
+        }
+
+        let fleet: Vec<AIAgent> = manager.list_agents().await.into_iter()
+            .filter(|agent| agent.fleet_id.as_deref() == Some("fleet-1") && agent.status == "Running")
+            .collect();
+        assert_eq!(fleet.len(), 4);
+        assert!(fleet.iter().all(|agent| agent.agent_type == "Worker"));
+    }
+
+    #[tokio::test]
+    async fn test_rolling_update_halts_when_replacements_fail() {
+        let proxy = MockProxy::default();
+        let log = Arc::clone(&proxy.log);
+        let reject = Arc::clone(&proxy.reject);
+        let (manager, agent_ids) = fleet_manager(proxy).await;
+        // Reject the replacement of the second agent in update order
+        *reject.lock().unwrap() = Some(manager.get_agent(&agent_ids[1]).await.unwrap().name);
+
+        let result = manager.rolling_update(rolling_update_params(2)).await;
+        assert!(matches!(result, Err(FabricError::RollingUpdateHalted { failed: 1, attempted: 2, .. })));
+
+        // The second batch was never started and its agents keep running
+        assert_eq!(log.lock().await.iter().filter(|entry| entry.starts_with("proxy:deploy:")).count(), 2);
+        assert_eq!(manager.get_agent(&agent_ids[0]).await.unwrap().status, "Stopped");
+        for agent_id in &agent_ids[1..] {
+            let agent = manager.get_agent(agent_id).await.unwrap();
+            assert_eq!((agent.agent_type.as_str(), agent.status.as_str()), ("Synthesizer", "Running"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rolling_update_reuses_the_old_agents_slot_and_rolls_back_when_it_cannot_stop() {
+        let proxy = MockProxy::default();
+        let reject = Arc::clone(&proxy.reject);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.max_agents_per_node = 1;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        manager.register_node(proxied_node("node-full", &proxy_addr)).await.unwrap();
+        let deploy = |agent_type: &str| manager.deploy(DeployAgentParams {
+            target_node_id: Some("node-full".to_string()),
+            name: "worker".to_string(),
+            agent_type: agent_type.to_string(),
+            pinned: false,
+            idempotency_key: None,
+            env: Default::default(),
+            fleet_id: Some("fleet-1".to_string()),
+            requirement: Default::default(),
+        });
+        let old_id = deploy("Synthesizer").await.unwrap().agent_id;
+        assert!(matches!(deploy("Synthesizer").await, Err(FabricError::NodeFull { .. })));
+
+        // The replacement takes the slot of the agent it replaces on a full node
+        assert_eq!(manager.rolling_update(rolling_update_params(1)).await.unwrap(), 1);
+        assert_eq!(manager.get_agent(&old_id).await.unwrap().status, "Stopped");
+        let running: Vec<AIAgent> = manager.list_agents().await.into_iter()
+            .filter(|agent| agent.status == "Running")
+            .collect();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].agent_type, "Worker");
+
+        // When the old agent will not stop, its replacement is stopped instead
+        let current_id = running[0].id.clone();
+        *reject.lock().unwrap() = Some(current_id.clone());
+        let mut params = rolling_update_params(1);
+        params.agent_type = "Synthesizer".to_string();
+        let result = manager.rolling_update(params).await;
+        assert!(matches!(result, Err(FabricError::RollingUpdateHalted { failed: 1, attempted: 1, .. })));
+        let active: Vec<AIAgent> = manager.list_agents().await.into_iter()
+            .filter(|agent| agent.status != "Stopped" && agent.status != "Failed")
+            .collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].id, current_id);
+    }
+
+    #[tokio::test]
+    async fn test_deploy_failures_are_reported() {
+        let manager = setup_manager();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-no-client", "") }).await.unwrap();
+        manager.register_node(ComputeNode {
+            status: "Offline".to_string(),
+            proxy_listen_address: None,
+            ..proxied_node("node-offline", "")
+        }).await.unwrap();
+        let deploy = |node_id: &str| manager.deploy_agent(node_id.to_string(), "Worker".to_string(), "Worker".to_string());
+
+        assert!(matches!(deploy("node-missing").await, Err(FabricError::NodeNotFound(_))));
+        assert!(matches!(deploy("node-offline").await, Err(FabricError::NodeOffline { status, .. }) if status == "Offline"));
+        assert!(matches!(deploy("node-no-client").await, Err(FabricError::NoProxyClient(_))));
+        assert!(manager.list_agents().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stop_reports_outcome_and_failures() {
+        let proxy = MockProxy::default();
+        let reject = Arc::clone(&proxy.reject);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-stop", &proxy_addr)).await.unwrap();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-no-client", "") }).await.unwrap();
+        let stopped = manager.deploy_agent("node-stop".to_string(), "Worker".to_string(), "Worker".to_string()).await.unwrap().agent_id;
+        let rejected = manager.deploy_agent("node-stop".to_string(), "Worker".to_string(), "Worker".to_string()).await.unwrap().agent_id;
+        manager.register_ai_agent(running_agent("agent-orphan", "node-no-client")).await.unwrap();
+        *reject.lock().unwrap() = Some(rejected.clone());
+
+        let outcome = manager.stop_agent(stopped.clone()).await.unwrap();
+        assert_eq!((outcome.agent_id, outcome.status.as_str(), outcome.message.as_str()), (stopped, "Stopped", "stopped"));
+        assert!(matches!(manager.stop_agent(rejected.clone()).await, Err(FabricError::ProxyError { .. })));
+        assert_eq!(manager.get_agent(&rejected).await.unwrap().status, "Error");
+        assert!(matches!(manager.stop_agent("agent-missing".to_string()).await, Err(FabricError::AgentNotFound(_))));
+        assert!(matches!(manager.stop_agent("agent-orphan".to_string()).await, Err(FabricError::NoProxyClient(_))));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_from_warm_pool_is_faster_and_consumes_pooled_agent() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_deploy_delay(std::time::Duration::from_millis(300))).await;
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.warm_pool_sizes.insert("Synthesizer".to_string(), 1);
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        manager.register_node(proxied_node("node-warm", &proxy_addr)).await.unwrap();
+        manager.replenish_warm_pool("Synthesizer").await;
+
+        // Registering the node already started filling the pool in the background, in
+        // which case the call above returns while that pooled agent is still deploying
+        let pooled_id = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+            loop {
+                {
+                    let state = manager.state.read().await;
+                    let pooled: Vec<_> = state.ai_agents.values().filter(|a| a.status == "Pooled").collect();
+                    if let [agent] = pooled.as_slice() {
+                        return agent.id.clone();
+                    }
+                    assert!(pooled.is_empty(), "{} pooled agents", pooled.len());
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        }).await.expect("warm pool was not filled");
+
+        let started = std::time::Instant::now();
+        manager.deploy_agent("node-warm".to_string(), "Pooled".to_string(), "Synthesizer".to_string()).await.unwrap();
+        let pooled_deploy = started.elapsed();
+
+        let started = std::time::Instant::now();
+        manager.deploy_agent("node-warm".to_string(), "Cold".to_string(), "Worker".to_string()).await.unwrap();
+        let cold_deploy = started.elapsed();
+
+        assert!(pooled_deploy < std::time::Duration::from_millis(100), "pooled deploy took {:?}", pooled_deploy);
+        assert!(cold_deploy >= std::time::Duration::from_millis(300), "cold deploy took {:?}", cold_deploy);
+        let state = manager.state.read().await;
+        assert_eq!(state.ai_agents[&pooled_id].name, "Pooled");
+        assert_eq!(state.ai_agents[&pooled_id].status, "Running");
+    }
+
+    #[tokio::test]
+    async fn test_failed_deploy_sets_node_last_error() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_deploy_error("disk full")).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        manager.register_node(proxied_node("node-flaky", &proxy_addr)).await.unwrap();
+
+        let result = manager.deploy_agent("node-flaky".to_string(), "Worker".to_string(), "Worker".to_string()).await;
+        assert!(matches!(result, Err(FabricError::ProxyError { .. })));
+
+        let node = manager.list_nodes().await.into_iter().find(|n| n.id == "node-flaky").unwrap();
+        assert!(node.last_error.as_deref().unwrap().contains("disk full"));
+        assert!(node.last_error_at.is_some());
+        let mut error_event = None;
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type == "NODE_ERROR" {
+                error_event = Some(event);
+            }
+        }
+        assert!(error_event.unwrap().metadata["last_error"].contains("disk full"));
+    }
+
+    #[tokio::test]
+    async fn test_update_node_metadata_makes_node_findable_by_label() {
+        let manager = setup_manager();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-gpu", "") }).await.unwrap();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-cpu", "") }).await.unwrap();
+        assert!(manager.list_nodes_by_label("gpu", "true").await.is_empty());
+
+        let service = FabricServiceServerImpl::new(manager.clone(), broadcast::channel(10).0);
+        let mut request = UpdateNodeMetadataRequest { node_id: "node-gpu".to_string(), ..Default::default() };
+        request.labels.insert("gpu".to_string(), "true".to_string());
+        service.update_node_metadata(tonic::Request::new(request)).await.unwrap();
+
+        let nodes = manager.list_nodes_by_label("gpu", "true").await;
+        assert_eq!(nodes.iter().map(|n| n.id.as_str()).collect::<Vec<_>>(), vec!["node-gpu"]);
+
+        let missing = UpdateNodeMetadataRequest { node_id: "node-missing".to_string(), ..Default::default() };
+        let status = service.update_node_metadata(tonic::Request::new(missing)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_node_whose_proxy_never_answers_is_registered_unreachable() {
+        let dead_addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.connect_max_attempts = 3;
+        fabric_config.connect_base_delay_ms = 50;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+
+        let started = std::time::Instant::now();
+        manager.register_node(proxied_node("node-dead", &dead_addr)).await.unwrap();
+        // Two retries, after 50ms and 100ms
+        assert!(started.elapsed() >= std::time::Duration::from_millis(150));
+
+        let node = manager.get_node("node-dead").await.unwrap();
+        assert_eq!(node.status, "Unreachable");
+        assert!(node.last_error.unwrap().contains("after 3 attempts"));
+        assert!(manager.connected_node_ids().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bulk_registration_connects_to_proxies_concurrently() {
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.connect_max_attempts = 3;
+        fabric_config.connect_base_delay_ms = 100;
+        fabric_config.max_concurrent_reconnects = 4;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        let nodes: Vec<ComputeNode> = (0..4).map(|i| {
+            let dead_addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+            proxied_node(&format!("node-dead-{}", i), &dead_addr)
+        }).collect();
+
+        let started = std::time::Instant::now();
+        manager.register_nodes(nodes).await.unwrap();
+        // Each node gives up after 300ms of retries; one at a time would take 1.2s
+        assert!(started.elapsed() < std::time::Duration::from_millis(900), "{:?}", started.elapsed());
+        for i in 0..4 {
+            assert_eq!(manager.get_node(&format!("node-dead-{}", i)).await.unwrap().status, "Unreachable");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restart_reconnects_to_restored_nodes() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let dead_addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().to_string();
+        let db = temp_db();
+        let new_manager = || {
+            let (event_bus_tx, _) = broadcast::channel(10);
+            let (event_stream_tx, _) = broadcast::channel(10);
+            let (command_tx, _) = mpsc::channel(10);
+            FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db.clone())
+        };
+        let manager = new_manager();
+        manager.register_node(proxied_node("node-live", &proxy_addr)).await.unwrap();
+        manager.register_node(proxied_node("node-dead", &dead_addr)).await.unwrap();
+        drop(manager);
+
+        let restarted = new_manager();
+        assert!(restarted.connected_node_ids().await.is_empty());
+        restarted.reconnect_restored_nodes().await.unwrap();
+
+        assert_eq!(restarted.connected_node_ids().await, vec!["node-live".to_string()]);
+        assert_eq!(restarted.get_node("node-dead").await.unwrap().status, "Unreachable");
+    }
+
+    #[tokio::test]
+    async fn test_command_history_lists_issued_commands_with_outcomes() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let db = temp_db();
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(32);
+        let (command_tx, mut command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx.clone(), command_tx, db.clone())
+            .with_command_history(CommandHistory::open(&db, 100).unwrap());
+        manager.register_node(proxied_node("node-history", &proxy_addr)).await.unwrap();
+
+        let mut deploy = FabricCommand {
+            command_id: "cmd-deploy".to_string(),
+            command_type: "DEPLOY_AGENT".to_string(),
+            target_id: "node-history".to_string(),
+            ..Default::default()
+        };
+        deploy.parameters.insert("name".to_string(), "Worker".to_string());
+        deploy.parameters.insert("type".to_string(), "Synthesizer".to_string());
+        let bogus = FabricCommand {
+            command_id: "cmd-bogus".to_string(),
+            command_type: "REBOOT_NODE".to_string(),
+            target_id: "node-history".to_string(),
+            ..Default::default()
+        };
+        for command in [deploy, bogus] {
+            manager.issue_command(command).await.unwrap();
+            let queued = command_rx.recv().await.unwrap();
+            let _ = manager.execute_command(queued).await;
+        }
+
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx);
+        let request = CommandHistoryRequest { target_id: "node-history".to_string(), ..Default::default() };
+        let entries = service.list_command_history(tonic::Request::new(request)).await.unwrap().into_inner().entries;
+
+        let outcomes: Vec<_> = entries.iter().map(|e| (e.command_id.as_str(), e.status.as_str())).collect();
+        assert_eq!(outcomes, vec![("cmd-bogus", "FAILED"), ("cmd-deploy", "SUCCEEDED")]);
+        assert!(entries[0].error.contains("REBOOT_NODE"));
+        assert_eq!(entries[1].parameters["name"], "Worker");
+
+        let other_target = CommandHistoryRequest { target_id: "node-other".to_string(), ..Default::default() };
+        assert!(service.list_command_history(tonic::Request::new(other_target)).await.unwrap().into_inner().entries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_register_agent_prefers_structured_capabilities() {
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), broadcast::channel(10).0);
+
+        let structured = AgentRegistrationRequest {
+            ip_address: "10.0.0.1".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            cpu_cores: 16,
+            memory_bytes: 64 << 30,
+            gpus: 2,
+            features: vec!["avx512".to_string()],
+            ..Default::default()
+        };
+        let legacy = AgentRegistrationRequest {
+            ip_address: "10.0.0.2".to_string(),
+            capabilities: "CPU:4,RAM:16GB,sse4".to_string(),
+            ..Default::default()
+        };
+        let structured_id = service.register_agent(tonic::Request::new(structured)).await.unwrap().into_inner().node_id;
+        let legacy_id = service.register_agent(tonic::Request::new(legacy)).await.unwrap().into_inner().node_id;
+
+        let structured_node = manager.get_node(&structured_id).await.unwrap();
+        assert_eq!(structured_node.resources, NodeCapabilities {
+            cpu_cores: 16,
+            memory_bytes: 64 << 30,
+            gpus: 2,
+            features: vec!["avx512".to_string()],
+        });
+        assert_eq!(structured_node.capabilities, "CPU:4,RAM:16GB");
+        assert_eq!(manager.get_node(&legacy_id).await.unwrap().resources, NodeCapabilities {
+            cpu_cores: 4,
+            memory_bytes: 16 << 30,
+            gpus: 0,
+            features: vec!["sse4".to_string()],
+        });
+    }
+
+    #[tokio::test]
+    async fn test_register_agent_rejects_oversized_capabilities() {
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), broadcast::channel(10).0);
+        let max_length = NexusConfig::default().fabric.max_field_length as usize;
+
+        let request = AgentRegistrationRequest {
+            ip_address: "127.0.0.1".to_string(),
+            capabilities: "C".repeat(max_length + 1),
+            ..Default::default()
+        };
+        let status = service.register_agent(tonic::Request::new(request)).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert!(status.message().contains("capabilities"));
+        assert!(manager.list_nodes().await.is_empty());
+
+        let request = AgentRegistrationRequest {
+            ip_address: "127.0.0.1".to_string(),
+            capabilities: "C".repeat(max_length),
+            ..Default::default()
+        };
+        assert!(service.register_agent(tonic::Request::new(request)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_register_nodes_streams_bulk_registration() {
+        use nexus_prime_core::fabric_proto::fabric::fabric_service_client::FabricServiceClient;
+        use nexus_prime_core::fabric_proto::fabric::fabric_service_server::FabricServiceServer;
+
+        let manager = setup_manager();
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx);
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        tokio::spawn(tonic::transport::Server::builder()
+            .add_service(FabricServiceServer::new(service))
+            .serve(addr));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+        let mut client = FabricServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+        let requests = (1..=3).map(|i| AgentRegistrationRequest {
+            ip_address: format!("10.0.0.{}", i),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            agent_type: 1,
+            proxy_listen_address: String::new(),
+            ..Default::default()
+        });
+        let response = client.register_nodes(tokio_stream::iter(requests)).await.unwrap().into_inner();
+
+        assert_eq!(response.node_ids.len(), 3);
+        let state = manager.state.read().await;
+        for node_id in &response.node_ids {
+            assert!(state.compute_nodes.contains_key(node_id));
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryStore {
+        state: std::sync::Mutex<Option<Vec<u8>>>,
+    }
+
+    #[tonic::async_trait]
+    impl FabricStateStore for MemoryStore {
+        fn load_state(&self) -> FabricResult<Option<FabricState>> {
+            Ok(self.state.lock().unwrap().as_ref().map(|bytes| bincode::deserialize(bytes).unwrap()))
+        }
+
+        async fn save_state(&self, state: &FabricState) -> FabricResult<()> {
+            *self.state.lock().unwrap() = Some(bincode::serialize(state)?);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_nodes_and_agents_sorted_by_id_across_backends() {
+        let (event_bus_tx, _) = broadcast::channel(32);
+        let (event_stream_tx, _) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let db = temp_db();
+        let backends = vec![
+            FabricManager::new(event_bus_tx.clone(), event_stream_tx.clone(), command_tx.clone(), db.clone()),
+            FabricManager::with_store(event_bus_tx.clone(), event_stream_tx.clone(), command_tx.clone(), Arc::new(MemoryStore::default())),
+        ];
+
+        for manager in &backends {
+            for id in ["node-c", "node-a", "node-b"] {
+                manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node(id, "") }).await.unwrap();
+                manager.register_ai_agent(AIAgent {
+                    id: id.replace("node", "agent"),
+                    name: "Worker".to_string(),
+                    agent_type: "Synthesizer".to_string(),
+                    assigned_node_id: Some(id.to_string()),
+                    status: "Running".to_string(),
+                    current_task: None,
+                    task_progress: None,
+                    pinned: false,
+                    env: Default::default(),
+                    last_active: Utc::now(),
+                    fleet_id: None,
+                    orphaned_at: None,
+                }).await.unwrap();
+            }
+        }
+        // Reloading from sled must not change the order either
+        let reloaded = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db);
+
+        for manager in backends.iter().chain(std::iter::once(&reloaded)) {
+            let node_ids: Vec<String> = manager.list_nodes().await.into_iter().map(|n| n.id).collect();
+            assert_eq!(node_ids, vec!["node-a", "node-b", "node-c"]);
+            let agent_ids: Vec<String> = manager.list_agents().await.into_iter().map(|a| a.id).collect();
+            assert_eq!(agent_ids, vec!["agent-a", "agent-b", "agent-c"]);
+            let again: Vec<String> = manager.list_nodes().await.into_iter().map(|n| n.id).collect();
+            assert_eq!(node_ids, again);
+        }
+    }
+
+    struct NullTelemetryStorage;
+
+    #[tonic::async_trait]
+    impl TelemetryStorage for NullTelemetryStorage {
+        async fn store_telemetry(&self, _telemetry: &storage::TelemetryRecord) -> storage::StorageResult<()> {
+            Ok(())
+        }
+
+        async fn get_latest_telemetry(&self, _entity_id: &str) -> storage::StorageResult<Option<storage::TelemetryRecord>> {
+            Ok(None)
+        }
+
+        async fn get_telemetry_history(&self, _entity_id: &str, _hours: u32) -> storage::StorageResult<Vec<storage::TelemetryRecord>> {
+            Ok(Vec::new())
+        }
+
+        async fn cleanup_old_telemetry(&self, _retention: &config::RetentionPolicy) -> storage::StorageResult<u64> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_node_status_telemetry_is_stored_and_summarized() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        // The store main attaches, on the in-memory backend
+        let mut database = NexusConfig::default().database;
+        database.backend = config::StorageBackend::Memory;
+        let telemetry_store = storage::open_storage(database.clone()).await.unwrap();
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
+            .with_telemetry_store(telemetry_store.clone());
+        manager.register_node(stale_node("node-metered", None)).await.unwrap();
+        while event_rx.try_recv().is_ok() {}
+
+        let telemetry = TelemetryData { cpu_utilization: 0.42, memory_utilization: 0.61, ..Default::default() };
+        manager.update_node_status("node-metered".to_string(), "Busy".to_string(), Some(telemetry)).await.unwrap();
+
+        let record = telemetry_store.get_latest_telemetry("node-metered").await.unwrap().unwrap();
+        assert_eq!(record.entity_type, "node");
+        assert_eq!(record.cpu_utilization, Some(0.42));
+        assert_eq!(record.memory_utilization, Some(0.61));
+        assert_eq!(record.timestamp, manager.get_node("node-metered").await.unwrap().last_seen);
+        let event = event_rx.try_recv().unwrap();
+        assert_eq!(event.metadata["telemetry_summary"], "cpu 42%, mem 61%");
+
+        // With storage turned off in config the sample is only summarized
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.store_node_telemetry = false;
+        let unstored = storage::open_storage(database).await.unwrap();
+        let manager = setup_manager().with_fabric_config(fabric_config).with_telemetry_store(unstored.clone());
+        manager.register_node(stale_node("node-metered", None)).await.unwrap();
+        manager.update_node_status("node-metered".to_string(), "Busy".to_string(), Some(TelemetryData::default())).await.unwrap();
+        assert!(unstored.get_latest_telemetry("node-metered").await.unwrap().is_none());
+    }
+
+    async fn telemetry_service() -> FabricServiceServerImpl {
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        let telemetry = TelemetryManager::new(config, Arc::new(NullTelemetryStorage)).await.unwrap();
+        let (event_stream_tx, _) = broadcast::channel(10);
+        FabricServiceServerImpl::new(setup_manager(), event_stream_tx).with_telemetry(Arc::new(telemetry))
+    }
+
+    #[tokio::test]
+    async fn test_collect_telemetry_now_returns_fresh_snapshot() {
+        let service = telemetry_service().await;
+        let before = Utc::now();
+
+        let snapshot = service.collect_telemetry_now(tonic::Request::new(())).await.unwrap().into_inner();
+        let system = snapshot.system.expect("system metrics");
+        let fabric = snapshot.fabric.expect("fabric metrics");
+        assert!(chrono::DateTime::parse_from_rfc3339(&system.timestamp).unwrap() >= before);
+        assert!(chrono::DateTime::parse_from_rfc3339(&fabric.timestamp).unwrap() >= before);
+    }
+
+    #[tokio::test]
+    async fn test_collect_telemetry_now_requires_permission() {
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let token = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        let service = telemetry_service().await.with_security(security);
+
+        let status = service.collect_telemetry_now(tonic::Request::new(())).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let mut request = tonic::Request::new(());
+        request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        let status = service.collect_telemetry_now(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_effective_config_applies_env_overrides_and_redacts_secrets() {
+        std::env::set_var("NEXUS_FABRIC__MAX_AGENTS_PER_NODE", "17");
+        let config = NexusConfig::from_env().unwrap();
+        std::env::remove_var("NEXUS_FABRIC__MAX_AGENTS_PER_NODE");
+        let security = SecurityManager::new(config.security.clone());
+        let token = security.generate_token("operator".to_string(), EntityType::User, vec![Permission::SystemControl]).await.unwrap();
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let service = FabricServiceServerImpl::new(setup_manager(), event_stream_tx)
+            .with_security(security)
+            .with_config(config);
+
+        let mut request = tonic::Request::new(());
+        request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        let response = service.get_effective_config(request).await.unwrap().into_inner();
+
+        let effective: NexusConfig = serde_json::from_str(&response.config_json).unwrap();
+        assert_eq!(effective.fabric.max_agents_per_node, 17);
+        assert_eq!(effective.security.auth_token_secret, "[REDACTED]");
+        assert!(!response.config_json.contains("CHANGEME_IN_PRODUCTION"));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_reports_port_conflict_without_applying() {
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let token = security.generate_token("operator".to_string(), EntityType::User, vec![Permission::SystemControl]).await.unwrap();
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let manager = setup_manager();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-a", "") }).await.unwrap();
+        manager.register_node(ComputeNode { proxy_listen_address: None, ..proxied_node("node-b", "") }).await.unwrap();
+        let service = FabricServiceServerImpl::new(manager, event_stream_tx)
+            .with_security(security)
+            .with_config(NexusConfig::default());
+        let mut candidate = NexusConfig::default();
+        candidate.security.auth_token_secret = "validate-config-test-secret-0123456789".to_string();
+        candidate.server.metrics_port = candidate.server.grpc_port;
+        candidate.fabric.max_nodes = 1;
+
+        let mut request = tonic::Request::new(ValidateConfigRequest { config_json: serde_json::to_string(&candidate).unwrap() });
+        request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        let response = service.validate_config(request).await.unwrap().into_inner();
+
+        assert!(!response.valid);
+        assert_eq!(response.errors, vec!["server.grpc_port and server.metrics_port are both set to port 50053".to_string()]);
+        assert_eq!(response.warnings, vec!["fabric.max_nodes is 1 but 2 nodes are registered".to_string()]);
+        assert_eq!(service.config.as_ref().unwrap().server.metrics_port, 9090);
+    }
+
+    #[test]
+    fn test_validate_refuses_a_guessable_token_secret_while_auth_is_enforced() {
+        let mut config = NexusConfig::default();
+        assert_eq!(config.validate(), vec!["security.auth_token_secret must be changed from its default while enforce_auth is on".to_string()]);
+        config.security.auth_token_secret = "too-short".to_string();
+        assert_eq!(config.validate(), vec!["security.auth_token_secret must be at least 32 bytes while enforce_auth is on".to_string()]);
+        config.security.auth_token_secret = "a-secret-of-exactly-32-bytes-ok!".to_string();
+        assert!(config.validate().is_empty());
+
+        // Local development without auth may keep the shipped secret
+        let mut config = NexusConfig::default();
+        config.security.enforce_auth = false;
+        assert!(config.validate().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deregistered_node_is_gone_immediately() {
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx.clone(), command_tx, temp_db());
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx);
+
+        let node_id = service.register_agent(tonic::Request::new(AgentRegistrationRequest {
+            ip_address: "10.0.0.9".to_string(),
+            capabilities: "CPU:2".to_string(),
+            ..Default::default()
+        })).await.unwrap().into_inner().node_id;
+        service.deregister_node(tonic::Request::new(DeregisterNodeRequest { node_id: node_id.clone() })).await.unwrap();
+
+        assert!(manager.get_node(&node_id).await.is_none());
+        assert!(std::iter::from_fn(|| event_rx.try_recv().ok()).any(|event| event.event_type == "NODE_PRUNED"));
+        let status = service.deregister_node(tonic::Request::new(DeregisterNodeRequest { node_id })).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_agent_id_reported_from_second_node_is_a_collision() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db());
+        manager.register_ai_agent(running_agent("agent-dup", "node-first")).await.unwrap();
+
+        let result = manager.register_ai_agent(running_agent("agent-dup", "node-second")).await;
+
+        assert!(matches!(result, Err(FabricError::AgentIdCollision { ref node_id, .. }) if node_id == "node-first"));
+        assert_eq!(manager.get_agent("agent-dup").await.unwrap().assigned_node_id.as_deref(), Some("node-first"));
+        let collision = std::iter::from_fn(|| event_rx.try_recv().ok())
+            .find(|event| event.event_type == "AGENT_ID_COLLISION")
+            .expect("collision event");
+        assert_eq!(collision.metadata["rejected_node_id"], "node-second");
+    }
+
+    #[tokio::test]
+    async fn test_registration_beyond_max_nodes_is_rejected() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.max_nodes = 1;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx.clone(), command_tx, temp_db())
+            .with_fabric_config(fabric_config);
+        let service = FabricServiceServerImpl::new(manager.clone(), event_stream_tx);
+        let request = |ip_address: &str| tonic::Request::new(AgentRegistrationRequest {
+            ip_address: ip_address.to_string(),
+            capabilities: "CPU:4".to_string(),
+            ..Default::default()
+        });
+
+        let first = service.register_agent(request("10.0.0.1")).await.unwrap().into_inner();
+        let status = service.register_agent(request("10.0.0.2")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        // A known node registering again is not a new node
+        let again = service.register_agent(request("10.0.0.1")).await.unwrap().into_inner();
+        assert_eq!(again.node_id, first.node_id);
+        assert_eq!(manager.list_nodes().await.len(), 1);
+
+        let mut rejected = None;
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type == "REGISTRATION_REJECTED" {
+                rejected = Some(event);
+            }
+        }
+        assert_eq!(rejected.expect("rejection event").metadata["ip_address"], "10.0.0.2");
+    }
+
+    #[tokio::test]
+    async fn test_only_the_registrant_or_the_node_can_take_over_a_node_id() {
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let rack_a = security.generate_token_for_role("rack-a".to_string(), EntityType::Node, Role::NodeAgent).await.unwrap();
+        let rack_b = security.generate_token_for_role("rack-b".to_string(), EntityType::Node, Role::NodeAgent).await.unwrap();
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), manager.event_stream_tx.clone()).with_security(security.clone());
+        let request = |token: &str| {
+            let mut request = tonic::Request::new(AgentRegistrationRequest {
+                ip_address: "10.0.0.1".to_string(),
+                capabilities: "CPU:4".to_string(),
+                ..Default::default()
+            });
+            request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+            request
+        };
+
+        let first = service.register_agent(request(&rack_a)).await.unwrap().into_inner().node_id;
+        assert_eq!(manager.get_node(&first).await.unwrap().registered_by.as_deref(), Some("rack-a"));
+        assert_eq!(service.register_agent(request(&rack_a)).await.unwrap().into_inner().node_id, first);
+
+        // Another entity at the same address gets a node of its own
+        let other = service.register_agent(request(&rack_b)).await.unwrap().into_inner().node_id;
+        assert_ne!(other, first);
+        assert_eq!(manager.get_node(&first).await.unwrap().registered_by.as_deref(), Some("rack-a"));
+
+        // A token issued for the node itself also counts
+        let own = security.generate_token_for_role(first.clone(), EntityType::Node, Role::NodeAgent).await.unwrap();
+        assert_eq!(service.register_agent(request(&own)).await.unwrap().into_inner().node_id, first);
+    }
+
+    #[tokio::test]
+    async fn test_idle_stopped_agents_are_pruned_and_active_agents_kept() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.agent_timeout_seconds = 60;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db())
+            .with_fabric_config(fabric_config);
+        manager.register_ai_agent(AIAgent {
+            last_active: Utc::now() - chrono::Duration::minutes(5),
+            status: "Stopped".to_string(),
+            ..running_agent("agent-idle", "node-1")
+        }).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-active", "node-1")).await.unwrap();
+        // Quiet but still running, or idle in a warm pool by design
+        manager.register_ai_agent(AIAgent {
+            last_active: Utc::now() - chrono::Duration::minutes(5),
+            ..running_agent("agent-quiet", "node-1")
+        }).await.unwrap();
+        manager.register_ai_agent(AIAgent {
+            last_active: Utc::now() - chrono::Duration::minutes(5),
+            status: "Pooled".to_string(),
+            ..running_agent("agent-pooled", "node-1")
+        }).await.unwrap();
+
+        manager.prune_stale_entities().await;
+
+        let state = manager.state.read().await;
+        assert!(!state.ai_agents.contains_key("agent-idle"));
+        assert!(state.ai_agents.contains_key("agent-active"));
+        assert!(state.ai_agents.contains_key("agent-quiet"));
+        assert!(state.ai_agents.contains_key("agent-pooled"));
+        drop(state);
+        let mut pruned = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type == "AGENT_PRUNED" {
+                pruned.push(event.metadata["agent_id"].clone());
+            }
+        }
+        assert_eq!(pruned, vec!["agent-idle".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_status_update_refreshes_last_active() {
+        let manager = setup_manager();
+        manager.register_ai_agent(AIAgent {
+            last_active: Utc::now() - chrono::Duration::hours(1),
+            fleet_id: None,
+            ..running_agent("agent-reporting", "node-1")
+        }).await.unwrap();
+
+        manager.update_ai_agent_status("agent-reporting".to_string(), "Running".to_string(), None, None).await.unwrap();
+        manager.prune_stale_entities().await;
+
+        assert!(manager.state.read().await.ai_agents.contains_key("agent-reporting"));
+    }
+
+    #[tokio::test]
+    async fn test_get_agents_by_node_returns_only_that_nodes_agents() {
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), manager.event_stream_tx.clone());
+        for node_id in ["node-a", "node-b"] {
+            manager.register_node(stale_node(node_id, None)).await.unwrap();
+        }
+        for (agent_id, node_id) in [("agent-2", "node-a"), ("agent-1", "node-a"), ("agent-3", "node-b")] {
+            manager.register_ai_agent(running_agent(agent_id, node_id)).await.unwrap();
+        }
+
+        let request = tonic::Request::new(GetAgentsByNodeRequest { node_id: "node-a".to_string() });
+        let agents = service.get_agents_by_node(request).await.unwrap().into_inner().agents;
+        let ids: Vec<_> = agents.iter().map(|agent| agent.agent_id.as_str()).collect();
+        assert_eq!(ids, vec!["agent-1", "agent-2"]);
+        assert!(agents.iter().all(|agent| agent.status == "Running"));
+
+        let request = tonic::Request::new(GetAgentsByNodeRequest { node_id: "node-missing".to_string() });
+        let status = service.get_agents_by_node(request).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_list_nodes_and_agents_over_grpc() {
+        use nexus_prime_core::fabric_proto::fabric::fabric_service_client::FabricServiceClient;
+        use nexus_prime_core::fabric_proto::fabric::fabric_service_server::FabricServiceServer;
+
+        let manager = setup_manager();
+        let service = FabricServiceServerImpl::new(manager.clone(), manager.event_stream_tx.clone());
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        tokio::spawn(tonic::transport::Server::builder()
+            .add_service(FabricServiceServer::new(service))
+            .serve(addr));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let mut client = FabricServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+
+        let mut node_ids = Vec::new();
+        for i in 1..=3 {
+            let request = AgentRegistrationRequest {
+                ip_address: format!("10.0.0.{}", i),
+                capabilities: "CPU:4,RAM:16GB".to_string(),
+                agent_type: 1,
+                proxy_listen_address: String::new(),
+                ..Default::default()
+            };
+            node_ids.push(client.register_agent(request).await.unwrap().into_inner().node_id);
+        }
+        node_ids.sort();
+        manager.update_node_status(node_ids[0].clone(), "Offline".to_string(), None).await.unwrap();
+        manager.register_ai_agent(running_agent("agent-1", &node_ids[1])).await.unwrap();
+        manager.register_ai_agent(AIAgent { status: "Stopped".to_string(), ..running_agent("agent-2", &node_ids[2]) }).await.unwrap();
+
+        let nodes = client.list_nodes(ListNodesRequest::default()).await.unwrap().into_inner().nodes;
+        assert_eq!(nodes.iter().map(|node| node.node_id.clone()).collect::<Vec<_>>(), node_ids);
+        assert!(nodes.iter().all(|node| node.capabilities == "CPU:4,RAM:16GB" && node.ip_address.starts_with("10.0.0.")));
+        assert_eq!(nodes[0].status, "Offline");
+        let online = client.list_nodes(ListNodesRequest { status: "Online".to_string() }).await.unwrap().into_inner().nodes;
+        assert_eq!(online.iter().map(|node| node.node_id.clone()).collect::<Vec<_>>(), node_ids[1..]);
+
+        let agents = client.list_agents(ListAgentsRequest::default()).await.unwrap().into_inner().agents;
+        assert_eq!(agents.len(), 2);
+        let running = client.list_agents(ListAgentsRequest { status: "Running".to_string() }).await.unwrap().into_inner().agents;
+        assert_eq!(running.len(), 1);
+        assert_eq!((running[0].agent_id.as_str(), running[0].node_id.as_str()), ("agent-1", node_ids[1].as_str()));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_node_reads_and_agent_writes_do_not_deadlock() {
+        let manager = setup_manager();
+        for i in 0..10 {
+            manager.register_node(stale_node(&format!("node-{}", i), None)).await.unwrap();
+        }
+
+        // Readers share the state lock instead of queueing behind each other. Writers
+        // still take it exclusively; this checks progress, not throughput.
+        let held = manager.state.read().await;
+        let nodes = tokio::time::timeout(std::time::Duration::from_secs(1), manager.list_nodes()).await
+            .expect("a read should not wait for another reader");
+        assert_eq!(nodes.len(), 10);
+        drop(held);
+
+        let tasks: Vec<_> = (0..50).map(|i| {
+            let manager = manager.clone();
+            tokio::spawn(async move {
+                if i % 2 == 0 {
+                    assert_eq!(manager.list_nodes().await.len(), 10);
+                } else {
+                    manager.register_ai_agent(running_agent(&format!("agent-{}", i), "node-0")).await.unwrap();
+                }
+            })
+        }).collect();
+        tokio::time::timeout(std::time::Duration::from_secs(10), async {
+            for task in tasks {
+                task.await.unwrap();
+            }
+        }).await.expect("concurrent reads and writes should not deadlock");
+        assert_eq!(manager.list_agents().await.len(), 25);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_adopts_node_reported_agent_states() {
+        let proxy = MockProxy::default().with_agent_reports();
+        let node_agents = Arc::clone(&proxy.agents);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.reconcile_grace_secs = 0;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db()).with_fabric_config(fabric_config);
+        manager.register_node(proxied_node("node-drift", &proxy_addr)).await.unwrap();
+        let mut agent_ids = Vec::new();
+        for _ in 0..3 {
+            agent_ids.push(manager.deploy_agent("node-drift".to_string(), "Worker".to_string(), "Worker".to_string()).await.unwrap().agent_id);
+        }
+        let (lost, exited, healthy) = (&agent_ids[0], &agent_ids[1], &agent_ids[2]);
+
+        // The node loses one agent and sees another exit behind the core's back
+        node_agents.lock().unwrap().remove(lost);
+        node_agents.lock().unwrap().insert(exited.clone(), "Stopped".to_string());
+        while event_rx.try_recv().is_ok() {}
+
+        assert_eq!(manager.reconcile_agents().await, 2);
+        assert_eq!(manager.get_agent(lost).await.unwrap().status, "Failed");
+        assert_eq!(manager.get_agent(exited).await.unwrap().status, "Stopped");
+        assert_eq!(manager.get_agent(healthy).await.unwrap().status, "Running");
+        let mut reconciled = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type == "AGENT_RECONCILED" {
+                reconciled.push((event.metadata["agent_id"].clone(), event.metadata["status"].clone()));
+            }
+        }
+        reconciled.sort();
+        let mut expected = vec![(lost.clone(), "Failed".to_string()), (exited.clone(), "Stopped".to_string())];
+        expected.sort();
+        assert_eq!(reconciled, expected);
+
+        // Once the core agrees with the node there is nothing left to correct
+        assert_eq!(manager.reconcile_agents().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_leaves_recently_deployed_agents_alone() {
+        let proxy = MockProxy::default().with_agent_reports();
+        let node_agents = Arc::clone(&proxy.agents);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let manager = setup_manager();
+        manager.register_node(proxied_node("node-slow-ack", &proxy_addr)).await.unwrap();
+        let agent_id = manager.deploy_agent("node-slow-ack".to_string(), "Worker".to_string(), "Worker".to_string()).await.unwrap().agent_id;
+
+        // The node has not reported the new agent yet, but it is still within the grace period
+        node_agents.lock().unwrap().clear();
+        assert_eq!(manager.reconcile_agents().await, 0);
+        assert_eq!(manager.get_agent(&agent_id).await.unwrap().status, "Running");
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_skips_agents_in_transitional_states() {
+        let proxy = MockProxy::default().with_agent_reports();
+        let node_agents = Arc::clone(&proxy.agents);
+        let proxy_addr = spawn_mock_proxy(proxy).await;
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.reconcile_grace_secs = 0;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        manager.register_node(proxied_node("node-busy", &proxy_addr)).await.unwrap();
+        for (agent_id, status) in [("agent-pooled", "Pooled"), ("agent-deploying", "Deploying"), ("agent-migrating", "Migrating")] {
+            manager.register_ai_agent(AIAgent {
+                status: status.to_string(),
+                last_active: Utc::now() - chrono::Duration::hours(1),
+                ..running_agent(agent_id, "node-busy")
+            }).await.unwrap();
+        }
+        // The node reports the pooled agent as plain running and knows nothing of the others yet
+        node_agents.lock().unwrap().insert("agent-pooled".to_string(), "Running".to_string());
+
+        assert_eq!(manager.reconcile_agents().await, 0);
+        assert_eq!(manager.get_agent("agent-pooled").await.unwrap().status, "Pooled");
+        assert_eq!(manager.get_agent("agent-deploying").await.unwrap().status, "Deploying");
+        assert_eq!(manager.get_agent("agent-migrating").await.unwrap().status, "Migrating");
+    }
+
+    #[tokio::test]
+    async fn test_pending_deploy_is_placed_once_a_node_registers() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.pending_deploy_capacity = 4;
+        fabric_config.pending_deploy_timeout_secs = 10;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db()).with_fabric_config(fabric_config);
+
+        let waiting = manager.clone();
+        let deploy = tokio::spawn(async move { waiting.deploy_agent_auto("Worker".to_string(), "Worker".to_string(), &CapabilityRequirement::default()).await });
+        while manager.pending_deploys() == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(!deploy.is_finished());
+        manager.register_node(proxied_node("node-elastic", &proxy_addr)).await.unwrap();
+
+        let outcome = tokio::time::timeout(std::time::Duration::from_secs(5), deploy).await.unwrap().unwrap().unwrap();
+        let agent = manager.get_agent(&outcome.agent_id).await.unwrap();
+        assert_eq!(agent.assigned_node_id.as_deref(), Some("node-elastic"));
+        assert_eq!(agent.status, "Running");
+        assert_eq!(manager.pending_deploys(), 0);
+        let mut lifecycle = Vec::new();
+        while let Ok(event) = event_rx.try_recv() {
+            if event.event_type == "DEPLOY_PENDING" || event.event_type == "AGENT_DEPLOYED" {
+                lifecycle.push(event.event_type);
+            }
+        }
+        assert_eq!(lifecycle, vec!["DEPLOY_PENDING", "AGENT_DEPLOYED"]);
+    }
+
+    #[tokio::test]
+    async fn test_waiting_untargeted_deploy_does_not_hold_up_the_command_queue() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.pending_deploy_capacity = 4;
+        fabric_config.pending_deploy_timeout_secs = 10;
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, temp_db()).with_fabric_config(fabric_config);
+        let deploy = |command_id: &str, target_id: &str| {
+            let mut command = FabricCommand {
+                command_id: command_id.to_string(),
+                command_type: "DEPLOY_AGENT".to_string(),
+                target_id: target_id.to_string(),
+                ..Default::default()
+            };
+            command.parameters.insert("name".to_string(), "Worker".to_string());
+            command.parameters.insert("type".to_string(), "Worker".to_string());
+            command
+        };
+
+        // No node has room yet, so the untargeted deploy waits in the background
+        let executed = tokio::time::timeout(std::time::Duration::from_secs(1), manager.execute_command(deploy("deploy-any", "")));
+        assert_eq!(executed.await.unwrap(), Ok(()));
+        while manager.pending_deploys() == 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        manager.register_node(proxied_node("node-late", &proxy_addr)).await.unwrap();
+        manager.execute_command(deploy("deploy-targeted", "node-late")).await.unwrap();
+
+        let mut executed = Vec::new();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while executed.len() < 2 {
+                let event = event_rx.recv().await.unwrap();
+                if event.event_type == "COMMAND_EXECUTED" {
+                    assert_eq!(event.metadata["result"], "SUCCESS", "{}", event.message);
+                    executed.push(event.metadata["command_id"].clone());
+                }
+            }
+        }).await.unwrap();
+        executed.sort();
+        assert_eq!(executed, vec!["deploy-any", "deploy-targeted"]);
+        assert_eq!(manager.list_agents().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unschedulable_deploy_fails_without_queue_room() {
+        let manager = setup_manager();
+        let result = manager.deploy_agent_auto("Worker".to_string(), "Worker".to_string(), &CapabilityRequirement::default()).await;
+        assert!(matches!(result, Err(FabricError::NoNodeAvailable)));
+
+        let mut fabric_config = NexusConfig::default().fabric;
+        fabric_config.pending_deploy_capacity = 1;
+        fabric_config.pending_deploy_timeout_secs = 0;
+        let manager = setup_manager().with_fabric_config(fabric_config);
+        let result = manager.deploy_agent_auto("Worker".to_string(), "Worker".to_string(), &CapabilityRequirement::default()).await;
+        assert!(matches!(result, Err(FabricError::PendingDeployTimedOut(_))));
+        assert_eq!(manager.pending_deploys(), 0);
+        assert!(manager.list_agents().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_matching_places_agents_only_on_eligible_nodes() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let manager = setup_manager();
+        for (id, capabilities) in [("node-small", "CPU:2,RAM:4GB"), ("node-gpu", "CPU:16,RAM:64GB,GPU:2"), ("node-big", "CPU:32,RAM:128GB")] {
+            manager.register_node(ComputeNode {
+                capabilities: capabilities.to_string(),
+                resources: NodeCapabilities::parse(capabilities),
+                registered_by: None,
+                draining: false,
+                ..proxied_node(id, &proxy_addr)
+            }).await.unwrap();
+        }
+
+        let requirement = CapabilityRequirement { cpu_cores: 8, memory_bytes: 32 << 30, gpus: 0 };
+        let matching: Vec<_> = manager.find_nodes_matching(&requirement).await.into_iter().map(|node| node.id).collect();
+        assert_eq!(matching, vec!["node-big", "node-gpu"]);
+
+        let gpu = CapabilityRequirement { gpus: 1, ..requirement };
+        let outcome = manager.deploy_agent_auto("Trainer".to_string(), "Worker".to_string(), &gpu).await.unwrap();
+        assert_eq!(manager.get_agent(&outcome.agent_id).await.unwrap().assigned_node_id.as_deref(), Some("node-gpu"));
+
+        let impossible = CapabilityRequirement { gpus: 8, ..Default::default() };
+        assert!(manager.find_nodes_matching(&impossible).await.is_empty());
+        let result = manager.deploy_agent_auto("Trainer".to_string(), "Worker".to_string(), &impossible).await;
+        assert!(matches!(result, Err(FabricError::NoNodeAvailable)));
+    }
+
+    #[tokio::test]
+    async fn test_deploy_command_without_target_prefers_least_loaded_eligible_node() {
+        let proxy_addr = spawn_mock_proxy(MockProxy::default().with_checkpoints()).await;
+        let manager = setup_manager();
+        for (id, capabilities) in [("node-a", "CPU:8"), ("node-b", "CPU:8"), ("node-c", "CPU:2")] {
+            manager.register_node(ComputeNode {
+                resources: NodeCapabilities::parse(capabilities),
+                registered_by: None,
+                draining: false,
+                ..proxied_node(id, &proxy_addr)
+            }).await.unwrap();
+        }
+        manager.deploy_agent("node-a".to_string(), "Busy".to_string(), "Worker".to_string()).await.unwrap();
+
+        let deploy = |name: &str| FabricCommand {
+            command_id: String::new(),
+            command_type: "DEPLOY_AGENT".to_string(),
+            target_id: String::new(),
+            parameters: [("name", name), ("type", "Worker"), ("min_cpu_cores", "4")]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        };
+        // Untargeted deploys run in the background, so wait for the agent to show up
+        let placed_on = |name: &'static str| {
+            let manager = manager.clone();
+            tokio::time::timeout(std::time::Duration::from_secs(5), async move {
+                loop {
+                    let agent = manager.list_agents().await.into_iter().find(|agent| agent.name == name);
+                    if let Some(node_id) = agent.and_then(|agent| agent.assigned_node_id) {
+                        return node_id;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                }
+            })
+        };
+
+        // node-c is idle but too small; node-b is the idler of the eligible nodes
+        manager.execute_command(deploy("First")).await.unwrap();
+        assert_eq!(placed_on("First").await.unwrap(), "node-b");
+        // With equal load the tie goes to the lower node id
+        manager.execute_command(deploy("Second")).await.unwrap();
+        assert_eq!(placed_on("Second").await.unwrap(), "node-a");
+    }
+
+    #[test]
+    fn test_fabric_errors_map_to_grpc_codes() {
+        use tonic::Code;
+        let cases = [
+            (FabricError::NodeNotFound("n".into()), Code::NotFound),
+            (FabricError::AgentNotFound("a".into()), Code::NotFound),
+            (FabricError::FleetNotFound("f".into()), Code::NotFound),
+            (FabricError::UnknownAgentType("t".into()), Code::InvalidArgument),
+            (FabricError::AgentIdCollision { agent_id: "a".into(), node_id: "n".into() }, Code::AlreadyExists),
+            (FabricError::AgentPinned("a".into()), Code::FailedPrecondition),
+            (FabricError::AgentUnassigned("a".into()), Code::FailedPrecondition),
+            (FabricError::NodeOffline { node_id: "n".into(), status: "Offline".into() }, Code::FailedPrecondition),
+            (FabricError::DrainIncomplete { node_id: "n".into(), remaining: vec!["a".into()] }, Code::FailedPrecondition),
+            (FabricError::NodeFull { node_id: "n".into(), max_agents: 1 }, Code::ResourceExhausted),
+            (FabricError::FabricFull { max_nodes: 1 }, Code::ResourceExhausted),
+            (FabricError::NoNodeAvailable, Code::ResourceExhausted),
+            (FabricError::PendingDeployQueueFull(1), Code::ResourceExhausted),
+            (FabricError::PendingDeployTimedOut(std::time::Duration::from_secs(1)), Code::DeadlineExceeded),
+            (FabricError::RollingUpdateHalted { fleet_id: "f".into(), failed: 1, attempted: 1 }, Code::Aborted),
+            (FabricError::NotNodeOwner { node_id: "n".into(), entity: "e".into() }, Code::PermissionDenied),
+            (FabricError::Degraded(3), Code::Unavailable),
+            (FabricError::ShuttingDown, Code::Unavailable),
+            (FabricError::NoProxyClient("n".into()), Code::Unavailable),
+            (FabricError::ProxyError { node_id: "n".into(), message: "down".into() }, Code::Unavailable),
+            (FabricError::Persistence(sled::Error::Unsupported("x".into())), Code::Internal),
+            (FabricError::Serialization(Box::new(bincode::ErrorKind::SizeLimit)), Code::Internal),
+            (FabricError::Encoding(EncodingError::Truncated), Code::Internal),
+        ];
+        for (error, code) in cases {
+            let message = error.to_string();
+            let status = tonic::Status::from(error);
+            assert_eq!(status.code(), code, "{}", message);
+            assert_eq!(status.message(), message);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_mode_rejects_deploys_but_not_queries_and_survives_restart() {
+        let db = temp_db();
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, mut event_rx) = broadcast::channel(32);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let manager = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db.clone());
+        manager.register_node(stale_node("node-paused", None)).await.unwrap();
+        while event_rx.try_recv().is_ok() {}
+
+        manager.set_maintenance_mode(true, "kernel upgrade".to_string()).await.unwrap();
+        let event = event_rx.try_recv().unwrap();
+        assert_eq!(event.event_type, "MAINTENANCE_MODE_CHANGED");
+        assert_eq!(event.metadata["enabled"], "true");
+
+        let rejected = manager.deploy_agent("node-paused".to_string(), "agent".to_string(), "Synthesizer".to_string()).await;
+        let Err(e @ FabricError::Maintenance(_)) = rejected else {
+            panic!("deploy must be rejected in maintenance mode, got {:?}", rejected);
+        };
+        let status = tonic::Status::from(e);
+        assert_eq!(status.code(), tonic::Code::Unavailable);
+        assert!(status.message().contains("kernel upgrade"));
+        assert_eq!(manager.list_nodes().await.len(), 1);
+
+        // The flag is persisted, so a restarted core is still paused
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let restarted = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, db);
+        assert_eq!(restarted.maintenance_mode().await.unwrap().reason, "kernel upgrade");
+        restarted.set_maintenance_mode(false, "done".to_string()).await.unwrap();
+        assert!(restarted.ensure_not_in_maintenance().await.is_ok());
+    }
+
+    // Collects formatted log lines so a test can count what reached the console
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_without_listeners_warn_once_per_quiet_period() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::WARN)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let warnings = || {
+            let text = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+            text.lines().filter(|line| line.contains("No listeners")).map(str::to_string).collect::<Vec<_>>()
+        };
+
+        let manager = setup_manager();
+        manager.register_node(stale_node("node-quiet", None)).await.unwrap();
+        for status in ["Busy", "Online"].iter().cycle().take(20) {
+            manager.update_node_status("node-quiet".to_string(), status.to_string(), None).await.unwrap();
+        }
+        let quiet = warnings();
+        assert_eq!(quiet.len(), 2, "{:?}", quiet);
+        assert!(quiet.iter().all(|line| line.contains("WARN")));
+
+        // A listener coming and going starts a new quiet period on the event bus only
+        let (_, events) = manager.subscribe_with_snapshot().await;
+        manager.update_node_status("node-quiet".to_string(), "Busy".to_string(), None).await.unwrap();
+        drop(events);
+        manager.update_node_status("node-quiet".to_string(), "Online".to_string(), None).await.unwrap();
+        manager.update_node_status("node-quiet".to_string(), "Busy".to_string(), None).await.unwrap();
+        let warned = warnings();
+        assert_eq!(warned.len(), 3, "{:?}", warned);
+        assert!(warned[2].contains("event bus"));
+    }
 }
@@ -0,0 +1,56 @@
+// Unit tests for gRPC request metrics
+
+#[cfg(test)]
+mod tests {
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use nexus_prime_core::config::NexusConfig;
+    use nexus_prime_core::fabric_proto::fabric::fabric_service_client::FabricServiceClient;
+    use nexus_prime_core::fabric_proto::fabric::fabric_service_server::FabricServiceServer;
+    use nexus_prime_core::fabric_proto::fabric::AgentRegistrationRequest;
+    use nexus_prime_core::observability::{ExportFormat, ObservabilityEngine};
+    use nexus_prime_core::*;
+    use std::sync::Arc;
+    use tokio::sync::{broadcast, mpsc};
+
+    #[tokio::test]
+    async fn test_each_rpc_is_counted_with_its_status() {
+        // The server records from its own tasks, so the recorder has to be the global one
+        let observability = Arc::new(
+            ObservabilityEngine::new(
+                "test".to_string(),
+                "0.0.0".to_string(),
+                "test".to_string(),
+                "deployment-test".to_string(),
+            )
+            .with_prometheus_handle(PrometheusBuilder::new().install_recorder().unwrap()),
+        );
+        let security = SecurityManager::new(NexusConfig::default().security);
+        let token = security.generate_token("node-agent".to_string(), EntityType::Node, vec![Permission::RegisterNode]).await.unwrap();
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let manager = FabricManager::new(broadcast::channel(10).0, event_stream_tx.clone(), mpsc::channel(10).0, db);
+        let service = FabricServiceServerImpl::new(manager, event_stream_tx).with_security(security);
+        let addr = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .layer(GrpcMetricsLayer::new(observability.clone()))
+                .add_service(FabricServiceServer::new(service))
+                .serve(addr),
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let mut client = FabricServiceClient::connect(format!("http://{}", addr)).await.unwrap();
+
+        let registration = || AgentRegistrationRequest { ip_address: "10.0.0.7".to_string(), ..Default::default() };
+        client.register_agent(registration()).await.unwrap_err();
+        let mut request = tonic::Request::new(registration());
+        request.metadata_mut().insert("authorization", format!("Bearer {}", token).parse().unwrap());
+        client.register_agent(request).await.unwrap();
+
+        let metrics = observability.export_metrics(ExportFormat::Prometheus).await.unwrap();
+        assert!(metrics.contains("grpc_requests_total{method=\"RegisterAgent\",code=\"Ok\"} 1"), "{}", metrics);
+        assert!(metrics.contains("grpc_requests_total{method=\"RegisterAgent\",code=\"Unauthenticated\"} 1"), "{}", metrics);
+        assert!(metrics.contains("grpc_requests_failed_total{method=\"RegisterAgent\",code=\"Unauthenticated\"} 1"), "{}", metrics);
+        assert!(!metrics.contains("grpc_requests_failed_total{method=\"RegisterAgent\",code=\"Ok\"}"), "{}", metrics);
+        assert!(metrics.contains("grpc_request_duration_seconds_count{method=\"RegisterAgent\",code=\"Ok\"} 1"), "{}", metrics);
+    }
+}
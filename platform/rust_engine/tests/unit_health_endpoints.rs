@@ -0,0 +1,85 @@
+// Unit tests for the /healthz and /readyz probes
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::observability::{HealthStatus, ObservabilityEngine};
+    use nexus_prime_core::*;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    async fn spawn_probes(observability: Arc<ObservabilityEngine>, readiness: Readiness) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, health_router(observability, readiness)).await });
+        format!("http://{}", addr)
+    }
+
+    async fn get(url: String) -> (u16, serde_json::Value) {
+        let response = reqwest::get(url).await.unwrap();
+        (response.status().as_u16(), response.json().await.unwrap())
+    }
+
+    fn engine() -> Arc<ObservabilityEngine> {
+        Arc::new(ObservabilityEngine::new(
+            "test".to_string(),
+            "0.0.0".to_string(),
+            "test".to_string(),
+            "deployment-test".to_string(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_readyz_waits_for_every_component() {
+        let readiness = Readiness::new(&["database", "grpc_server", "command_processor"]);
+        let base = spawn_probes(engine(), readiness.clone()).await;
+
+        readiness.mark_ready("database");
+        let (status, body) = get(format!("{}/readyz", base)).await;
+        assert_eq!(status, 503);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["pending"], serde_json::json!(["command_processor", "grpc_server"]));
+
+        readiness.mark_ready("grpc_server");
+        readiness.mark_ready("command_processor");
+        let (status, body) = get(format!("{}/readyz", base)).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["ready"], true);
+    }
+
+    #[tokio::test]
+    async fn test_readyz_fails_while_a_background_task_is_unhealthy() {
+        let watchdog = Watchdog::new();
+        let heartbeat = watchdog.register("periodic_pruner", std::time::Duration::from_millis(10));
+        let base = spawn_probes(engine(), Readiness::new(&[]).with_watchdog(watchdog.clone())).await;
+
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        watchdog.check();
+        let (status, body) = get(format!("{}/readyz", base)).await;
+        assert_eq!(status, 503);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["unhealthy_tasks"], serde_json::json!(["periodic_pruner"]));
+
+        heartbeat.beat();
+        watchdog.check();
+        let (status, body) = get(format!("{}/readyz", base)).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["unhealthy_tasks"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_healthz_reports_health_state_and_fails_while_unhealthy() {
+        let observability = engine();
+        let base = spawn_probes(observability.clone(), Readiness::new(&[])).await;
+
+        let (status, body) = get(format!("{}/healthz", base)).await;
+        assert_eq!(status, 200);
+        assert_eq!(body["overall_status"], "Healthy");
+
+        let details = HashMap::from([("sled_error".to_string(), "io error".to_string())]);
+        observability.update_subsystem_health("database_connectivity", HealthStatus::Critical, 1, 0, 0.0, details).await;
+        let (status, body) = get(format!("{}/healthz", base)).await;
+        assert_eq!(status, 503);
+        assert_eq!(body["overall_status"], "Critical");
+        assert_eq!(body["subsystem_health"]["database_connectivity"]["details"]["sled_error"], "io error");
+    }
+}
@@ -0,0 +1,33 @@
+// Unit tests for metrics export formats
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use nexus_prime_core::observability::metrics::*;
+
+    #[test]
+    fn test_export_format_negotiated_from_accept_header() {
+        assert_eq!(ExportFormat::from_accept(Some("application/openmetrics-text; version=1.0.0")), ExportFormat::OpenMetrics);
+        assert_eq!(ExportFormat::from_accept(Some("text/plain")), ExportFormat::Prometheus);
+        assert_eq!(ExportFormat::from_accept(None), ExportFormat::Prometheus);
+    }
+
+    #[test]
+    fn test_openmetrics_export_includes_trace_exemplars() {
+        let metrics = MetricsCollector::new("nexus-prime-core", "1.0.0", "test").unwrap();
+        metrics.record_http_request_traced("GET", "/api/nodes", 200, "nexus-prime-core", "1.0.0", Duration::from_millis(30), 512, "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        let output = metrics.export(ExportFormat::OpenMetrics).unwrap();
+        assert!(output.ends_with("# EOF\n"));
+        assert!(output.contains("# TYPE omnimesh_http_http_requests counter"));
+        assert!(output.contains("omnimesh_http_http_requests_total{"));
+        let bucket = output.lines()
+            .find(|line| line.starts_with("omnimesh_http_http_request_duration_seconds_bucket") && line.contains("le=\"0.05\""))
+            .expect("latency bucket");
+        assert!(bucket.contains("# {trace_id=\"4bf92f3577b34da6a3ce929d0e0e4736\"} 0.03"));
+
+        let prometheus = metrics.export(ExportFormat::Prometheus).unwrap();
+        assert!(!prometheus.contains("# EOF"));
+        assert!(!prometheus.contains("trace_id"));
+    }
+}
@@ -0,0 +1,83 @@
+// Unit tests for the Prometheus /metrics endpoint
+
+#[cfg(test)]
+mod tests {
+    use metrics_exporter_prometheus::PrometheusBuilder;
+    use nexus_prime_core::observability::{MetricsCollector, ObservabilityEngine};
+    use nexus_prime_core::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_metrics_are_scraped_as_prometheus_text() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let observability = Arc::new(
+            ObservabilityEngine::new(
+                "test".to_string(),
+                "0.0.0".to_string(),
+                "test".to_string(),
+                "deployment-test".to_string(),
+            )
+            .with_prometheus_handle(recorder.handle()),
+        );
+        metrics::with_local_recorder(&recorder, || {
+            observability.record_request("grpc", "RegisterAgent", 200, Duration::from_millis(5), None);
+        });
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, metrics_router(observability)).await });
+
+        let response = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap();
+        assert_eq!(response.status(), 200);
+        let content_type = response.headers()[reqwest::header::CONTENT_TYPE].to_str().unwrap().to_string();
+        assert!(content_type.starts_with("text/plain; version=0.0.4"), "{}", content_type);
+        let body = response.text().await.unwrap();
+        assert!(body.contains("# TYPE http_requests_total counter"), "{}", body);
+        assert!(body.contains("http_requests_total{method=\"RegisterAgent\",status_code=\"200\",request_type=\"grpc\"} 1"), "{}", body);
+    }
+
+    // The Accept header Prometheus 2.x sends when scraping
+    const PROMETHEUS_ACCEPT: &str = "application/openmetrics-text;version=1.0.0,application/openmetrics-text;version=0.0.1;q=0.75,text/plain;version=0.0.4;q=0.5,*/*;q=0.1";
+
+    #[tokio::test]
+    async fn test_prometheus_scrape_gets_openmetrics_with_recorder_metrics_and_exemplars() {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let collector = MetricsCollector::new("nexus-prime-core", "1.0.0", "test").unwrap();
+        let observability = Arc::new(
+            ObservabilityEngine::new(
+                "test".to_string(),
+                "0.0.0".to_string(),
+                "test".to_string(),
+                "deployment-test".to_string(),
+            )
+            .with_prometheus_handle(recorder.handle())
+            .with_metrics_collector(&collector),
+        );
+        metrics::with_local_recorder(&recorder, || {
+            observability.record_request("grpc", "RegisterAgent", 200, Duration::from_millis(5), None);
+        });
+        collector.record_http_request_traced("GET", "/api/nodes", 200, "nexus-prime-core", "1.0.0", Duration::from_millis(30), 512, "4bf92f3577b34da6a3ce929d0e0e4736");
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, metrics_router(observability)).await });
+
+        let response = reqwest::Client::new()
+            .get(format!("http://{}/metrics", addr))
+            .header(reqwest::header::ACCEPT, PROMETHEUS_ACCEPT)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let content_type = response.headers()[reqwest::header::CONTENT_TYPE].to_str().unwrap().to_string();
+        assert!(content_type.starts_with("application/openmetrics-text"), "{}", content_type);
+        let body = response.text().await.unwrap();
+        assert!(body.ends_with("# EOF\n"), "{}", body);
+        assert_eq!(body.matches("# EOF").count(), 1, "{}", body);
+        assert!(!body.lines().any(|line| line.is_empty()), "{}", body);
+        assert!(body.contains("# TYPE http_requests counter"), "{}", body);
+        assert!(body.contains("http_requests_total{method=\"RegisterAgent\",status_code=\"200\",request_type=\"grpc\"} 1"), "{}", body);
+        assert!(body.contains("# {trace_id=\"4bf92f3577b34da6a3ce929d0e0e4736\"} 0.03"), "{}", body);
+    }
+}
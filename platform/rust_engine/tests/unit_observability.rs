@@ -0,0 +1,273 @@
+// Unit tests for observability health checks, structured log outputs and level filtering
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::observability::{
+        BackpressurePolicy, BufferConfig, HealthCheckSchedule, LogContext, LogFilterError, LogLevel,
+        LogLevelFilter, LogOutput, ObservabilityEngine, ResourceProbe, ResourceUsage,
+        RollingJsonFileOutput, StructuredLogEntry, StructuredLogger,
+    };
+    use nexus_prime_core::config::ResourceThresholds;
+    use std::sync::Arc;
+    use std::io::Read;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, Instant};
+
+    struct FakeProbe(ResourceUsage);
+
+    impl ResourceProbe for FakeProbe {
+        fn sample(&self) -> ResourceUsage {
+            self.0
+        }
+    }
+
+    fn engine() -> ObservabilityEngine {
+        ObservabilityEngine::new(
+            "test".to_string(),
+            "0.0.0".to_string(),
+            "test".to_string(),
+            "deployment-test".to_string(),
+        )
+    }
+
+    fn temp_log_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rolling_logs_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Entries serialize to lines of equal length
+    fn entry(i: usize) -> StructuredLogEntry {
+        StructuredLogEntry {
+            timestamp: chrono::DateTime::UNIX_EPOCH,
+            level: LogLevel::INFO,
+            message: format!("entry {:03}", i),
+            context: LogContext {
+                trace_id: "trace".to_string(),
+                span_id: "span".to_string(),
+                service: "test".to_string(),
+                version: "0.0.0".to_string(),
+                environment: "test".to_string(),
+                node_id: None,
+                user_id: None,
+                session_id: None,
+                correlation_id: None,
+                operation: None,
+                component: None,
+            },
+            fields: [("i".to_string(), serde_json::json!(format!("{:03}", i)))].into(),
+            error: None,
+            performance: None,
+            security: None,
+        }
+    }
+
+    // Sorted names of the files in dir
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(dir).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    // Entry numbers in a gzipped rolled file
+    fn rolled_entries(path: &Path) -> Vec<u64> {
+        let mut contents = String::new();
+        flate2::read::GzDecoder::new(std::fs::File::open(path).unwrap()).read_to_string(&mut contents).unwrap();
+        contents.lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["fields"]["i"].as_str().unwrap().parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_schedule_rechecks_faster_after_failure_and_backs_off() {
+        let mut schedule = HealthCheckSchedule::new(Duration::from_secs(40));
+        assert_eq!(schedule.next_delay(true), Duration::from_secs(40));
+        assert_eq!(schedule.next_delay(false), Duration::from_secs(10));
+        assert_eq!(schedule.next_delay(false), Duration::from_secs(10));
+        assert_eq!(schedule.next_delay(true), Duration::from_secs(20));
+        assert_eq!(schedule.next_delay(true), Duration::from_secs(40));
+        assert_eq!(schedule.next_delay(true), Duration::from_secs(40));
+    }
+
+    #[tokio::test]
+    async fn test_background_health_checks_update_health_state() {
+        let engine = engine();
+        let before = engine.get_health_state().await.last_health_check;
+
+        let handle = engine.start_health_checks(Duration::from_millis(20));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.abort();
+
+        let state = engine.get_health_state().await;
+        assert!(state.last_health_check > before);
+        assert!(!state.subsystem_health.is_empty());
+    }
+
+    #[test]
+    fn test_buffered_logging_does_not_block_callers_and_writes_what_it_keeps() {
+        let path = std::env::temp_dir().join(format!("buffered_log_{}.jsonl", uuid::Uuid::new_v4()));
+        let logger = StructuredLogger::new("test", "0.0.0", "test")
+            .with_file_output(path.to_str().unwrap())
+            .with_buffering(BufferConfig {
+                capacity: 1_000,
+                flush_interval: Duration::from_millis(10),
+                max_batch_size: 100,
+                policy: BackpressurePolicy::DropOldest,
+            });
+
+        let mut slowest = Duration::ZERO;
+        for i in 0..10_000 {
+            let started = Instant::now();
+            logger.info("flood").with_field("i", i).commit();
+            slowest = slowest.max(started.elapsed());
+        }
+        assert!(slowest < Duration::from_millis(50), "a caller waited {:?}", slowest);
+
+        // Dropping the logger writes out whatever is still queued
+        let dropped = logger.dropped_entries();
+        drop(logger);
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(lines.len() as u64, 10_000 - dropped);
+        assert_eq!(lines.last().unwrap()["fields"]["i"], 9_999);
+    }
+
+    #[test]
+    fn test_rolling_output_keeps_the_newest_compressed_files() {
+        let dir = temp_log_dir();
+        let path = dir.join("app.log");
+        let line_len = serde_json::to_string(&entry(0)).unwrap().len() as u64 + 1;
+        let output = RollingJsonFileOutput::new(path.to_str().unwrap(), line_len * 10, 3);
+
+        // 55 lines fill five files and start a sixth; the two oldest are deleted
+        let entries: Vec<_> = (0..55).map(entry).collect();
+        output.write_batch(&entries[..30]).unwrap();
+        for entry in &entries[30..] {
+            output.write(entry).unwrap();
+        }
+
+        assert_eq!(file_names(&dir), vec!["app.log", "app.log.3.gz", "app.log.4.gz", "app.log.5.gz"]);
+        assert_eq!(rolled_entries(&dir.join("app.log.3.gz")), (20..30).collect::<Vec<_>>());
+        assert_eq!(rolled_entries(&dir.join("app.log.5.gz")), (40..50).collect::<Vec<_>>());
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 5);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_rotation_interrupted_by_a_crash_is_completed_on_restart() {
+        let dir = temp_log_dir();
+        let path = dir.join("app.log");
+        let line = |i| serde_json::to_string(&entry(i)).unwrap() + "\n";
+
+        // The active file had been renamed aside and was partway through compression
+        std::fs::write(dir.join("app.log.1"), line(0) + &line(1)).unwrap();
+        std::fs::write(dir.join("app.log.1.gz.tmp"), b"partial").unwrap();
+        std::fs::write(&path, line(2)).unwrap();
+
+        let output = RollingJsonFileOutput::new(path.to_str().unwrap(), 1 << 20, 3);
+        assert_eq!(file_names(&dir), vec!["app.log", "app.log.1.gz"]);
+        assert_eq!(rolled_entries(&dir.join("app.log.1.gz")), vec![0, 1]);
+
+        output.write(&entry(3)).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), line(2) + &line(3));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_log_level_directives_parse_or_are_rejected() {
+        assert_eq!("Warning".parse::<LogLevel>().unwrap(), LogLevel::WARN);
+        assert_eq!("trace".parse::<LogLevel>().unwrap(), LogLevel::DEBUG);
+        assert_eq!("verbose".parse::<LogLevel>().unwrap_err(), LogFilterError::UnknownLevel("verbose".to_string()));
+
+        let filter: LogLevelFilter = " error , fabric=debug ".parse().unwrap();
+        assert_eq!(filter.level_for(None), LogLevel::ERROR);
+        assert_eq!(filter.level_for(Some("fabric")), LogLevel::DEBUG);
+        assert_eq!("".parse::<LogLevelFilter>().unwrap(), LogLevelFilter::default());
+        assert_eq!("fabric=loud".parse::<LogLevelFilter>().unwrap_err(), LogFilterError::UnknownLevel("loud".to_string()));
+        assert_eq!("=debug".parse::<LogLevelFilter>().unwrap_err(), LogFilterError::MalformedDirective("=debug".to_string()));
+
+        // The environment takes precedence over the configured level
+        assert_eq!(LogLevelFilter::resolve(Some("critical"), Some("warn"), "debug").unwrap().level_for(None), LogLevel::CRITICAL);
+        assert_eq!(LogLevelFilter::resolve(Some(" "), Some("warn"), "debug").unwrap().level_for(None), LogLevel::WARN);
+        assert_eq!(LogLevelFilter::resolve(None, None, "debug").unwrap().level_for(None), LogLevel::DEBUG);
+    }
+
+    #[test]
+    fn test_off_and_bare_targets_are_accepted_like_rust_log() {
+        let filter: LogLevelFilter = "off,nexus_prime_core::security,tonic=warn".parse().unwrap();
+        assert!(!filter.enabled(LogLevel::CRITICAL, None));
+        assert!(filter.enabled(LogLevel::DEBUG, Some("nexus_prime_core::security")));
+        assert!(!filter.enabled(LogLevel::INFO, Some("tonic")));
+        assert_eq!(filter.to_string(), "off,nexus_prime_core::security=debug,tonic=warn");
+
+        // The rendered directives are what the process logger is set up with
+        assert!(filter.to_string().parse::<tracing_subscriber::filter::Targets>().is_ok());
+        assert_eq!("info,fabric==debug".parse::<LogLevelFilter>().unwrap_err(), LogFilterError::UnknownLevel("=debug".to_string()));
+    }
+
+    #[test]
+    fn test_component_override_takes_precedence_over_the_global_level() {
+        let filter: LogLevelFilter = "info,fabric=debug,fabric::health=error,security=warn".parse().unwrap();
+        assert!(filter.enabled(LogLevel::DEBUG, Some("fabric")));
+        assert!(filter.enabled(LogLevel::DEBUG, Some("fabric::scheduler")));
+        assert!(!filter.enabled(LogLevel::WARN, Some("fabric::health")));
+        assert!(!filter.enabled(LogLevel::INFO, Some("security")));
+        assert!(!filter.enabled(LogLevel::DEBUG, Some("fabricated")));
+        assert!(filter.enabled(LogLevel::INFO, None));
+
+        let dir = temp_log_dir();
+        let path = dir.join("app.log");
+        let logger = StructuredLogger::new("test", "0.0.0", "test")
+            .with_file_output(path.to_str().unwrap())
+            .with_level_filter(filter);
+        logger.debug("kept").with_component("fabric").commit();
+        logger.debug("dropped").commit();
+        logger.info("dropped").with_component("security").commit();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let messages: Vec<String> = contents.lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["message"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(messages, vec!["kept"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resource_check_fails_with_readings_past_a_threshold() {
+        let engine = engine()
+            .with_resource_probe(Arc::new(FakeProbe(ResourceUsage { cpu_percent: 97.5, memory_percent: 40.0, disk_percent: 12.0 })))
+            .with_resource_thresholds(ResourceThresholds { max_cpu_percent: 90.0, ..ResourceThresholds::default() });
+
+        let result = engine.perform_health_check().await;
+        let check = result.checks.iter().find(|check| check.name == "system_resources").unwrap();
+        assert!(!check.passed);
+        assert_eq!(check.message, "cpu usage 97.5% is above 90.0%");
+        assert_eq!(check.details["cpu_percent"], "97.5");
+        assert_eq!(check.details["max_cpu_percent"], "90.0");
+        assert_eq!(check.details["memory_percent"], "40.0");
+        assert_eq!(check.details["disk_percent"], "12.0");
+
+        let state = engine.get_health_state().await;
+        assert_eq!(state.subsystem_health["system_resources"].details["cpu_percent"], "97.5");
+    }
+
+    #[tokio::test]
+    async fn test_database_check_pings_the_sled_database() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let engine = engine()
+            .with_resource_probe(Arc::new(FakeProbe(ResourceUsage::default())))
+            .with_database(db.clone());
+
+        let result = engine.perform_health_check().await;
+        let check = result.checks.iter().find(|check| check.name == "database_connectivity").unwrap();
+        assert!(check.passed, "{}", check.message);
+        assert!(check.details.contains_key("sled_latency_ms"));
+        assert!(db.open_tree("health_check").unwrap().get("last_ping").unwrap().is_some());
+    }
+}
@@ -0,0 +1,52 @@
+// Unit tests for reconnection rate limiting
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use tokio::time::{Duration, Instant};
+    use nexus_prime_core::config::NexusConfig;
+    use nexus_prime_core::reconnect::ReconnectLimiter;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_reconnections_throttled_for_mass_outage() {
+        let mut config = NexusConfig::default().fabric;
+        config.max_concurrent_reconnects = 4;
+        config.reconnects_per_second = 10;
+        config.reconnect_jitter_ms = 50;
+        let limiter = ReconnectLimiter::new(&config);
+
+        // 50 nodes drop at once and all try to reconnect
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let peak_in_progress = Arc::new(Mutex::new(0));
+        let mut attempts = Vec::new();
+        for _ in 0..50 {
+            let limiter = limiter.clone();
+            let started = Arc::clone(&started);
+            let peak_in_progress = Arc::clone(&peak_in_progress);
+            attempts.push(tokio::spawn(async move {
+                let _permit = limiter.acquire().await;
+                started.lock().await.push(Instant::now());
+                {
+                    let mut peak = peak_in_progress.lock().await;
+                    *peak = (*peak).max(limiter.in_progress());
+                }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }));
+        }
+        for attempt in attempts {
+            attempt.await.unwrap();
+        }
+
+        let started = started.lock().await;
+        assert_eq!(started.len(), 50);
+        assert!(*peak_in_progress.lock().await <= 4);
+        for (i, start) in started.iter().enumerate() {
+            let within_second = started[i..].iter()
+                .filter(|later| later.duration_since(*start) < Duration::from_secs(1))
+                .count();
+            assert!(within_second <= 10, "{} attempts started within one second", within_second);
+        }
+        assert_eq!(limiter.in_progress(), 0);
+    }
+}
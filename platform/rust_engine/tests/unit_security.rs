@@ -0,0 +1,219 @@
+// Unit tests for SecurityManager token handling
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::config::NexusConfig;
+    use nexus_prime_core::security::*;
+    use nexus_prime_core::clock::{Clock, MockClock};
+    use std::sync::Arc;
+
+    fn setup_security() -> SecurityManager {
+        SecurityManager::new(NexusConfig::default().security)
+    }
+
+    #[tokio::test]
+    async fn test_revoke_entity_revokes_all_tokens_for_entity() {
+        let security = setup_security();
+        let first = security.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::UpdateNodeStatus]).await.unwrap();
+        let second = security.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::DeployAgent]).await.unwrap();
+        let other = security.generate_token("node-2".to_string(), EntityType::Node, vec![Permission::UpdateNodeStatus]).await.unwrap();
+
+        assert_eq!(security.revoke_entity("node-1").await, 2);
+
+        assert!(security.validate_token(&first).await.is_err());
+        assert!(security.validate_token(&second).await.is_err());
+        assert!(security.validate_token(&other).await.is_ok());
+        assert_eq!(security.revoke_entity("node-1").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_token_expires_when_mock_clock_passes_session_timeout() {
+        let clock = MockClock::default();
+        let security = setup_security().with_clock(Arc::new(clock.clone()));
+        let token = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        assert!(security.validate_token(&token).await.is_ok());
+
+        let session_timeout = NexusConfig::default().security.session_timeout_minutes as i64;
+        clock.advance(chrono::Duration::minutes(session_timeout) + chrono::Duration::seconds(1));
+
+        assert!(security.validate_token(&token).await.is_err());
+        assert_eq!(security.cleanup_expired_tokens().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tokens_are_signed_jwts_that_round_trip() {
+        let security = setup_security();
+        let token = security.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::RegisterNode]).await.unwrap();
+        assert_eq!(token.split('.').count(), 3);
+        assert!(!token.contains(&NexusConfig::default().security.auth_token_secret));
+
+        let decoded = security.decode_token(&token).unwrap();
+        assert_eq!(decoded.entity_id, "node-1");
+        assert_eq!(decoded.permissions, vec![Permission::RegisterNode]);
+        assert_eq!(security.validate_token(&token).await.unwrap().token_id, decoded.token_id);
+    }
+
+    #[tokio::test]
+    async fn test_tampered_or_foreign_tokens_are_rejected() {
+        let security = setup_security();
+        let viewer = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        let admin = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::SystemControl]).await.unwrap();
+
+        // The admin claims under the viewer's signature
+        let viewer_parts: Vec<_> = viewer.split('.').collect();
+        let admin_parts: Vec<_> = admin.split('.').collect();
+        let tampered = format!("{}.{}.{}", viewer_parts[0], admin_parts[1], viewer_parts[2]);
+        assert!(matches!(security.decode_token(&tampered), Err(SecurityError::Token(_))));
+        assert!(security.validate_token(&tampered).await.is_err());
+
+        let mut config = NexusConfig::default().security;
+        config.auth_token_secret = "another-secret".to_string();
+        let other = SecurityManager::new(config);
+        assert!(matches!(other.decode_token(&viewer), Err(SecurityError::Token(_))));
+        assert!(matches!(security.decode_token("not-a-token"), Err(SecurityError::Token(_))));
+    }
+
+    #[tokio::test]
+    async fn test_token_past_its_exp_is_rejected_when_decoded() {
+        let clock = MockClock::default();
+        let security = setup_security().with_clock(Arc::new(clock.clone()));
+        let token = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        let expires_at = security.decode_token(&token).unwrap().expires_at;
+
+        clock.advance(expires_at - clock.now());
+        assert!(matches!(security.decode_token(&token), Err(SecurityError::Authentication(_))));
+    }
+
+    #[tokio::test]
+    async fn test_token_validates_on_a_manager_that_never_saw_it() {
+        let issuer = setup_security();
+        let token = issuer.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+
+        // A restarted or second instance starts with no active tokens
+        let restarted = setup_security();
+        assert_eq!(restarted.validate_token(&token).await.unwrap().entity_id, "ui");
+        assert!(restarted.check_permission(&token, &Permission::ViewFabricStatus).await.unwrap());
+        assert_eq!(restarted.revoke_entity("ui").await, 1);
+        assert!(restarted.validate_token(&token).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revocations_are_persisted_until_the_token_expires() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let clock = MockClock::default();
+        let security = setup_security().with_clock(Arc::new(clock.clone())).with_revocation_list(&db).unwrap();
+        let revoked = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        let kept = security.generate_token("ui".to_string(), EntityType::User, vec![Permission::ViewFabricStatus]).await.unwrap();
+        security.revoke_token(&revoked).await.unwrap();
+
+        let restarted = setup_security().with_clock(Arc::new(clock.clone())).with_revocation_list(&db).unwrap();
+        assert!(matches!(restarted.validate_token(&revoked).await, Err(SecurityError::Authentication(_))));
+        assert!(restarted.validate_token(&kept).await.is_ok());
+
+        // Once the token has expired its revocation is no longer needed
+        let session_timeout = NexusConfig::default().security.session_timeout_minutes as i64;
+        clock.advance(chrono::Duration::minutes(session_timeout) + chrono::Duration::seconds(1));
+        restarted.cleanup_expired_tokens().await.unwrap();
+        assert!(db.open_tree("revoked_tokens").unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_entity_revocation_survives_restart() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let security = setup_security().with_revocation_list(&db).unwrap();
+        let compromised = security.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::UpdateNodeStatus]).await.unwrap();
+        assert_eq!(security.revoke_entity("node-1").await, 1);
+        drop(security);
+
+        let restarted = setup_security().with_revocation_list(&db).unwrap();
+        assert!(matches!(restarted.validate_token(&compromised).await, Err(SecurityError::Authentication(_))));
+        let reissued = restarted.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::UpdateNodeStatus]).await.unwrap();
+        assert!(restarted.validate_token(&reissued).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_entity_revocation_covers_tokens_this_manager_never_saw() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        let clock = MockClock::default();
+        let issuer = setup_security().with_clock(Arc::new(clock.clone()));
+        let unseen = issuer.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::UpdateNodeStatus]).await.unwrap();
+        clock.advance(chrono::Duration::seconds(5));
+
+        let security = setup_security().with_clock(Arc::new(clock.clone())).with_revocation_list(&db).unwrap();
+        assert_eq!(security.revoke_entity("node-1").await, 0);
+        drop(security);
+
+        let restarted = setup_security().with_clock(Arc::new(clock.clone())).with_revocation_list(&db).unwrap();
+        assert!(matches!(restarted.validate_token(&unseen).await, Err(SecurityError::Authentication(_))));
+        clock.advance(chrono::Duration::seconds(1));
+        let reissued = issuer.generate_token("node-1".to_string(), EntityType::Node, vec![Permission::UpdateNodeStatus]).await.unwrap();
+        assert!(restarted.validate_token(&reissued).await.is_ok());
+
+        // Once a session has passed, every token issued before the revocation has expired
+        let session_timeout = NexusConfig::default().security.session_timeout_minutes as i64;
+        clock.advance(chrono::Duration::minutes(session_timeout));
+        restarted.cleanup_expired_tokens().await.unwrap();
+        assert!(db.open_tree("revoked_entities").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_roles_expand_to_their_permissions() {
+        assert_eq!(permissions_for_role(Role::Viewer), vec![Permission::ViewFabricStatus, Permission::ViewTelemetry]);
+        assert_eq!(permissions_for_role(Role::NodeAgent), vec![Permission::RegisterNode, Permission::UpdateNodeStatus]);
+
+        // Each role grants everything the role below it does
+        let operator = permissions_for_role(Role::Operator);
+        assert!(permissions_for_role(Role::Viewer).iter().all(|permission| operator.contains(permission)));
+        assert!(operator.contains(&Permission::ManageFabric));
+        assert!(!operator.contains(&Permission::SystemControl));
+        let admin = permissions_for_role(Role::Admin);
+        assert!(operator.iter().chain(&permissions_for_role(Role::NodeAgent)).all(|permission| admin.contains(permission)));
+        assert!(admin.contains(&Permission::SystemControl) && admin.contains(&Permission::ManageSecurityPolicy));
+    }
+
+    #[tokio::test]
+    async fn test_role_tokens_carry_the_role_and_only_its_permissions() {
+        let security = setup_security();
+        let viewer = security.generate_token_for_role("ui".to_string(), EntityType::User, Role::Viewer).await.unwrap();
+        let operator = security.generate_token_for_role("ops".to_string(), EntityType::User, Role::Operator).await.unwrap();
+
+        assert!(security.check_permission(&viewer, &Permission::ViewFabricStatus).await.unwrap());
+        assert!(!security.check_permission(&viewer, &Permission::ManageFabric).await.unwrap());
+        assert!(security.check_permission(&operator, &Permission::ManageFabric).await.unwrap());
+        assert_eq!(security.validate_token(&viewer).await.unwrap().metadata["role"], "Viewer");
+    }
+
+    #[test]
+    fn test_role_parse_ignores_case_and_rejects_unknown_names() {
+        assert_eq!(Role::parse("admin"), Some(Role::Admin));
+        assert_eq!(Role::parse("NodeAgent"), Some(Role::NodeAgent));
+        assert_eq!(Role::parse("OPERATOR"), Some(Role::Operator));
+        assert_eq!(Role::parse("root"), None);
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_a_token_pass_only_when_auth_is_not_enforced() {
+        let metadata = tonic::metadata::MetadataMap::new();
+        let status = setup_security().authorize_metadata(&metadata, Permission::ViewFabricStatus).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+
+        let mut config = NexusConfig::default().security;
+        config.enforce_auth = false;
+        assert!(SecurityManager::new(config).authorize_metadata(&metadata, Permission::SystemControl).await.is_ok());
+    }
+
+    #[test]
+    fn test_security_errors_map_to_grpc_codes() {
+        use tonic::Code;
+        let cases = [
+            (SecurityError::Authentication("expired".into()), Code::Unauthenticated),
+            (SecurityError::Token("malformed".into()), Code::Unauthenticated),
+            (SecurityError::Authorization("missing permission".into()), Code::PermissionDenied),
+            (SecurityError::Certificate("bad pem".into()), Code::Internal),
+            (SecurityError::Io(std::io::Error::other("disk")), Code::Internal),
+        ];
+        for (error, code) in cases {
+            assert_eq!(tonic::Status::from(error).code(), code);
+        }
+    }
+}
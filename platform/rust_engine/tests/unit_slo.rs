@@ -0,0 +1,68 @@
+// Unit tests for SLO evaluation
+
+#[cfg(test)]
+mod tests {
+    use nexus_prime_core::config::{SloConfig, SloMetric};
+    use nexus_prime_core::SloEvaluator;
+    use std::time::Duration;
+
+    fn slo(name: &str, metric: SloMetric, target: f64, latency_threshold_ms: Option<u64>) -> SloConfig {
+        SloConfig { name: name.to_string(), metric, target, latency_threshold_ms }
+    }
+
+    #[test]
+    fn test_breached_slo_is_reported_with_exhausted_budget() {
+        let evaluator = SloEvaluator::new(vec![
+            slo("deploy_success", SloMetric::DeploySuccess, 0.99, None),
+            slo("command_latency", SloMetric::CommandLatency, 0.5, Some(100)),
+        ], 100);
+        for i in 0..10 {
+            evaluator.record_deploy(i >= 2);
+            evaluator.record_command_latency(Duration::from_millis(if i < 2 { 500 } else { 10 }));
+        }
+
+        let statuses = evaluator.evaluate();
+        let deploys = &statuses[0];
+        assert_eq!(deploys.samples, 10);
+        assert!((deploys.current - 0.8).abs() < 1e-9);
+        assert!(!deploys.met);
+        assert_eq!(deploys.error_budget_remaining, 0.0);
+
+        // 2 slow commands out of an allowed 5 leaves 60% of the budget
+        let latency = &statuses[1];
+        assert!(latency.met);
+        assert!((latency.error_budget_remaining - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_breach_is_reported_once_until_the_slo_is_met_again() {
+        let evaluator = SloEvaluator::new(vec![slo("deploy_success", SloMetric::DeploySuccess, 0.9, None)], 2);
+        evaluator.record_deploy(true);
+        assert!(evaluator.new_breaches().is_empty());
+
+        evaluator.record_deploy(false);
+        let breaches = evaluator.new_breaches();
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(breaches[0].name, "deploy_success");
+        assert!(evaluator.new_breaches().is_empty());
+
+        evaluator.record_deploy(true);
+        evaluator.record_deploy(true);
+        assert!(evaluator.new_breaches().is_empty());
+        evaluator.record_deploy(false);
+        assert_eq!(evaluator.new_breaches().len(), 1);
+    }
+
+    #[test]
+    fn test_only_the_newest_samples_count() {
+        let evaluator = SloEvaluator::new(vec![slo("deploy_success", SloMetric::DeploySuccess, 0.9, None)], 3);
+        evaluator.record_deploy(false);
+        for _ in 0..3 {
+            evaluator.record_deploy(true);
+        }
+        let status = &evaluator.evaluate()[0];
+        assert_eq!(status.samples, 3);
+        assert!(status.met);
+        assert_eq!(status.error_budget_remaining, 1.0);
+    }
+}
@@ -0,0 +1,536 @@
+// Unit tests for storage layer helpers
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use nexus_prime_core::config::{NexusConfig, RetentionPolicy, StorageBackend};
+    use nexus_prime_core::storage::*;
+
+    #[derive(Default)]
+    struct CountingNodeStorage {
+        nodes: tokio::sync::RwLock<HashMap<String, FabricNode>>,
+        get_calls: Arc<AtomicUsize>,
+        hold_next_get: std::sync::Mutex<Option<(Arc<tokio::sync::Notify>, Arc<tokio::sync::Notify>)>>, // Signals once read, then waits
+    }
+
+    #[async_trait]
+    impl NodeStorage for CountingNodeStorage {
+        async fn store_node(&self, node: &FabricNode) -> StorageResult<()> {
+            self.nodes.write().await.insert(node.node_id.clone(), node.clone());
+            Ok(())
+        }
+
+        async fn get_node(&self, node_id: &str) -> StorageResult<Option<FabricNode>> {
+            self.get_calls.fetch_add(1, Ordering::SeqCst);
+            let node = self.nodes.read().await.get(node_id).cloned();
+            let hold = self.hold_next_get.lock().unwrap().take();
+            if let Some((read, release)) = hold {
+                read.notify_one();
+                release.notified().await;
+            }
+            Ok(node)
+        }
+
+        async fn list_nodes(&self) -> StorageResult<Vec<FabricNode>> {
+            Ok(self.nodes.read().await.values().cloned().collect())
+        }
+
+        async fn update_node_status(&self, node_id: &str, status: NodeStatus) -> StorageResult<()> {
+            if let Some(node) = self.nodes.write().await.get_mut(node_id) {
+                node.status = status;
+            }
+            Ok(())
+        }
+
+        async fn delete_node(&self, node_id: &str) -> StorageResult<()> {
+            self.nodes.write().await.remove(node_id);
+            Ok(())
+        }
+    }
+
+    fn test_node(node_id: &str) -> FabricNode {
+        FabricNode {
+            node_id: node_id.to_string(),
+            ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: "127.0.0.1:50052".to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            agent_type: "PC".to_string(),
+            status: NodeStatus::Online,
+            last_seen: Utc::now(),
+            created_at: Utc::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cached_get_node_within_ttl_skips_storage() {
+        let backend = CountingNodeStorage::default();
+        let get_calls = Arc::clone(&backend.get_calls);
+        backend.store_node(&test_node("node-1")).await.unwrap();
+
+        let mut config = NexusConfig::default().database;
+        config.cache_ttl_seconds = 60;
+        let storage = CachedStorage::new(backend, &config);
+
+        assert!(storage.get_node("node-1").await.unwrap().is_some());
+        assert!(storage.get_node("node-1").await.unwrap().is_some());
+        assert_eq!(get_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cached_get_node_invalidated_on_update() {
+        let backend = CountingNodeStorage::default();
+        let get_calls = Arc::clone(&backend.get_calls);
+        backend.store_node(&test_node("node-2")).await.unwrap();
+
+        let mut config = NexusConfig::default().database;
+        config.cache_ttl_seconds = 60;
+        let storage = CachedStorage::new(backend, &config);
+
+        storage.get_node("node-2").await.unwrap();
+        storage.update_node_status("node-2", NodeStatus::Maintenance).await.unwrap();
+        let node = storage.get_node("node-2").await.unwrap().unwrap();
+        assert!(matches!(node.status, NodeStatus::Maintenance));
+        assert_eq!(get_calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_overlapping_an_update_is_not_cached() {
+        let backend = CountingNodeStorage::default();
+        backend.store_node(&test_node("node-3")).await.unwrap();
+        let (read, release) = (Arc::new(tokio::sync::Notify::new()), Arc::new(tokio::sync::Notify::new()));
+        *backend.hold_next_get.lock().unwrap() = Some((read.clone(), release.clone()));
+
+        let mut config = NexusConfig::default().database;
+        config.cache_ttl_seconds = 60;
+        let storage = Arc::new(CachedStorage::new(backend, &config));
+
+        // The first read fetches the Online node, then the update lands before it returns
+        let reader = tokio::spawn({
+            let storage = storage.clone();
+            async move { storage.get_node("node-3").await.unwrap() }
+        });
+        read.notified().await;
+        storage.update_node_status("node-3", NodeStatus::Maintenance).await.unwrap();
+        release.notify_one();
+        assert!(matches!(reader.await.unwrap().unwrap().status, NodeStatus::Online));
+
+        let node = storage.get_node("node-3").await.unwrap().unwrap();
+        assert!(matches!(node.status, NodeStatus::Maintenance));
+    }
+
+    #[test]
+    fn test_cache_settings_default_when_missing_from_config() {
+        let mut value = serde_json::to_value(NexusConfig::default().database).unwrap();
+        let fields = value.as_object_mut().unwrap();
+        fields.remove("cache_ttl_seconds");
+        fields.remove("cache_max_entries");
+
+        let config: nexus_prime_core::config::DatabaseConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.cache_ttl_seconds, NexusConfig::default().database.cache_ttl_seconds);
+        assert_eq!(config.cache_max_entries, NexusConfig::default().database.cache_max_entries);
+    }
+
+    // Records each span's name, parent and fields so a test can see what was traced
+    #[derive(Clone, Default)]
+    struct SpanRecorder(Arc<std::sync::Mutex<Vec<RecordedSpan>>>);
+
+    struct RecordedSpan {
+        id: tracing::span::Id,
+        name: &'static str,
+        parent: Option<&'static str>,
+        fields: HashMap<String, String>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl tracing::field::Visit for FieldVisitor<'_> {
+        fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+            self.0.insert(field.name().to_string(), value.to_string());
+        }
+
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+
+    impl<S> tracing_subscriber::Layer<S> for SpanRecorder
+    where
+        S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: tracing_subscriber::layer::Context<'_, S>) {
+            let mut fields = HashMap::new();
+            attrs.record(&mut FieldVisitor(&mut fields));
+            let parent = ctx.span(id).and_then(|span| span.parent()).map(|parent| parent.name());
+            self.0.lock().unwrap().push(RecordedSpan { id: id.clone(), name: attrs.metadata().name(), parent, fields });
+        }
+
+        fn on_record(&self, id: &tracing::span::Id, values: &tracing::span::Record<'_>, _ctx: tracing_subscriber::layer::Context<'_, S>) {
+            if let Some(span) = self.0.lock().unwrap().iter_mut().find(|span| &span.id == id) {
+                values.record(&mut FieldVisitor(&mut span.fields));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_node_is_traced_as_a_child_database_span() {
+        use tracing::Instrument;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let recorder = SpanRecorder::default();
+        let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(recorder.clone()));
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = None;
+        config.use_rocksdb = true;
+        config.embedded_db_path = std::env::temp_dir().join(format!("nexus-storage-trace-{}", uuid::Uuid::new_v4()));
+        let storage = HybridStorage::new(config.clone()).await.unwrap();
+
+        storage.store_node(&test_node("node-traced")).instrument(tracing::info_span!("register_node")).await.unwrap();
+        storage.delete_node("node-traced").await.unwrap();
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&config.embedded_db_path);
+
+        let spans = recorder.0.lock().unwrap();
+        let store = spans.iter().find(|span| span.fields.get("db.operation").map(String::as_str) == Some("store")).unwrap();
+        assert_eq!(store.name, "db");
+        assert_eq!(store.parent, Some("register_node"));
+        assert_eq!(store.fields["db.table"], "nodes");
+        assert_eq!(store.fields["db.rows_affected"], "1");
+        assert!(store.fields.contains_key("duration_ms"));
+        assert!(spans.iter().any(|span| span.fields.get("db.operation").map(String::as_str) == Some("delete")));
+    }
+
+    fn telemetry_record(entity_id: &str, entity_type: &str, age_days: i64) -> TelemetryRecord {
+        TelemetryRecord {
+            id: uuid::Uuid::new_v4(),
+            entity_id: entity_id.to_string(),
+            entity_type: entity_type.to_string(),
+            timestamp: Utc::now() - chrono::Duration::days(age_days),
+            cpu_utilization: Some(0.5),
+            memory_utilization: None,
+            network_in_kbps: None,
+            network_out_kbps: None,
+            custom_metrics: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_applies_retention_of_each_entity_type() {
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = None;
+        config.use_rocksdb = true;
+        config.embedded_db_path = std::env::temp_dir().join(format!("nexus-storage-retention-{}", uuid::Uuid::new_v4()));
+        let storage = HybridStorage::new(config.clone()).await.unwrap();
+        for (entity_type, age_days) in [("system", 10), ("system", 2), ("node", 10), ("node", 20), ("custom", 40), ("custom", 20)] {
+            storage.store_telemetry(&telemetry_record(entity_type, entity_type, age_days)).await.unwrap();
+        }
+
+        let retention = RetentionPolicy {
+            default_days: 30,
+            days_by_entity_type: HashMap::from([("system".to_string(), 7), ("node".to_string(), 14)]),
+        };
+        let removed = storage.cleanup_old_telemetry(&retention).await.unwrap();
+        let ages = |records: Vec<TelemetryRecord>| -> Vec<i64> {
+            records.iter().map(|record| (Utc::now() - record.timestamp).num_days()).collect()
+        };
+        let hours = 24 * 60;
+        let system = ages(storage.get_telemetry_history("system", hours).await.unwrap());
+        let node = ages(storage.get_telemetry_history("node", hours).await.unwrap());
+        let custom = ages(storage.get_telemetry_history("custom", hours).await.unwrap());
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&config.embedded_db_path);
+
+        assert_eq!(removed, 3);
+        assert_eq!(system, vec![2]);
+        assert_eq!(node, vec![10]);
+        assert_eq!(custom, vec![20]);
+    }
+
+    async fn rocksdb_storage(name: &str) -> (HybridStorage, std::path::PathBuf) {
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = None;
+        config.use_rocksdb = true;
+        config.embedded_db_path = std::env::temp_dir().join(format!("nexus-storage-{}-{}", name, uuid::Uuid::new_v4()));
+        (HybridStorage::new(config.clone()).await.unwrap(), config.embedded_db_path)
+    }
+
+    #[tokio::test]
+    async fn test_batched_telemetry_is_all_retrievable() {
+        let (storage, path) = rocksdb_storage("batch").await;
+        let records: Vec<TelemetryRecord> = (0..50)
+            .map(|i| telemetry_record(&format!("node-{}", i % 5), "node", 0))
+            .collect();
+
+        storage.store_telemetry_many(&records).await.unwrap();
+
+        for node in 0..5 {
+            let entity_id = format!("node-{}", node);
+            let mut stored: Vec<_> = storage.get_telemetry_history(&entity_id, 1).await.unwrap().iter().map(|record| record.id).collect();
+            let mut expected: Vec<_> = records.iter().filter(|record| record.entity_id == entity_id).map(|record| record.id).collect();
+            stored.sort();
+            expected.sort();
+            assert_eq!(stored, expected);
+        }
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[tokio::test]
+    async fn test_rocksdb_telemetry_is_compressed_and_older_records_still_read() {
+        let (storage, path) = rocksdb_storage("telemetry-zstd").await;
+        drop(storage);
+        // A record as written before telemetry values carried a format header
+        let legacy = telemetry_record("node-1", "node", 1);
+        {
+            let rocks = rocksdb::DB::open(&rocksdb::Options::default(), &path).unwrap();
+            let key = format!("telemetry:node-1:{}:{}", legacy.timestamp.timestamp(), legacy.id);
+            rocks.put(key.as_bytes(), bincode::serialize(&legacy).unwrap()).unwrap();
+        }
+
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = None;
+        config.use_rocksdb = true;
+        config.embedded_db_path = path.clone();
+        config.compression = nexus_prime_core::config::Compression::Zstd;
+        let storage = HybridStorage::new(config).await.unwrap();
+        let recent = telemetry_record("node-1", "node", 0);
+        storage.store_telemetry(&recent).await.unwrap();
+        let history = storage.get_telemetry_history("node-1", 24 * 7).await.unwrap();
+        assert_eq!(history.iter().map(|record| record.id).collect::<Vec<_>>(), vec![legacy.id, recent.id]);
+        drop(storage);
+
+        let rocks = rocksdb::DB::open(&rocksdb::Options::default(), &path).unwrap();
+        let key = format!("telemetry:node-1:{}:{}", recent.timestamp.timestamp(), recent.id);
+        let stored = rocks.get(key.as_bytes()).unwrap().unwrap();
+        assert_eq!(stored, nexus_prime_core::compression::encode(&recent, nexus_prime_core::config::Compression::Zstd, 3).unwrap());
+        drop(rocks);
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    // The record layout and key written before metrics became optional and keys carried the record id
+    #[derive(Clone, serde::Serialize)]
+    struct TelemetryRecordV1 {
+        id: uuid::Uuid,
+        entity_id: String,
+        entity_type: String,
+        timestamp: chrono::DateTime<Utc>,
+        cpu_utilization: f32,
+        memory_utilization: f32,
+        network_in_kbps: f32,
+        network_out_kbps: f32,
+        custom_metrics: HashMap<String, f32>,
+    }
+
+    #[tokio::test]
+    async fn test_rocksdb_telemetry_in_the_first_layout_is_still_read_and_cleaned_up() {
+        let (storage, path) = rocksdb_storage("telemetry-v1").await;
+        drop(storage);
+        let first = TelemetryRecordV1 {
+            id: uuid::Uuid::new_v4(),
+            entity_id: "node-1".to_string(),
+            entity_type: "node".to_string(),
+            timestamp: Utc::now() - chrono::Duration::hours(2),
+            cpu_utilization: 0.25,
+            memory_utilization: 0.0,
+            network_in_kbps: 12.5,
+            network_out_kbps: 3.0,
+            custom_metrics: HashMap::new(),
+        };
+        let expired = TelemetryRecordV1 { id: uuid::Uuid::new_v4(), timestamp: Utc::now() - chrono::Duration::days(60), ..first.clone() };
+        {
+            let rocks = rocksdb::DB::open(&rocksdb::Options::default(), &path).unwrap();
+            for record in [&first, &expired] {
+                let key = format!("telemetry:node-1:{}", record.timestamp.timestamp());
+                rocks.put(key.as_bytes(), bincode::serialize(record).unwrap()).unwrap();
+            }
+        }
+
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = None;
+        config.use_rocksdb = true;
+        config.embedded_db_path = path.clone();
+        let storage = HybridStorage::new(config).await.unwrap();
+        let recent = telemetry_record("node-1", "node", 0);
+        storage.store_telemetry(&recent).await.unwrap();
+        let removed = storage.cleanup_old_telemetry(&RetentionPolicy { default_days: 30, days_by_entity_type: HashMap::new() }).await.unwrap();
+        let history = storage.get_telemetry_history("node-1", 24).await.unwrap();
+        drop(storage);
+        let _ = std::fs::remove_dir_all(path);
+
+        assert_eq!(removed, 1);
+        assert_eq!(history.iter().map(|record| record.id).collect::<Vec<_>>(), vec![first.id, recent.id]);
+        assert_eq!(history[0].cpu_utilization, Some(0.25));
+        assert_eq!(history[0].memory_utilization, Some(0.0));
+        assert_eq!(history[0].network_in_kbps, Some(12.5));
+        assert_eq!(history[1].memory_utilization, None);
+    }
+
+    // Compares wall-clock times, so it is left out of normal runs; run it with --ignored
+    #[tokio::test]
+    #[ignore = "timing-sensitive"]
+    async fn test_batched_telemetry_is_faster_than_single_rows() {
+        let (storage, path) = rocksdb_storage("batch-timing").await;
+
+        // Best of three rounds, so a stall in one round does not decide the comparison
+        let (mut single_elapsed, mut batch_elapsed) = (std::time::Duration::MAX, std::time::Duration::MAX);
+        for round in 0..3 {
+            let single: Vec<TelemetryRecord> = (0..1000).map(|_| telemetry_record(&format!("node-single-{}", round), "node", 0)).collect();
+            let batched: Vec<TelemetryRecord> = (0..1000).map(|_| telemetry_record(&format!("node-batched-{}", round), "node", 0)).collect();
+
+            let started = std::time::Instant::now();
+            for record in &single {
+                storage.store_telemetry(record).await.unwrap();
+            }
+            single_elapsed = single_elapsed.min(started.elapsed());
+            let started = std::time::Instant::now();
+            storage.store_telemetry_many(&batched).await.unwrap();
+            batch_elapsed = batch_elapsed.min(started.elapsed());
+        }
+        assert!(batch_elapsed < single_elapsed, "{:?} one at a time, {:?} batched", single_elapsed, batch_elapsed);
+        assert_eq!(storage.get_telemetry_history("node-batched-2", 1).await.unwrap().len(), 1000);
+        drop(storage);
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    async fn memory_storage() -> Arc<dyn Storage> {
+        let mut config = NexusConfig::default().database;
+        config.backend = StorageBackend::Memory;
+        open_storage(config).await.unwrap()
+    }
+
+    fn test_agent(agent_id: &str, node_id: &str) -> AIAgent {
+        AIAgent {
+            agent_id: agent_id.to_string(),
+            node_id: node_id.to_string(),
+            name: "Synthesizer".to_string(),
+            agent_type: "Synthesizer".to_string(),
+            status: AgentStatus::Starting,
+            created_at: Utc::now(),
+            last_active: Utc::now(),
+            config: HashMap::new(),
+            resources: AgentResources { cpu_cores: 1.0, memory_mb: 512, gpu_units: None },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_stores_nodes_and_agents() {
+        let storage = memory_storage().await;
+        storage.store_node(&test_node("node-b")).await.unwrap();
+        storage.store_node(&test_node("node-a")).await.unwrap();
+        storage.update_node_status("node-b", NodeStatus::Maintenance).await.unwrap();
+        storage.delete_node("node-a").await.unwrap();
+        for (agent_id, node_id) in [("agent-2", "node-b"), ("agent-1", "node-b"), ("agent-3", "node-c")] {
+            storage.store_agent(&test_agent(agent_id, node_id)).await.unwrap();
+        }
+        storage.update_agent_status("agent-1", AgentStatus::Running).await.unwrap();
+        storage.delete_agent("agent-3").await.unwrap();
+
+        let nodes = storage.list_nodes().await.unwrap();
+        assert_eq!(nodes.iter().map(|node| node.node_id.as_str()).collect::<Vec<_>>(), vec!["node-b"]);
+        assert!(matches!(nodes[0].status, NodeStatus::Maintenance));
+        let agents = storage.list_agents_by_node("node-b").await.unwrap();
+        assert_eq!(agents.iter().map(|agent| agent.agent_id.as_str()).collect::<Vec<_>>(), vec!["agent-1", "agent-2"]);
+        assert!(matches!(storage.get_agent("agent-1").await.unwrap().unwrap().status, AgentStatus::Running));
+        assert!(storage.get_agent("agent-3").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_stores_telemetry() {
+        let storage = memory_storage().await;
+        let old = telemetry_record("node-1", "node", 3);
+        let recent = telemetry_record("node-1", "node", 0);
+        storage.store_telemetry(&old).await.unwrap();
+        storage.store_telemetry_many(&[recent.clone(), telemetry_record("node-2", "node", 0)]).await.unwrap();
+
+        assert_eq!(storage.get_latest_telemetry("node-1").await.unwrap().unwrap().id, recent.id);
+        assert_eq!(storage.get_telemetry_history("node-1", 24 * 7).await.unwrap().len(), 2);
+        let retention = RetentionPolicy { default_days: 2, days_by_entity_type: HashMap::new() };
+        assert_eq!(storage.cleanup_old_telemetry(&retention).await.unwrap(), 1);
+        let history = storage.get_telemetry_history("node-1", 24 * 7).await.unwrap();
+        assert_eq!(history.iter().map(|record| record.id).collect::<Vec<_>>(), vec![recent.id]);
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_rocksdb_node_is_an_error_not_a_miss() {
+        let (storage, path) = rocksdb_storage("corrupt").await;
+        drop(storage);
+        {
+            let rocks = rocksdb::DB::open(&rocksdb::Options::default(), &path).unwrap();
+            rocks.put(b"node:node-1", [0xff; 3]).unwrap();
+        }
+
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = None;
+        config.use_rocksdb = true;
+        config.embedded_db_path = path.clone();
+        let storage = HybridStorage::new(config).await.unwrap();
+
+        assert!(matches!(storage.get_node("node-1").await, Err(StorageError::Deserialization(message)) if message.contains("node:node-1")));
+        assert!(storage.get_node("node-2").await.unwrap().is_none());
+        let _ = std::fs::remove_dir_all(path);
+    }
+
+    // Needs a scratch database: NEXUS_TEST_POSTGRES_URL=postgres://... cargo test --features postgres-tests
+    #[cfg(feature = "postgres-tests")]
+    #[tokio::test]
+    async fn test_malformed_postgres_node_status_is_an_error() {
+        let url = std::env::var("NEXUS_TEST_POSTGRES_URL").expect("NEXUS_TEST_POSTGRES_URL must be set");
+        let pool = sqlx::PgPool::connect(&url).await.unwrap();
+        sqlx::query(r#"
+            CREATE TABLE IF NOT EXISTS nodes (
+                node_id TEXT PRIMARY KEY, ip_address TEXT, proxy_listen_address TEXT, capabilities TEXT,
+                agent_type TEXT, status TEXT, last_seen TIMESTAMPTZ, created_at TIMESTAMPTZ, metadata JSONB
+            )
+        "#).execute(&pool).await.unwrap();
+        let node_id = format!("corrupt-{}", uuid::Uuid::new_v4());
+        sqlx::query("INSERT INTO nodes VALUES ($1, '127.0.0.1', '127.0.0.1:50052', 'CPU:4', 'PC', 'not-a-status', now(), now(), '{}')")
+            .bind(&node_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = Some(url);
+        config.use_rocksdb = false;
+        config.use_timescaledb = false;
+        let storage = HybridStorage::new(config).await.unwrap();
+        let result = storage.get_node(&node_id).await;
+
+        sqlx::query("DELETE FROM nodes WHERE node_id = $1").bind(&node_id).execute(&pool).await.unwrap();
+        assert!(matches!(result, Err(StorageError::Deserialization(_))), "got {:?}", result.map(|node| node.map(|node| node.status)));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_postgres_fails_startup_with_config_error() {
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = Some("postgres://nexus@127.0.0.1:1/nexus".to_string());
+        config.use_rocksdb = false;
+        config.acquire_timeout_secs = 1;
+
+        let error = HybridStorage::new(config).await.err().expect("startup should fail");
+        assert!(matches!(&error, StorageError::Config(message) if message.contains("unreachable")), "got {}", error);
+    }
+
+    #[cfg(feature = "postgres-tests")]
+    #[tokio::test]
+    async fn test_postgres_pool_respects_max_connections() {
+        let mut config = NexusConfig::default().database;
+        config.postgres_url = Some(std::env::var("NEXUS_TEST_POSTGRES_URL").expect("NEXUS_TEST_POSTGRES_URL must be set"));
+        config.use_rocksdb = false;
+        config.use_timescaledb = false;
+        config.max_connections = 2;
+        config.acquire_timeout_secs = 1;
+        let storage = HybridStorage::new(config).await.unwrap();
+        storage.ping().await.unwrap();
+        let pool = storage.postgres_pool().unwrap();
+
+        let _first = pool.acquire().await.unwrap();
+        let _second = pool.acquire().await.unwrap();
+        assert!(matches!(pool.acquire().await, Err(sqlx::Error::PoolTimedOut)));
+        assert_eq!(pool.size(), 2);
+    }
+}
@@ -0,0 +1,330 @@
+// Unit tests for TelemetryManager collection
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use async_trait::async_trait;
+    use nexus_prime_core::config::{NexusConfig, RetentionPolicy};
+    use nexus_prime_core::storage::*;
+    use nexus_prime_core::telemetry::{kbps, TaskCounters, TelemetryAverages, TelemetryError, TelemetryManager};
+    use nexus_prime_core::{AIAgent, ComputeNode, FabricManager};
+    use chrono::Utc;
+    use tokio::sync::{broadcast, mpsc, Mutex};
+
+    #[derive(Default)]
+    struct RecordingTelemetryStorage {
+        records: Mutex<Vec<TelemetryRecord>>,
+        batch_sizes: Mutex<Vec<usize>>,
+        unavailable: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait]
+    impl TelemetryStorage for RecordingTelemetryStorage {
+        async fn store_telemetry(&self, telemetry: &TelemetryRecord) -> StorageResult<()> {
+            self.records.lock().await.push(telemetry.clone());
+            Ok(())
+        }
+
+        async fn store_telemetry_many(&self, records: &[TelemetryRecord]) -> StorageResult<()> {
+            if self.unavailable.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(StorageError::Config("storage unavailable".to_string()));
+            }
+            self.batch_sizes.lock().await.push(records.len());
+            self.records.lock().await.extend_from_slice(records);
+            Ok(())
+        }
+
+        async fn get_latest_telemetry(&self, entity_id: &str) -> StorageResult<Option<TelemetryRecord>> {
+            Ok(self.records.lock().await.iter().rev().find(|r| r.entity_id == entity_id).cloned())
+        }
+
+        async fn get_telemetry_history(&self, entity_id: &str, _hours: u32) -> StorageResult<Vec<TelemetryRecord>> {
+            Ok(self.records.lock().await.iter().filter(|r| r.entity_id == entity_id).cloned().collect())
+        }
+
+        async fn cleanup_old_telemetry(&self, _retention: &RetentionPolicy) -> StorageResult<u64> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_system_telemetry_uses_configured_instance_id() {
+        let storage = Arc::new(RecordingTelemetryStorage::default());
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        config.instance_id = "core-eu-west-1".to_string();
+        let telemetry = TelemetryManager::new(config, storage.clone()).await.unwrap();
+
+        telemetry.collect_now().await.unwrap();
+
+        let records = storage.records.lock().await;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].entity_id, "core-eu-west-1");
+    }
+
+    #[tokio::test]
+    async fn test_fabric_metrics_report_task_counters() {
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        let counters = TaskCounters::new();
+        let telemetry = TelemetryManager::new(config, Arc::new(RecordingTelemetryStorage::default())).await.unwrap()
+            .with_task_counters(counters.clone());
+
+        counters.task_started();
+        counters.task_started();
+        counters.task_finished(true, true);
+
+        let (_, fabric_metrics) = telemetry.collect_now().await.unwrap();
+        assert_eq!(fabric_metrics.pending_tasks, 1);
+        assert_eq!(fabric_metrics.completed_tasks, 1);
+        assert_eq!(fabric_metrics.failed_tasks, 0);
+    }
+
+    #[test]
+    fn test_finished_task_totals_are_exported_as_counters() {
+        let recorder = metrics_exporter_prometheus::PrometheusBuilder::new().build_recorder();
+        let counters = TaskCounters::new();
+        metrics::with_local_recorder(&recorder, || {
+            counters.task_started();
+            counters.task_started();
+            counters.task_finished(true, true);
+            counters.task_finished(false, true);
+            counters.task_finished(true, false);
+        });
+
+        let rendered = recorder.handle().render();
+        assert!(rendered.contains("# TYPE fabric_tasks_completed_total counter\nfabric_tasks_completed_total 2"), "{}", rendered);
+        assert!(rendered.contains("# TYPE fabric_tasks_failed_total counter\nfabric_tasks_failed_total 1"), "{}", rendered);
+        assert!(rendered.contains("# TYPE fabric_tasks_pending gauge\nfabric_tasks_pending 0"), "{}", rendered);
+    }
+
+    fn node(id: &str, status: &str) -> ComputeNode {
+        ComputeNode {
+            id: id.to_string(),
+            node_type: "PC".to_string(),
+            last_seen: Utc::now(),
+            status: status.to_string(),
+            capabilities: "CPU:4,RAM:16GB".to_string(),
+            ip_address: "127.0.0.1".to_string(),
+            proxy_listen_address: None,
+            last_error: None,
+            last_error_at: None,
+            labels: Default::default(),
+            metadata: Default::default(),
+            resources: Default::default(),
+            registered_by: None,
+            draining: false,
+        }
+    }
+
+    fn agent(id: &str, status: &str) -> AIAgent {
+        AIAgent {
+            id: id.to_string(),
+            name: "Synthesizer".to_string(),
+            agent_type: "Synthesizer".to_string(),
+            assigned_node_id: Some("node-1".to_string()),
+            status: status.to_string(),
+            current_task: None,
+            task_progress: None,
+            pinned: false,
+            env: Default::default(),
+            last_active: Utc::now(),
+            fleet_id: None,
+            orphaned_at: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fabric_metrics_count_nodes_and_agents_each_tick() {
+        let (event_bus_tx, _) = broadcast::channel(10);
+        let (event_stream_tx, _) = broadcast::channel(10);
+        let (command_tx, _command_rx) = mpsc::channel(10);
+        let fabric = FabricManager::new(event_bus_tx, event_stream_tx, command_tx, sled::Config::new().temporary(true).open().unwrap());
+        fabric.register_node(node("node-1", "Online")).await.unwrap();
+        fabric.register_node(node("node-2", "Unreachable")).await.unwrap();
+        fabric.register_ai_agent(agent("agent-1", "Running")).await.unwrap();
+        fabric.register_ai_agent(agent("agent-2", "Running")).await.unwrap();
+        fabric.register_ai_agent(agent("agent-3", "Stopped")).await.unwrap();
+
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        let telemetry = TelemetryManager::new(config, Arc::new(RecordingTelemetryStorage::default())).await.unwrap()
+            .with_fabric_manager(fabric.clone());
+        telemetry.record_operation("deploy_agent", std::time::Duration::from_millis(10), true).await;
+        telemetry.record_operation("deploy_agent", std::time::Duration::from_millis(30), true).await;
+
+        let tasks = telemetry.start_collection_tasks();
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        for task in tasks {
+            task.abort();
+        }
+
+        let metrics = telemetry.get_fabric_metrics().await;
+        assert_eq!((metrics.total_nodes, metrics.online_nodes), (2, 1));
+        assert_eq!((metrics.total_agents, metrics.running_agents), (3, 2));
+        assert!((metrics.average_task_duration_ms - 20.0).abs() < 0.01);
+    }
+
+    #[tokio::test]
+    async fn test_custom_metrics_are_written_in_batches() {
+        let storage = Arc::new(RecordingTelemetryStorage::default());
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        config.telemetry_batch_size = 4;
+        let telemetry = TelemetryManager::new(config, storage.clone()).await.unwrap();
+
+        for value in 0..2 {
+            telemetry.record_custom_metric("agent-1", "queue_depth", value as f32).await;
+        }
+        assert!(storage.records.lock().await.is_empty());
+
+        // The system record joins the two waiting custom metrics in one write
+        telemetry.collect_now().await.unwrap();
+        for value in 0..4 {
+            telemetry.record_custom_metric("agent-1", "queue_depth", value as f32).await;
+        }
+        assert_eq!(*storage.batch_sizes.lock().await, vec![3, 4]);
+        assert_eq!(storage.records.lock().await.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_failed_telemetry_batches_are_retried_up_to_a_bound() {
+        let storage = Arc::new(RecordingTelemetryStorage::default());
+        storage.unavailable.store(true, std::sync::atomic::Ordering::SeqCst);
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        config.telemetry_batch_size = 2;
+        let telemetry = TelemetryManager::new(config, storage.clone()).await.unwrap();
+
+        // Ten batches' worth are kept, so the oldest five of 25 records are dropped
+        for value in 0..25 {
+            telemetry.record_custom_metric("agent-1", "queue_depth", value as f32).await;
+        }
+        assert!(telemetry.flush().await.is_err());
+
+        storage.unavailable.store(false, std::sync::atomic::Ordering::SeqCst);
+        telemetry.flush().await.unwrap();
+        let values: Vec<f32> = storage.records.lock().await.iter().map(|record| record.custom_metrics["queue_depth"]).collect();
+        assert_eq!(values, (5..25).map(|value| value as f32).collect::<Vec<_>>());
+        assert_eq!(*storage.batch_sizes.lock().await, vec![20]);
+    }
+
+    #[test]
+    fn test_averages_skip_missing_metrics() {
+        let record = |cpu: f32, memory: Option<f32>| TelemetryRecord {
+            id: uuid::Uuid::new_v4(),
+            entity_id: "node-1".to_string(),
+            entity_type: "node".to_string(),
+            timestamp: chrono::Utc::now(),
+            cpu_utilization: Some(cpu),
+            memory_utilization: memory,
+            network_in_kbps: None,
+            network_out_kbps: None,
+            custom_metrics: Default::default(),
+        };
+        let records = vec![record(10.0, Some(40.0)), record(20.0, None), record(30.0, Some(60.0))];
+
+        let averages = TelemetryAverages::from_records(&records);
+        assert_eq!(averages.cpu_utilization, Some(20.0));
+        assert_eq!(averages.memory_utilization, Some(50.0));
+        assert_eq!(averages.network_in_kbps, None);
+    }
+
+    #[test]
+    fn test_kbps_is_kilobits_per_second_over_the_interval() {
+        // 125,000 bytes is one megabit
+        assert_eq!(kbps(125_000, 1.0), 1000.0);
+        assert_eq!(kbps(125_000, 10.0), 100.0);
+        assert_eq!(kbps(125_000, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_instance_id_defaults_to_hostname() {
+        let config = NexusConfig::default();
+        assert!(!config.telemetry.instance_id.is_empty());
+        assert_ne!(config.telemetry.instance_id, "system");
+    }
+
+    #[tokio::test]
+    async fn test_system_metrics_read_from_host_as_deltas() {
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        let telemetry = TelemetryManager::new(config, Arc::new(RecordingTelemetryStorage::default())).await.unwrap();
+
+        let (first, _) = telemetry.collect_now().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        let (second, _) = telemetry.collect_now().await.unwrap();
+
+        assert!(second.timestamp > first.timestamp);
+        // Nothing precedes the first collection to diff against
+        assert_eq!((first.network_in_bytes, first.network_out_bytes), (0, 0));
+        assert_eq!((first.disk_read_bytes, first.disk_written_bytes), (0, 0));
+        for metrics in [&first, &second] {
+            assert!((0.0..=100.0).contains(&metrics.cpu_usage));
+            assert!((0.0..=100.0).contains(&metrics.memory_usage));
+            assert!((0.0..=100.0).contains(&metrics.disk_usage));
+            assert!(metrics.process_cpu_usage >= 0.0);
+            assert!(metrics.memory_total > 0);
+            assert!(metrics.memory_available <= metrics.memory_total);
+            assert!(metrics.disk_available <= metrics.disk_total);
+            assert!(metrics.load_average.iter().all(|load| *load >= 0.0));
+            assert!(metrics.process_count >= 1);
+            assert!(metrics.thread_count >= metrics.process_count);
+        }
+        if cfg!(target_os = "linux") {
+            assert!(second.file_descriptor_count >= 3);
+        }
+        // Deltas over a quarter second stay far below the counters' running totals
+        assert!(second.network_in_bytes < 1024 * 1024 * 1024);
+        assert_eq!(telemetry.get_system_metrics().await.timestamp, second.timestamp);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_system_metrics_collected_at_configured_interval() {
+        let storage = Arc::new(RecordingTelemetryStorage::default());
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        config.system_metrics_interval_seconds = 1;
+        let telemetry = TelemetryManager::new(config, storage.clone()).await.unwrap();
+
+        let tasks = telemetry.start_collection_tasks();
+        tokio::time::sleep(std::time::Duration::from_millis(3500)).await;
+        for task in tasks {
+            task.abort();
+        }
+
+        // Ticks at 0s, 1s, 2s and 3s
+        assert_eq!(storage.records.lock().await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_zero_collection_interval_rejected() {
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        config.fabric_metrics_interval_seconds = 0;
+        let result = TelemetryManager::new(config, Arc::new(RecordingTelemetryStorage::default())).await;
+        assert!(matches!(result, Err(TelemetryError::Config(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unique_operations_are_evicted_least_recently_used_first() {
+        let mut config = NexusConfig::default().telemetry;
+        config.enable_prometheus = false;
+        config.max_tracked_operations = 10;
+        let telemetry = TelemetryManager::new(config, Arc::new(RecordingTelemetryStorage::default())).await.unwrap();
+        let duration = std::time::Duration::from_millis(5);
+
+        // A burst of unique labels, with one operation that keeps being used throughout
+        for i in 0..100 {
+            telemetry.record_operation("deploy", duration, true).await;
+            telemetry.record_operation(&format!("request-{}", i), duration, i % 2 == 0).await;
+        }
+
+        let summary = telemetry.get_performance_summary().await;
+        assert_eq!(summary.len(), 10);
+        assert_eq!(summary["deploy"].total_count, 100);
+        assert!(summary.contains_key("request-99"));
+        assert!(!summary.contains_key("request-0"));
+        assert_eq!(summary["request-99"].error_count, 1);
+    }
+}
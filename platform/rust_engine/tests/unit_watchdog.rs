@@ -0,0 +1,48 @@
+// Unit tests for the background task watchdog
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use nexus_prime_core::watchdog::Watchdog;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_task_that_stops_heartbeating_is_flagged() {
+        let watchdog = Watchdog::new();
+        let heartbeat = watchdog.register("pruner", Duration::from_secs(10));
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        heartbeat.beat();
+        tokio::time::advance(Duration::from_secs(8)).await;
+        assert!(watchdog.check().is_empty());
+        assert!(watchdog.is_healthy("pruner"));
+
+        // No heartbeat for longer than the expected interval
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert_eq!(watchdog.check(), vec!["pruner".to_string()]);
+        assert!(!watchdog.is_healthy("pruner"));
+        assert_eq!(watchdog.unhealthy_tasks(), vec!["pruner".to_string()]);
+
+        heartbeat.beat();
+        assert!(watchdog.check().is_empty());
+        assert!(watchdog.is_healthy("pruner"));
+        assert!(watchdog.unhealthy_tasks().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_exited_task_is_restarted() {
+        let watchdog = Watchdog::new();
+        let starts = Arc::new(AtomicUsize::new(0));
+        let spawned = Arc::clone(&starts);
+        watchdog.spawn_restartable("processor", Duration::from_secs(10), move |_heartbeat| {
+            spawned.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async {})
+        });
+
+        // Let the task run to completion
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(watchdog.check(), vec!["processor".to_string()]);
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+    }
+}
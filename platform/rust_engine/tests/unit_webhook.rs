@@ -0,0 +1,121 @@
+// Unit tests for webhook notifications of fabric events
+
+#[cfg(test)]
+mod tests {
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use nexus_prime_core::config::{EventSeverity, WebhookConfig};
+    use nexus_prime_core::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Mutex;
+
+    // Records every payload it is sent, failing the first `failures` requests with a 503
+    #[derive(Clone, Default)]
+    struct MockEndpoint {
+        received: Arc<Mutex<Vec<serde_json::Value>>>,
+        failures: Arc<Mutex<usize>>,
+    }
+
+    async fn receive(State(endpoint): State<MockEndpoint>, Json(payload): Json<serde_json::Value>) -> StatusCode {
+        endpoint.received.lock().await.push(payload);
+        let mut failures = endpoint.failures.lock().await;
+        if *failures > 0 {
+            *failures -= 1;
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+        StatusCode::OK
+    }
+
+    async fn spawn_endpoint(endpoint: MockEndpoint) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = Router::new().route("/hook", post(receive)).with_state(endpoint);
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{}/hook", addr)
+    }
+
+    fn webhook(url: String) -> WebhookConfig {
+        WebhookConfig {
+            url,
+            event_types: vec![],
+            min_severity: Some(EventSeverity::Critical),
+            max_attempts: 3,
+            max_per_minute: 30,
+        }
+    }
+
+    async fn wait_for_posts(endpoint: &MockEndpoint, count: usize) -> Vec<serde_json::Value> {
+        for _ in 0..50 {
+            if endpoint.received.lock().await.len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        endpoint.received.lock().await.clone()
+    }
+
+    #[tokio::test]
+    async fn test_critical_event_is_posted_and_retried_while_info_events_are_not() {
+        let endpoint = MockEndpoint::default();
+        *endpoint.failures.lock().await = 1;
+        let notifier = WebhookNotifier::spawn(vec![webhook(spawn_endpoint(endpoint.clone()).await)]).unwrap();
+
+        notifier.publish(&FabricManager::convert_event(&InternalFabricEvent::PersistenceRecovered));
+        notifier.publish(&FabricManager::convert_event(&InternalFabricEvent::PersistenceDegraded(3)));
+
+        // The first delivery fails, the retry gets through
+        let received = wait_for_posts(&endpoint, 2).await;
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0], received[1]);
+        assert_eq!(received[1]["event_type"], "PERSISTENCE_DEGRADED");
+        assert_eq!(received[1]["severity"], "critical");
+        assert_eq!(received[1]["metadata"]["consecutive_failures"], "3");
+    }
+
+    #[tokio::test]
+    async fn test_events_past_the_rate_limit_are_dropped() {
+        let endpoint = MockEndpoint::default();
+        let notifier = WebhookNotifier::spawn(vec![WebhookConfig {
+            event_types: vec!["NODE_PRUNED".to_string()],
+            min_severity: None,
+            max_per_minute: 2,
+            ..webhook(spawn_endpoint(endpoint.clone()).await)
+        }]).unwrap();
+
+        for node_id in ["node-1", "node-2", "node-3"] {
+            notifier.publish(&FabricManager::convert_event(&InternalFabricEvent::NodePruned(node_id.to_string())));
+        }
+        notifier.publish(&FabricManager::convert_event(&InternalFabricEvent::PersistenceDegraded(3)));
+
+        wait_for_posts(&endpoint, 3).await;
+        let messages: Vec<_> = endpoint.received.lock().await.iter().map(|payload| payload["message"].clone()).collect();
+        assert_eq!(messages, vec!["Node pruned: node-1", "Node pruned: node-2"]);
+    }
+
+    #[test]
+    fn test_outage_slo_and_deploy_failure_events_carry_a_severity() {
+        let node_status = |status: &str| {
+            let event = InternalFabricEvent::NodeStatusUpdate("node-1".to_string(), status.to_string(), None);
+            webhook::event_severity(&FabricManager::convert_event(&event))
+        };
+        assert_eq!(node_status("Unreachable"), EventSeverity::Critical);
+        assert_eq!(node_status("Error"), EventSeverity::Critical);
+        assert_eq!(node_status("Recovering"), EventSeverity::Warning);
+        assert_eq!(node_status("Online"), EventSeverity::Info);
+
+        let breach = InternalFabricEvent::SloBreached { name: "deploy_success".to_string(), current: 0.8, target: 0.99 };
+        assert_eq!(webhook::event_severity(&FabricManager::convert_event(&breach)), EventSeverity::Critical);
+        let failures = InternalFabricEvent::DeployFailuresRepeated(3);
+        assert_eq!(webhook::event_severity(&FabricManager::convert_event(&failures)), EventSeverity::Critical);
+    }
+
+    #[test]
+    fn test_webhook_urls_are_redacted() {
+        let mut config = NexusConfig::default();
+        config.telemetry.webhooks = vec![webhook("https://hooks.slack.com/services/T000/B000/secret-token".to_string())];
+
+        let redacted = config.redacted();
+        assert_eq!(redacted.telemetry.webhooks[0].url, nexus_prime_core::config::REDACTED);
+        assert!(!serde_json::to_string(&redacted).unwrap().contains("secret-token"));
+    }
+}